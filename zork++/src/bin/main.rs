@@ -2,7 +2,11 @@ use clap::Parser;
 use color_eyre::eyre::Context;
 use color_eyre::Result;
 use env_logger::Target;
-use zork::{cli::input::CliArgs, utils::logger::config_logger, worker::run_zork};
+use zork::{
+    cli::input::{CliArgs, Command},
+    utils::logger::config_logger,
+    worker::run_zork,
+};
 
 /// The entry point for the binary generated
 /// for the program
@@ -11,8 +15,13 @@ fn main() -> Result<()> {
     let process_start_time = std::time::Instant::now();
 
     let cli_args = CliArgs::parse();
-    config_logger(cli_args.verbose, Target::Stdout)
-        .with_context(|| "Error configuring the logger")?;
+    // The schema and the commands of a dry run are written to stdout, so they can be redirected to a file
+    let log_target = if cli_args.command.eq(&Command::Schema) || cli_args.dry_run {
+        Target::Stderr
+    } else {
+        Target::Stdout
+    };
+    config_logger(cli_args.verbose, log_target).with_context(|| "Error configuring the logger")?;
 
     log::info!("Launching a new Zork++ program");
     match run_zork(&cli_args) {
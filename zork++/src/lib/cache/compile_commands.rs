@@ -40,7 +40,10 @@ pub(crate) fn map_generated_commands_to_compilation_db(
             .chain(flyweight_data.general_args.as_ref().iter())
             .chain(flyweight_data.shared_args.as_ref().iter())
             .chain(flyweight_data.std_references.iter())
+            .chain(flyweight_data.header_units_refs.iter())
             .chain(flyweight_data.compile_but_dont_link.iter())
+            .chain(flyweight_data.compile_options.iter())
+            .chain(flyweight_data.compile_args.iter())
             .chain(source_command_line.args.iter())
             .collect::<Vec<&Argument>>();
 
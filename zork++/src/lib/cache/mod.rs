@@ -3,13 +3,15 @@
 pub mod compile_commands;
 
 use chrono::{DateTime, Utc};
-use color_eyre::{eyre::Context, Result};
+use color_eyre::{
+    eyre::{Context, ContextCompat},
+    Result,
+};
 
 use std::collections::HashMap;
 use std::fmt::Debug;
 
 use std::{
-    fs::File,
     path::{Path, PathBuf},
     time::Instant,
 };
@@ -39,13 +41,7 @@ pub fn load<'a>(
     project_root: &Path,
 ) -> Result<ZorkCache<'a>> {
     let compiler: CppCompiler = config.compiler.cpp_compiler.into();
-    let output_dir = Path::new(project_root).join(
-        config
-            .build
-            .as_ref()
-            .and_then(|build_attr| build_attr.output_dir)
-            .unwrap_or(dir_names::DEFAULT_OUTPUT_DIR),
-    );
+    let output_dir = utils::reader::resolve_output_dir(config, project_root);
     let cache_path = output_dir.join(constants::ZORK).join(dir_names::CACHE);
 
     let cache_file_path = cache_path
@@ -97,7 +93,11 @@ impl<'a> ZorkCache<'a> {
             TranslationUnitKind::SourceFile(for_target) => {
                 self.get_source_cmd(translation_unit, for_target)
             }
+            TranslationUnitKind::PrecompiledHeader(for_target) => {
+                self.get_pch_cmd(translation_unit, for_target)
+            }
             TranslationUnitKind::SystemHeader => self.get_system_module_cmd(translation_unit),
+            TranslationUnitKind::HeaderUnit => self.get_header_unit_cmd(translation_unit),
             TranslationUnitKind::ModularStdLib(stdlib_mode) => match stdlib_mode {
                 StdLibMode::Cpp => self.get_cpp_stdlib_cmd(),
                 StdLibMode::CCompat => self.get_ccompat_stdlib_cmd(),
@@ -105,6 +105,53 @@ impl<'a> ZorkCache<'a> {
         }
     }
 
+    /// Stores a new generated [`SourceCommandLine`] in the collection that corresponds
+    /// to its [`TranslationUnitKind`]
+    pub fn add_cmd_for_translation_unit_kind(
+        &mut self,
+        cmd: SourceCommandLine<'a>,
+        translation_unit_kind: &TranslationUnitKind<'a>,
+    ) -> Result<()> {
+        let modules = &mut self.generated_commands.modules;
+        match translation_unit_kind {
+            TranslationUnitKind::ModuleInterface => modules.interfaces.push(cmd),
+            TranslationUnitKind::ModuleImplementation => modules.implementations.push(cmd),
+            TranslationUnitKind::SourceFile(for_target) => self
+                .generated_commands
+                .targets
+                .get_mut(*for_target)
+                .with_context(|| {
+                    format!(
+                        "{}: {:?}",
+                        error_messages::TARGET_ENTRY_NOT_FOUND,
+                        for_target
+                    )
+                })?
+                .sources
+                .push(cmd),
+            TranslationUnitKind::PrecompiledHeader(for_target) => {
+                self.generated_commands
+                    .targets
+                    .get_mut(*for_target)
+                    .with_context(|| {
+                        format!(
+                            "{}: {:?}",
+                            error_messages::TARGET_ENTRY_NOT_FOUND,
+                            for_target
+                        )
+                    })?
+                    .pch = Some(cmd)
+            }
+            TranslationUnitKind::SystemHeader => modules.system_modules.push(cmd),
+            TranslationUnitKind::HeaderUnit => modules.header_units.push(cmd),
+            TranslationUnitKind::ModularStdLib(stdlib_mode) => {
+                self.set_cpp_stdlib_cmd_by_kind(*stdlib_mode, Some(cmd))
+            }
+        };
+
+        Ok(())
+    }
+
     fn get_module_ifc_cmd<T: TranslationUnit<'a>>(
         &mut self,
         module_interface: &T,
@@ -143,6 +190,18 @@ impl<'a> ZorkCache<'a> {
             })
     }
 
+    fn get_pch_cmd<T: TranslationUnit<'a>>(
+        &mut self,
+        header: &T,
+        for_target: &TargetIdentifier<'a>,
+    ) -> Option<&mut SourceCommandLine<'a>> {
+        self.generated_commands
+            .targets
+            .get_mut(for_target)
+            .and_then(|target| target.pch.as_mut())
+            .filter(|cached_tu| header.path().eq(&cached_tu.path()))
+    }
+
     /// Gets the target [`SystemModule`] generated [`SourceCommandLine`] from the cache
     ///
     /// NOTE: While we don't implement the lookup of the directory of the installed system headers,
@@ -158,6 +217,17 @@ impl<'a> ZorkCache<'a> {
             .find(|cached_tu| system_module.file_stem().eq(cached_tu.filename()))
     }
 
+    fn get_header_unit_cmd<T: TranslationUnit<'a>>(
+        &mut self,
+        header_unit: &T,
+    ) -> Option<&mut SourceCommandLine<'a>> {
+        self.generated_commands
+            .modules
+            .header_units
+            .iter_mut()
+            .find(|cached_tu| header_unit.path().eq(&cached_tu.path()))
+    }
+
     pub fn get_cpp_stdlib_cmd_by_kind(
         &mut self,
         stdlib_mode: StdLibMode,
@@ -194,6 +264,8 @@ impl<'a> ZorkCache<'a> {
             msvc::load_metadata(self, program_data)?
         } else if compiler.eq(&CppCompiler::CLANG) {
             clang::load_metadata(self, program_data)?
+        } else if compiler.eq(&CppCompiler::CLANG_CL) {
+            clang::load_clang_cl_metadata(self, program_data)?
         }
 
         Ok(())
@@ -225,6 +297,7 @@ impl<'a> ZorkCache<'a> {
             CppCompiler::MSVC => &self.compilers_metadata.msvc.env_vars,
             CppCompiler::CLANG => &self.compilers_metadata.clang.env_vars,
             CppCompiler::GCC => &self.compilers_metadata.gcc.env_vars,
+            CppCompiler::CLANG_CL => &self.compilers_metadata.clang_cl.env_vars,
         }
     }
 
@@ -259,6 +332,7 @@ impl<'a> ZorkCache<'a> {
         latest_commands.modules.interfaces.len()
             + latest_commands.modules.implementations.len()
             + latest_commands.modules.system_modules.len()
+            + latest_commands.modules.header_units.len()
             + 2 // the cpp_stdlib and the c_compat_stdlib
             + latest_commands.targets.values().flat_map(|target| target.sources.iter()).count()
     }
@@ -272,11 +346,18 @@ pub struct CacheMetadata {
     pub last_program_execution: DateTime<Utc>,
     pub cache_file_path: PathBuf,
     pub project_model_file_path: PathBuf,
+    // The inputs of the cached model that aren't declared on the configuration files
+    #[serde(default)]
+    pub profile: String,
+    #[serde(default)]
+    pub config_env_vars: EnvVars,
     // Internal helpers
     #[serde(skip)]
     pub generate_compilation_database: bool,
     #[serde(skip)]
     pub cfg_modified: bool,
+    #[serde(skip)]
+    pub expanded_values_changed: bool,
 }
 
 /// Type alias for the underlying key-value based collection of environmental variables
@@ -288,6 +369,7 @@ pub struct CompilersMetadata<'a> {
     pub msvc: MsvcMetadata<'a>,
     pub clang: ClangMetadata,
     pub gcc: GccMetadata,
+    pub clang_cl: ClangMetadata,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
@@ -325,7 +407,7 @@ pub struct GccMetadata {
     pub env_vars: EnvVars,
 }
 
-/// Helper procedures to process cache data for Microsoft's MSVC
+/// Helper procedures to process cache data for Clang and its MSVC compatible driver, `clang-cl`
 mod clang {
     use color_eyre::eyre::{self, Context, ContextCompat, Result};
     use regex::Regex;
@@ -349,14 +431,7 @@ mod clang {
 
         // TODO: if the driver changes on the cfg, how do we know that we have to process this
         // cached information again? Just because cache and model are rebuilt again? Can't recall it
-        let clang_cmd_info = std::process::Command::new(OsStr::new(driver.as_ref()))
-            .arg("-###")
-            .output()
-            .with_context(|| error_messages::clang::FAILURE_READING_CLANG_DRIVER_INFO)?;
-        // Combine stdout and stderr into a single output string
-        let combined_output = String::from_utf8_lossy(&clang_cmd_info.stdout).to_string()
-            + &String::from_utf8_lossy(&clang_cmd_info.stderr);
-        // Typically, the useful information will be in stderr for `clang++ -###` commands
+        let combined_output = query_frontend_driver_info(&driver)?;
         cache.compilers_metadata.clang = process_frontend_driver_info(&combined_output)?;
 
         if cache.compilers_metadata.clang.major > 17 {
@@ -366,6 +441,39 @@ mod clang {
         Ok(())
     }
 
+    /// Gathers the metadata of the `clang-cl` driver. The output of the `-###` query is the same
+    /// as the one of the regular Clang driver, but there's no discovery of the modular standard
+    /// libraries, since `clang-cl` works against the MSVC's STL
+    pub(crate) fn load_clang_cl_metadata(
+        cache: &mut ZorkCache,
+        program_data: &ZorkModel,
+    ) -> color_eyre::Result<()> {
+        if cache.compilers_metadata.clang_cl.major != 0 {
+            log::debug!("Clang-cl metadata already gathered on the cache");
+            return Ok(());
+        }
+
+        let compiler = program_data.compiler.cpp_compiler;
+        let driver = compiler.get_driver(&program_data.compiler);
+
+        let combined_output = query_frontend_driver_info(&driver)?;
+        cache.compilers_metadata.clang_cl = process_frontend_driver_info(&combined_output)?;
+
+        Ok(())
+    }
+
+    /// Invokes the given driver with the `-###` flag, returning the combination of its stdout
+    /// and stderr, since typically, the useful information will be in stderr for such commands
+    fn query_frontend_driver_info(driver: &str) -> Result<String> {
+        let clang_cmd_info = std::process::Command::new(OsStr::new(driver))
+            .arg("-###")
+            .output()
+            .with_context(|| error_messages::clang::FAILURE_READING_CLANG_DRIVER_INFO)?;
+
+        Ok(String::from_utf8_lossy(&clang_cmd_info.stdout).to_string()
+            + &String::from_utf8_lossy(&clang_cmd_info.stderr))
+    }
+
     fn discover_modular_stdlibs(program_data: &ZorkModel<'_>, cache: &mut ZorkCache) -> Result<()> {
         let out_dir = &program_data.build.output_dir;
 
@@ -629,11 +737,13 @@ mod msvc {
                 path: vs_stdlib_path.clone(),
                 file_stem: Cow::Borrowed("std"),
                 extension: compiler.default_module_extension(),
+                ..Default::default()
             };
             msvc.vs_ccompat_stdlib_path = SourceFile {
                 path: vs_stdlib_path,
                 file_stem: Cow::Borrowed("std.compat"),
                 extension: compiler.default_module_extension(),
+                ..Default::default()
             };
             let modular_stdlib_byproducts_path = Path::new(&output_dir)
                 .join(compiler.as_ref())
@@ -697,7 +807,8 @@ pub(crate) mod helpers {
         cache_file_path: PathBuf,
         compiler: CppCompiler,
     ) -> Result<ZorkCache<'a>> {
-        File::create(&cache_file_path).with_context(|| error_messages::FAILURE_LOADING_CACHE)?;
+        // The cache file is only written when the cache is saved, so the runs that don't save it
+        // (a check, or a failed one) don't leave an empty cache file behind for the next ones
         helpers::initialize_cache(cache_path, cache_file_path, compiler)
     }
 
@@ -752,6 +863,9 @@ pub(crate) mod helpers {
         ) || remove_if_needed_from_cache_and_count_changes(
             &mut cache.generated_commands.modules.implementations,
             &program_data.modules.implementations,
+        ) || remove_if_needed_from_cache_and_count_changes(
+            &mut cache.generated_commands.modules.header_units,
+            &program_data.modules.header_units,
         ) || {
             for (target_name, target_data) in cache.generated_commands.targets.iter_mut() {
                 let changes = remove_if_needed_from_cache_and_count_changes(
@@ -18,6 +18,30 @@ use crate::project_model;
 /// assert_eq!(parser.clear_cache, true);
 /// assert_eq!(parser.driver_path, Some(String::from("/usr/bin/clang-15/clang++")));
 /// assert_eq!(parser.targets, Some(vec![String::from("executable"), String::from("tests")]));
+/// assert!(parser.config_files.is_empty());
+/// assert!(!parser.keep_going);
+/// assert!(!parser.dry_run);
+/// assert_eq!(parser.profile, "debug");
+///
+/// let parser = CliArgs::parse_from(["", "-C", "zork_linux.toml", "--config", "../other/zork.toml", "build"]);
+/// assert_eq!(parser.config_files, vec![String::from("zork_linux.toml"), String::from("../other/zork.toml")]);
+///
+/// let parser = CliArgs::parse_from(["", "-k", "build"]);
+/// assert!(parser.keep_going);
+/// let parser = CliArgs::parse_from(["", "--keep-going", "build"]);
+/// assert!(parser.keep_going);
+///
+/// let parser = CliArgs::parse_from(["", "--dry-run", "build"]);
+/// assert!(parser.dry_run);
+///
+/// let parser = CliArgs::parse_from(["", "--profile", "release", "build"]);
+/// assert_eq!(parser.profile, "release");
+///
+/// let parser = CliArgs::parse_from(["", "check"]);
+/// assert_eq!(parser.command, Command::Check);
+///
+/// let parser = CliArgs::parse_from(["", "schema"]);
+/// assert_eq!(parser.command, Command::Schema);
 ///
 // Create Template Project
 /// let parser = CliArgs::parse_from(["", "new", "example", "--git", "--compiler", "clang"]);
@@ -69,6 +93,46 @@ pub struct CliArgs {
         help = "Filters between the Zork++ configuration files for the project, taking only the ones that contains in their name the value passed in"
     )]
     pub match_files: Option<String>,
+
+    #[arg(
+        short = 'C',
+        long = "config",
+        value_name = "PATH",
+        help = "The path to a Zork++ configuration file, which skips the discovery of the configuration files of the project. Relative paths are relative to the project root. Can be repeated"
+    )]
+    pub config_files: Vec<String>,
+
+    #[arg(
+        long,
+        help = "The path to a toml file with a [toolchain] table, which replaces the one declared on the configuration files. A relative path is relative to the project root"
+    )]
+    pub toolchain: Option<String>,
+
+    #[arg(
+        long,
+        help = "A program which the compile commands will be prefixed with, like ccache or sccache"
+    )]
+    pub launcher: Option<String>,
+
+    #[arg(
+        short = 'k',
+        long,
+        help = "Keeps building the translation units, targets and configuration files that don't depend on the failed ones, reporting all the failures at the end"
+    )]
+    pub keep_going: bool,
+
+    #[arg(
+        long,
+        help = "Prints the commands that would be executed, in their execution order and with the reason for executing them, without running any of them"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        default_value = "debug",
+        help = "The name of the build profile, available as the ${profile} variable on the configuration files"
+    )]
+    pub profile: String,
 }
 
 /// [`Command`] -  The core enum commands
@@ -81,6 +145,11 @@ pub enum Command {
     Run,
     /// Executes the tests under the specified directory in the config file
     Test,
+    /// Validates the configuration files and generates the commands of the project, without
+    /// building anything
+    Check,
+    /// Prints the JSON Schema of the configuration files
+    Schema,
     /// Creates a new template project
     New {
         #[arg(help = "Name of the new project")]
@@ -5,24 +5,86 @@ use std::ffi::OsStr;
 use std::{path::Path, process::ExitStatus};
 
 use crate::cache::EnvVars;
-use crate::domain::commands::arguments::Argument;
-use crate::domain::commands::command_lines::ModulesCommands;
+use crate::cli::output::hooks::{self, HookStage};
+use crate::domain::commands::arguments::{Argument, Arguments};
+use crate::domain::commands::command_lines::{Commands, ModulesCommands, SourceCommandLine};
 use crate::domain::flyweight_data::FlyweightData;
-use crate::domain::target::{Target, TargetIdentifier};
-use crate::domain::translation_unit::TranslationUnitStatus;
-use crate::utils::constants::error_messages;
-use crate::{
-    project_model::{compiler::CppCompiler, ZorkModel},
-    utils::constants,
+use crate::domain::reachability::ModulesReachability;
+use crate::domain::target::{Target, TargetIdentifier, TargetKind};
+use crate::domain::translation_unit::{BuildReason, TranslationUnitStatus};
+use crate::project_model::{
+    compiler::{CompilerModel, CppCompiler},
+    ZorkModel,
 };
-use color_eyre::eyre::ContextCompat;
+use crate::utils::constants::error_messages;
+use color_eyre::eyre::{eyre, ContextCompat};
 use color_eyre::{eyre::Context, Report, Result};
 use indexmap::IndexMap;
 
+/// Runs the rules declared by the user that generate files of the project, whenever they are
+/// pending to be executed on the current iteration
+pub fn run_generators(
+    program_data: &ZorkModel<'_>,
+    generators: &mut [SourceCommandLine<'_>],
+    env_vars: &EnvVars,
+    keep_going: bool,
+) -> Result<()> {
+    let compiler = program_data.compiler.cpp_compiler;
+
+    for generator in &program_data.generators {
+        let generated_cmd = generators
+            .iter_mut()
+            .find(|scl| generator.outputs[0].eq(&scl.path()))
+            .with_context(|| error_messages::FAILURE_GENERATORS)?;
+
+        if generated_cmd
+            .status
+            .ne(&TranslationUnitStatus::PendingToBuild)
+        {
+            continue;
+        }
+
+        log::info!("Running the generator: {:?}", generator.command);
+        generator
+            .outputs
+            .iter()
+            .filter_map(|output| output.parent())
+            .try_for_each(std::fs::create_dir_all)
+            .with_context(|| error_messages::FAILURE_GENERATORS)?;
+
+        let (program, args) = generated_cmd.args.split_first().with_context(|| {
+            format!("Found an empty generator command: {:?}", generator.command)
+        })?;
+        let args = args.iter().collect::<Arguments>();
+
+        let result = spawn_command(compiler, program.value(), &args, env_vars);
+        generated_cmd.status = TranslationUnitStatus::from(&result);
+
+        let subject = format!("the generator {:?}", generator.command);
+        if !helpers::check_command_result(result, keep_going, &subject)? {
+            continue;
+        }
+        if let Some(missing) = generator.outputs.iter().find(|output| !output.exists()) {
+            generated_cmd.status = TranslationUnitStatus::Error;
+            helpers::handle_failure(
+                keep_going,
+                eyre!(
+                    "The generator {:?} didn't generate its declared output: {missing:?}",
+                    generator.command
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn run_modules_generated_commands(
     program_data: &ZorkModel<'_>,
     flyweight_data: &FlyweightData,
     modules_generated_commands: &mut ModulesCommands<'_>,
+    reachability: &ModulesReachability,
+    keep_going: bool,
 ) -> Result<()> {
     log::info!("Proceeding to execute the generated modules commands...");
 
@@ -30,8 +92,21 @@ pub fn run_modules_generated_commands(
         program_data,
         flyweight_data,
         modules_generated_commands,
+        keep_going,
+    )?;
+    helpers::process_header_units_commands(
+        program_data,
+        flyweight_data,
+        modules_generated_commands,
+        keep_going,
     )?;
-    helpers::process_user_modules_commands(program_data, flyweight_data, modules_generated_commands)
+    helpers::process_user_modules_commands(
+        program_data,
+        flyweight_data,
+        modules_generated_commands,
+        reachability,
+        keep_going,
+    )
 }
 
 pub fn run_targets_generated_commands(
@@ -39,14 +114,13 @@ pub fn run_targets_generated_commands(
     flyweight_data: &FlyweightData,
     targets: &mut IndexMap<TargetIdentifier, Target>,
     modules: &ModulesCommands<'_>,
+    reachability: &ModulesReachability,
+    keep_going: bool,
 ) -> Result<()> {
     log::info!("Proceeding to execute the generated commands...");
 
-    let shared_args = flyweight_data
-        .general_args
-        .iter()
-        .chain(flyweight_data.shared_args.iter())
-        .chain(flyweight_data.std_references.iter());
+    // The targets that couldn't be built when the user asked to keep going after the failures
+    let mut failed_targets: Vec<&TargetIdentifier> = Vec::new();
 
     // Process the user declared targets
     for (target_identifier, target_data) in targets
@@ -60,17 +134,77 @@ pub fn run_targets_generated_commands(
             target_identifier.name()
         );
 
-        let extra_args = &program_data
+        let target_model = program_data
             .targets
             .get(target_identifier)
-            .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?
-            .extra_args;
+            .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?;
 
-        let target_shared_args = shared_args
-            .clone()
-            .chain(flyweight_data.compile_but_dont_link.iter())
-            .chain(extra_args.as_slice())
-            .collect();
+        // The dependencies are sorted before their dependents, so a failed one is already known
+        if let Some(dependency) = target_model
+            .dependencies
+            .iter()
+            .find(|dependency| failed_targets.contains(dependency))
+        {
+            log::error!(
+                "Skipping the target {:?}, because its dependency {:?} failed to build",
+                target_identifier.name(),
+                dependency.name()
+            );
+            target_data.linker.execution_result = TranslationUnitStatus::Failed;
+            failed_targets.push(target_identifier);
+            continue;
+        }
+
+        let target_shared_args =
+            helpers::target_shared_args(program_data, flyweight_data, target_model);
+
+        let pre_build = hooks::run_target_hooks(
+            program_data,
+            target_identifier,
+            target_model,
+            HookStage::PreBuild,
+            env_vars,
+        );
+        if let Err(e) = pre_build {
+            helpers::handle_failure(keep_going, e)?;
+            target_data.linker.execution_result = TranslationUnitStatus::Failed;
+            failed_targets.push(target_identifier);
+            continue;
+        }
+
+        // The precompiled header must be built before the sources that use it
+        if let Some(pch) = target_data
+            .pch
+            .as_mut()
+            .filter(|scl| scl.status.eq(&TranslationUnitStatus::PendingToBuild))
+        {
+            log::info!(
+                "Building the precompiled header of target: {:?}",
+                target_identifier.name()
+            );
+            helpers::execute_source_command_line(
+                program_data,
+                &target_shared_args,
+                env_vars,
+                pch,
+                keep_going,
+            )?;
+        }
+
+        // The sources can't be built when the precompiled header, or any of the modules that
+        // they import, couldn't be built
+        let unusable_pch = target_data
+            .pch
+            .as_ref()
+            .filter(|pch| pch.status.is_unusable())
+            .map(SourceCommandLine::path);
+        let unusable_modules = helpers::unusable_units(
+            modules
+                .system_modules
+                .iter()
+                .chain(modules.header_units.iter())
+                .chain(modules.interfaces.iter()),
+        );
 
         // Send to build to the compiler the sources declared for the current iteration target
         for source in target_data
@@ -78,11 +212,22 @@ pub fn run_targets_generated_commands(
             .iter_mut()
             .filter(|scl| scl.status.eq(&TranslationUnitStatus::PendingToBuild))
         {
+            let unusable_dependency = unusable_pch.clone().or_else(|| {
+                reachability
+                    .imported_by(&source.path())
+                    .find(|unit| unusable_modules.contains(*unit))
+                    .cloned()
+            });
+            if helpers::skip_on_unusable_dependency(source, unusable_dependency) {
+                continue;
+            }
+
             helpers::execute_source_command_line(
                 program_data,
                 &target_shared_args,
                 env_vars,
                 source,
+                keep_going,
             )?;
         }
 
@@ -91,31 +236,334 @@ pub fn run_targets_generated_commands(
             target_identifier.name()
         );
 
-        // Invoke the linker to generate the final product for the current iteration target
-        helpers::execute_linker_command_line(
-            program_data,
-            flyweight_data,
+        // Only the module units reachable from the target are sent to its linker (or archiver)
+        let target_modules = helpers::ReachableModulesCommands {
             modules,
+            reachability,
+            target: target_identifier,
+        };
+
+        // A target whose translation units couldn't be built can't be linked, so it's reported
+        // as failed too
+        let failed_unit = target_data
+            .pch
+            .iter()
+            .chain(target_data.sources.iter())
+            .chain(target_modules.interfaces())
+            .chain(target_modules.implementations())
+            .find(|scl| scl.status.is_unusable());
+        if let Some(failed_unit) = failed_unit {
+            log::error!(
+                "Skipping the linkage of target {:?}, because {:?} couldn't be built",
+                target_identifier.name(),
+                failed_unit.filename
+            );
+            target_data.linker.execution_result = TranslationUnitStatus::Failed;
+            failed_targets.push(target_identifier);
+            continue;
+        }
+
+        // Invoke the linker (or the archiver) to generate the final product for the current iteration target
+        let archiver = program_data
+            .compiler
+            .archiver()
+            .filter(|_| target_data.kind.eq(&TargetKind::StaticLib));
+        let linked = if let Some(archiver) = archiver {
+            helpers::execute_archiver_command_line(
+                program_data,
+                archiver,
+                &target_modules,
+                env_vars,
+                target_data,
+                keep_going,
+            )?
+        } else {
+            helpers::execute_linker_command_line(
+                program_data,
+                flyweight_data,
+                &target_modules,
+                env_vars,
+                target_data,
+                keep_going,
+            )?
+        };
+        if !linked {
+            failed_targets.push(target_identifier);
+            continue;
+        }
+
+        let post_build = hooks::run_target_hooks(
+            program_data,
+            target_identifier,
+            target_model,
+            HookStage::PostBuild,
             env_vars,
-            target_data,
-        )?;
+        );
+        if let Err(e) = post_build {
+            helpers::handle_failure(keep_going, e)?;
+            target_data.linker.execution_result = TranslationUnitStatus::Failed;
+            failed_targets.push(target_identifier);
+        }
     }
 
     Ok(())
 }
 
+/// Collects the translation units and the targets that failed to build on the current
+/// iteration, when the user asked to keep going after the failures, along with the translation
+/// units skipped because of them. Returns an error with the summary of all of them, if any
+pub fn check_build_failures(
+    generators: &[SourceCommandLine<'_>],
+    modules: &ModulesCommands<'_>,
+    targets: &IndexMap<TargetIdentifier, Target>,
+) -> Result<()> {
+    let units = generators
+        .iter()
+        .chain(modules.cpp_stdlib.iter())
+        .chain(modules.c_compat_stdlib.iter())
+        .chain(modules.system_modules.iter())
+        .chain(modules.header_units.iter())
+        .chain(modules.interfaces.iter())
+        .chain(modules.implementations.iter())
+        .chain(
+            targets
+                .values()
+                .filter(|target| target.enabled_for_current_program_iteration)
+                .flat_map(|target| target.pch.iter().chain(target.sources.iter())),
+        );
+    let (failed_units, skipped_units): (String, String) = units
+        .filter(|scl| scl.status.is_unusable())
+        .map(|scl| (scl.status, format!("\n\t- {}", scl.path().display())))
+        .fold(
+            Default::default(),
+            |(mut failed, mut skipped), (status, entry)| {
+                if status.is_failure() {
+                    failed.push_str(&entry);
+                } else {
+                    skipped.push_str(&entry);
+                }
+                (failed, skipped)
+            },
+        );
+    let failed_targets = targets
+        .iter()
+        .filter(|(_, target)| {
+            target.enabled_for_current_program_iteration
+                && target.linker.execution_result.is_failure()
+        })
+        .map(|(identifier, _)| format!("\n\t- {}", identifier.name()))
+        .collect::<String>();
+
+    if failed_units.is_empty() && failed_targets.is_empty() {
+        return Ok(());
+    }
+
+    let mut summary = String::from("The build finished with failures");
+    if !failed_units.is_empty() {
+        summary.push_str(&format!("\nFailed translation units:{failed_units}"));
+    }
+    if !skipped_units.is_empty() {
+        summary.push_str(&format!(
+            "\nSkipped translation units, because their dependencies failed:{skipped_units}"
+        ));
+    }
+    if !failed_targets.is_empty() {
+        summary.push_str(&format!("\nFailed targets:{failed_targets}"));
+    }
+
+    Err(eyre!(summary))
+}
+
+/// Prints every command line that would be executed on the current iteration, in their execution
+/// order and along with the reason for executing them, without running any of them.
+///
+/// The hooks declared by the user are listed as well, since they're executed on every iteration
+pub fn print_dry_run(
+    program_data: &ZorkModel<'_>,
+    generated_commands: &Commands<'_>,
+    reachability: &ModulesReachability,
+) -> Result<()> {
+    dry_run_commands(program_data, generated_commands, reachability)?
+        .iter()
+        .for_each(|line| println!("{line}"));
+    Ok(())
+}
+
+/// The lines printed by [`print_dry_run`], each one with the reason for executing the command
+pub fn dry_run_commands(
+    program_data: &ZorkModel<'_>,
+    generated_commands: &Commands<'_>,
+    reachability: &ModulesReachability,
+) -> Result<Vec<String>> {
+    let flyweight_data = generated_commands
+        .flyweight_data
+        .as_ref()
+        .with_context(|| error_messages::FAILURE_LOADING_FLYWEIGHT_DATA)?;
+    let modules = &generated_commands.modules;
+
+    let driver = program_data
+        .compiler
+        .cpp_compiler
+        .get_driver(&program_data.compiler)
+        .to_string();
+    // The compiler launcher only prefixes the commands that generates object files
+    let compile_program = match &program_data.compiler.launcher {
+        Some(launcher) => format!("{launcher} {driver}"),
+        None => driver.clone(),
+    };
+
+    let mut lines = Vec::new();
+    let mut print = |reason: Option<BuildReason>, program: &str, args: &Arguments| {
+        let reason = reason.unwrap_or(BuildReason::NotBuilt);
+        if program.is_empty() {
+            lines.push(format!("[{reason}] {args}"));
+        } else {
+            lines.push(format!("[{reason}] {program} {args}"));
+        }
+    };
+    let is_pending =
+        |scl: &&SourceCommandLine| scl.status.eq(&TranslationUnitStatus::PendingToBuild);
+
+    for hook in hooks::project_hooks(program_data, HookStage::PreBuild)? {
+        print(Some(BuildReason::Always), &hook.program, &hook.args);
+    }
+
+    for generator in &program_data.generators {
+        let generated_cmd = generated_commands
+            .generators
+            .iter()
+            .find(|scl| generator.outputs[0].eq(&scl.path()))
+            .with_context(|| error_messages::FAILURE_GENERATORS)?;
+        if is_pending(&generated_cmd) {
+            // The program of the generators is their first argument
+            print(generated_cmd.build_reason, "", &generated_cmd.args);
+        }
+    }
+
+    for std_lib in modules
+        .cpp_stdlib
+        .iter()
+        .chain(modules.c_compat_stdlib.iter())
+        .filter(is_pending)
+    {
+        let args = helpers::std_module_args(flyweight_data, std_lib);
+        print(std_lib.build_reason, &driver, &args);
+    }
+
+    for header_unit in modules
+        .system_modules
+        .iter()
+        .chain(modules.header_units.iter())
+        .filter(is_pending)
+    {
+        let args = helpers::header_unit_args(flyweight_data, header_unit);
+        print(header_unit.build_reason, &driver, &args);
+    }
+
+    for (index, is_implementation) in helpers::user_modules_build_order(program_data, modules) {
+        let (module_unit, program) = if is_implementation {
+            (&modules.implementations[index], &compile_program)
+        } else {
+            (&modules.interfaces[index], &driver)
+        };
+        let args = helpers::user_module_args(flyweight_data, module_unit);
+        print(module_unit.build_reason, program, &args);
+    }
+
+    for (target_identifier, target_data) in generated_commands
+        .targets
+        .iter()
+        .filter(|(_, target_data)| target_data.enabled_for_current_program_iteration)
+    {
+        let target_model = program_data
+            .targets
+            .get(target_identifier)
+            .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?;
+        let target_shared_args =
+            helpers::target_shared_args(program_data, flyweight_data, target_model);
+
+        for hook in hooks::target_hooks(
+            program_data,
+            target_identifier,
+            target_model,
+            HookStage::PreBuild,
+        )? {
+            print(Some(BuildReason::Always), &hook.program, &hook.args);
+        }
+
+        for source in target_data
+            .pch
+            .iter()
+            .chain(target_data.sources.iter())
+            .filter(is_pending)
+        {
+            let args = helpers::source_args(&target_shared_args, source);
+            print(source.build_reason, &compile_program, &args);
+        }
+
+        let target_modules = helpers::ReachableModulesCommands {
+            modules,
+            reachability,
+            target: target_identifier,
+        };
+        // The final product of every target is generated again on every iteration
+        let dependency_rebuilt = target_data
+            .pch
+            .iter()
+            .chain(target_data.sources.iter())
+            .chain(target_modules.interfaces())
+            .chain(target_modules.implementations())
+            .any(|scl| is_pending(&scl));
+        let reason = if dependency_rebuilt {
+            BuildReason::DependencyRebuilt
+        } else {
+            BuildReason::Always
+        };
+
+        let archiver = program_data
+            .compiler
+            .archiver()
+            .filter(|_| target_data.kind.eq(&TargetKind::StaticLib));
+        if let Some(archiver) = archiver {
+            let compiler = program_data.compiler.cpp_compiler;
+            let args = helpers::archiver_args(compiler, &target_modules, target_data);
+            print(Some(reason), archiver, &args);
+        } else {
+            let args =
+                helpers::linker_args(program_data, flyweight_data, &target_modules, target_data);
+            print(Some(reason), &driver, &args);
+        }
+
+        for hook in hooks::target_hooks(
+            program_data,
+            target_identifier,
+            target_model,
+            HookStage::PostBuild,
+        )? {
+            print(Some(BuildReason::Always), &hook.program, &hook.args);
+        }
+    }
+
+    for hook in hooks::project_hooks(program_data, HookStage::PostBuild)? {
+        print(Some(BuildReason::Always), &hook.program, &hook.args);
+    }
+
+    Ok(lines)
+}
+
 /// Executes a new [`std::process::Command`] to run the generated binary
 /// after the build process in the specified shell
 pub fn autorun_generated_binary(
-    compiler: &CppCompiler,
+    compiler_model: &CompilerModel,
     output_dir: &Path,
-    executable_name: &str,
+    executable: &Path,
 ) -> Result<()> {
+    let compiler = compiler_model.cpp_compiler;
     let args = &[Argument::from(
         output_dir
             .join(compiler.as_ref())
-            .join(executable_name)
-            .with_extension(constants::BINARY_EXTENSION),
+            .join(executable)
+            .with_extension(compiler_model.toolchain.binary_extension()),
     )];
 
     log::info!(
@@ -124,7 +572,7 @@ pub fn autorun_generated_binary(
     );
 
     std::process::Command::new(Argument::from(
-        output_dir.join(compiler.as_ref()).join(executable_name),
+        output_dir.join(compiler.as_ref()).join(executable),
     ))
     .spawn()?
     .wait()
@@ -145,14 +593,50 @@ where
     S: AsRef<OsStr>,
 {
     let compiler = model.compiler.cpp_compiler;
+    let driver = compiler.get_driver(&model.compiler);
+    spawn_command(compiler, driver.as_ref(), arguments, env_vars)
+}
+
+/// Executes a compile command, prefixed with the compiler launcher declared by the user, if any.
+///
+/// The launcher must only be used on the commands that generates object files from their
+/// inputs, so the module interfaces precompilation steps shouldn't be executed through here
+fn execute_compile_command(
+    model: &ZorkModel,
+    arguments: &Arguments,
+    env_vars: &EnvVars,
+) -> Result<ExitStatus, Report> {
+    match &model.compiler.launcher {
+        Some(launcher) => {
+            let compiler = model.compiler.cpp_compiler;
+            let driver = Argument::from(compiler.get_driver(&model.compiler));
+            let args = std::iter::once(&driver)
+                .chain(arguments.iter())
+                .collect::<Arguments>();
+
+            spawn_command(compiler, launcher, &args, env_vars)
+        }
+        None => execute_command(model, arguments, env_vars),
+    }
+}
+
+/// Spawns a new [`std::process::Command`] for the given program, and waits for it to finish
+pub(crate) fn spawn_command<T, S>(
+    compiler: CppCompiler,
+    program: &str,
+    arguments: T,
+    env_vars: &EnvVars,
+) -> Result<ExitStatus, Report>
+where
+    T: IntoIterator<Item = S> + std::fmt::Display + std::marker::Copy,
+    S: AsRef<OsStr>,
+{
     log::trace!(
         "[{compiler}] - Executing command => {:?}",
-        format!("{} {}", compiler.get_driver(&model.compiler), arguments)
+        format!("{} {}", program, arguments)
     );
 
-    let driver = compiler.get_driver(&model.compiler);
-    let os_driver = OsStr::new(driver.as_ref());
-    std::process::Command::new(os_driver)
+    std::process::Command::new(OsStr::new(program))
         .args(arguments)
         .envs(env_vars)
         .spawn()?
@@ -162,16 +646,22 @@ where
 
 mod helpers {
     use crate::cache::EnvVars;
-    use crate::cli::output::executors::execute_command;
-    use crate::domain::commands::arguments::Arguments;
+    use crate::cli::output::executors::{execute_command, execute_compile_command, spawn_command};
+    use crate::domain::commands::arguments::{self, Argument, Arguments};
     use crate::domain::commands::command_lines::{ModulesCommands, SourceCommandLine};
     use crate::domain::flyweight_data::FlyweightData;
-    use crate::domain::target::Target;
-    use crate::domain::translation_unit::TranslationUnitStatus;
+    use crate::domain::reachability::ModulesReachability;
+    use crate::domain::target::{Target, TargetIdentifier};
+    use crate::domain::translation_unit::{TranslationUnit, TranslationUnitStatus};
     use crate::project_model::compiler::CppCompiler;
+    use crate::project_model::target::TargetModel;
     use crate::project_model::ZorkModel;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
 
     use color_eyre::eyre::{eyre, Result};
+    use color_eyre::Report;
+    use std::fmt::Display;
     use std::process::ExitStatus;
 
     pub(crate) fn execute_source_command_line(
@@ -179,47 +669,171 @@ mod helpers {
         shared_args: &Arguments<'_>,
         env_vars: &EnvVars,
         source: &mut SourceCommandLine<'_>,
-    ) -> Result<()> {
-        let args = shared_args
-            .as_slice()
-            .iter()
-            .chain(source.args.as_slice().iter())
-            .collect::<Arguments>();
+        keep_going: bool,
+    ) -> Result<bool> {
+        let args = source_args(shared_args, source);
 
-        let r = execute_command(program_data, &args, env_vars);
+        let r = execute_compile_command(program_data, &args, env_vars);
         source.status = TranslationUnitStatus::from(&r);
 
-        if let Err(e) = r {
-            return Err(e);
-        } else if !r.as_ref().unwrap().success() {
-            let err = eyre!(
-                "Ending the program, because the build of: {:?} failed",
-                source.filename
-            );
-            return Err(err);
+        check_command_result(
+            r,
+            keep_going,
+            format!("the build of: {:?}", source.filename),
+        )
+    }
+
+    /// Checks the result of an executed command, returning whether it was successful.
+    ///
+    /// A failure ends the program, unless the user asked to keep going, where it's
+    /// only reported
+    pub(crate) fn check_command_result(
+        result: Result<ExitStatus>,
+        keep_going: bool,
+        subject: impl Display,
+    ) -> Result<bool> {
+        match result {
+            Ok(status) if status.success() => Ok(true),
+            Ok(_) if keep_going => handle_failure(keep_going, eyre!("{subject} failed")),
+            Ok(_) => Err(eyre!("Ending the program, because {subject} failed")),
+            Err(e) => handle_failure(keep_going, e.wrap_err(format!("{subject} failed"))),
         }
+    }
 
-        Ok(())
+    /// Propagates the given failure, unless the user asked to keep going, where it's
+    /// logged and the build continues with the work that doesn't depend on it
+    pub(crate) fn handle_failure(keep_going: bool, err: Report) -> Result<bool> {
+        if keep_going {
+            log::error!("{err:#}");
+            Ok(false)
+        } else {
+            Err(err)
+        }
+    }
+
+    /// The generated commands of the modules, seen from a concrete target
+    pub(crate) struct ReachableModulesCommands<'a, 'b> {
+        pub modules: &'b ModulesCommands<'a>,
+        pub reachability: &'b ModulesReachability<'b>,
+        pub target: &'b TargetIdentifier<'b>,
+    }
+
+    impl<'a, 'b> ReachableModulesCommands<'a, 'b> {
+        pub(crate) fn interfaces(&self) -> impl Iterator<Item = &'b SourceCommandLine<'a>> + '_ {
+            self.modules.interfaces.iter().filter(|scl| {
+                self.reachability
+                    .is_reachable_from(self.target, &scl.path())
+            })
+        }
+
+        pub(crate) fn implementations(
+            &self,
+        ) -> impl Iterator<Item = &'b SourceCommandLine<'a>> + '_ {
+            self.modules.implementations.iter().filter(|scl| {
+                self.reachability
+                    .is_reachable_from(self.target, &scl.path())
+            })
+        }
     }
 
     pub(crate) fn execute_linker_command_line(
         program_data: &ZorkModel,
         flyweight_data: &FlyweightData,
-        modules: &ModulesCommands<'_>,
+        target_modules: &ReachableModulesCommands<'_, '_>,
         env_vars: &EnvVars,
         target_data: &mut Target,
-    ) -> Result<ExitStatus> {
+        keep_going: bool,
+    ) -> Result<bool> {
+        let args = linker_args(program_data, flyweight_data, target_modules, target_data);
+
+        let r = execute_command(program_data, &args, env_vars);
+        target_data.linker.execution_result = TranslationUnitStatus::from(&r);
+
+        check_command_result(r, keep_going, "the linker command line execution")
+    }
+
+    /// Bundles the object files of a static library target with the archiver of the toolchain
+    pub(crate) fn execute_archiver_command_line(
+        program_data: &ZorkModel,
+        archiver: &str,
+        target_modules: &ReachableModulesCommands<'_, '_>,
+        env_vars: &EnvVars,
+        target_data: &mut Target,
+        keep_going: bool,
+    ) -> Result<bool> {
+        let compiler = program_data.compiler.cpp_compiler;
+        let args = archiver_args(compiler, target_modules, target_data);
+
+        let r = spawn_command(compiler, archiver, &args, env_vars);
+        target_data.linker.execution_result = TranslationUnitStatus::from(&r);
+
+        check_command_result(r, keep_going, "the archiver command line execution")
+    }
+
+    /// The arguments of the compile command of a source of a target, joined to the ones shared
+    /// by all the sources of the target
+    pub(crate) fn source_args<'a>(
+        shared_args: &'a Arguments<'_>,
+        source: &'a SourceCommandLine<'_>,
+    ) -> Arguments<'a> {
+        shared_args
+            .as_slice()
+            .iter()
+            .chain(source.args.as_slice().iter())
+            .collect::<Arguments>()
+    }
+
+    /// The arguments shared by all the sources (and the precompiled header) of the given target
+    pub(crate) fn target_shared_args<'a>(
+        program_data: &'a ZorkModel<'_>,
+        flyweight_data: &'a FlyweightData<'_>,
+        target_model: &'a TargetModel<'_>,
+    ) -> Arguments<'a> {
+        let target_compile_options = arguments::compile_options_args(
+            program_data.compiler.cpp_compiler,
+            &target_model.options,
+        );
+
+        flyweight_data
+            .general_args
+            .iter()
+            .chain(flyweight_data.shared_args.iter())
+            .chain(flyweight_data.std_references.iter())
+            .chain(flyweight_data.header_units_refs.iter())
+            .chain(flyweight_data.compile_but_dont_link.iter())
+            .chain(flyweight_data.compile_options.iter())
+            .chain(flyweight_data.compile_args.iter())
+            .cloned()
+            .chain(target_compile_options)
+            .chain(target_model.compile_args.iter().cloned())
+            .chain(target_model.extra_args.iter().cloned())
+            .collect()
+    }
+
+    /// The arguments of the linker command line of the given target
+    pub(crate) fn linker_args<'a>(
+        program_data: &ZorkModel,
+        flyweight_data: &'a FlyweightData<'_>,
+        target_modules: &'a ReachableModulesCommands<'_, '_>,
+        target_data: &'a Target<'_>,
+    ) -> Arguments<'a> {
         let compiler = program_data.compiler.cpp_compiler;
         let target_output = target_data.linker.get_target_output_for(compiler);
+        let modules = target_modules.modules;
 
-        let linker_sources_byproducts = target_data.sources.iter().map(|scl| &scl.byproduct);
+        let pch_object_file = pch_object_file(compiler, target_data);
+        let linker_sources_byproducts = target_data
+            .sources
+            .iter()
+            .map(|scl| &scl.byproduct)
+            .chain(pch_object_file.iter());
         let modules_byproducts = modules
             .cpp_stdlib
             .as_slice()
             .iter()
             .chain(modules.c_compat_stdlib.iter())
-            .chain(modules.interfaces.iter())
-            .chain(modules.implementations.iter())
+            .chain(target_modules.interfaces())
+            .chain(target_modules.implementations())
             .chain(if compiler.eq(&CppCompiler::CLANG) {
                 // NOTE: gcc handles them itself with the
                 // gcm.cache. MSVC doesn't need them and
@@ -231,9 +845,10 @@ mod helpers {
             })
             .map(|scl| &scl.byproduct);
 
-        let args = flyweight_data
+        flyweight_data
             .general_args
             .iter()
+            .chain(flyweight_data.linker_args.iter())
             .chain(flyweight_data.shared_args.iter())
             .chain(flyweight_data.std_references.iter())
             .chain(target_data.linker.args.iter())
@@ -241,26 +856,71 @@ mod helpers {
             .chain(target_output.iter())
             .chain(modules_byproducts)
             .chain(linker_sources_byproducts)
-            .collect::<Arguments>();
+            .chain(target_data.linker.link_options.iter())
+            .cloned()
+            .collect::<Arguments>()
+    }
 
-        let r = execute_command(program_data, &args, env_vars);
-        target_data.linker.execution_result = TranslationUnitStatus::from(&r);
+    /// The arguments of the archiver command line of the given static library target
+    pub(crate) fn archiver_args<'a>(
+        compiler: CppCompiler,
+        target_modules: &'a ReachableModulesCommands<'_, '_>,
+        target_data: &'a Target<'_>,
+    ) -> Arguments<'a> {
+        let archive_output = match compiler {
+            CppCompiler::CLANG | CppCompiler::GCC => {
+                vec![Argument::from("rcs"), target_data.linker.target.clone()]
+            }
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => vec![
+                Argument::from("/NOLOGO"),
+                Argument::from(format!("/OUT:{}", target_data.linker.target)),
+            ],
+        };
 
-        if let Err(e) = r {
-            return Err(e);
-        } else if !r.as_ref().unwrap().success() {
-            return Err(eyre!(
-                "Ending the program, because the linker command line execution failed",
-            ));
-        }
+        let pch_object_file = pch_object_file(compiler, target_data);
+        let linker_sources_byproducts = target_data
+            .sources
+            .iter()
+            .map(|scl| &scl.byproduct)
+            .chain(pch_object_file.iter());
+        let modules_byproducts = target_modules
+            .interfaces()
+            // NOTE: the byproducts of the Clang's module interfaces are BMIs, not object files
+            .filter(|_| compiler.ne(&CppCompiler::CLANG))
+            .chain(target_modules.implementations())
+            .map(|scl| &scl.byproduct);
 
-        r
+        archive_output
+            .iter()
+            .chain(modules_byproducts)
+            .chain(linker_sources_byproducts)
+            .cloned()
+            .collect::<Arguments>()
+    }
+
+    /// `MSVC` generates an object file along the precompiled header, which must be sent
+    /// to the linker (or archiver) of the target
+    fn pch_object_file<'a>(
+        compiler: CppCompiler,
+        target_data: &Target<'a>,
+    ) -> Option<Argument<'a>> {
+        target_data
+            .pch
+            .as_ref()
+            .filter(|_| compiler.eq(&CppCompiler::MSVC))
+            .map(|pch| {
+                Argument::from(
+                    std::path::Path::new(&pch.byproduct)
+                        .with_extension(compiler.get_obj_file_extension()),
+                )
+            })
     }
 
     pub(crate) fn process_std_modules_commands(
         program_data: &ZorkModel<'_>,
         flyweight_data: &FlyweightData,
         generated_commands: &mut ModulesCommands<'_>,
+        keep_going: bool,
     ) -> Result<()> {
         let std_libs_commands: Vec<&mut SourceCommandLine> =
             get_std_modules_commands(generated_commands);
@@ -270,14 +930,7 @@ mod helpers {
         }
 
         for std_lib in std_libs_commands {
-            // Join the concrete args of any translation unit with the ones held in the flyweights
-            let translation_unit_cmd_args = flyweight_data
-                .general_args
-                .iter()
-                .chain(flyweight_data.shared_args.iter())
-                .chain(flyweight_data.compile_but_dont_link.iter()) // NOTE: non-required in Clang
-                .chain(std_lib.args.iter())
-                .collect::<Arguments>();
+            let translation_unit_cmd_args = std_module_args(flyweight_data, std_lib);
 
             let r = execute_command(
                 program_data,
@@ -286,67 +939,202 @@ mod helpers {
             );
             std_lib.status = TranslationUnitStatus::from(&r);
 
-            if let Err(e) = r {
-                return Err(e);
-            } else if !r.as_ref().unwrap().success() {
-                let err = eyre!(
-                    "Ending the program, because the build of: {:?} failed",
-                    std_lib.filename
-                );
-                return Err(err);
-            }
+            check_command_result(
+                r,
+                keep_going,
+                format!("the build of: {:?}", std_lib.filename),
+            )?;
         }
 
         Ok(())
     }
 
-    pub(crate) fn process_user_modules_commands(
+    /// Builds the system headers declared as modules and the user headers declared as header units.
+    /// They are always built, since there's no way of knowing if a header is imported without
+    /// preprocessing the importers
+    pub(crate) fn process_header_units_commands(
         program_data: &ZorkModel<'_>,
         flyweight_data: &FlyweightData,
         generated_commands: &mut ModulesCommands<'_>,
+        keep_going: bool,
     ) -> Result<()> {
-        let translation_units_commands: Vec<&mut SourceCommandLine> =
-            get_user_modules_translation_units_commands(generated_commands);
+        for header_unit in generated_commands
+            .system_modules
+            .iter_mut()
+            .chain(generated_commands.header_units.iter_mut())
+            .filter(|scl| scl.status.eq(&TranslationUnitStatus::PendingToBuild))
+        {
+            let translation_unit_cmd_args = header_unit_args(flyweight_data, header_unit);
 
-        if translation_units_commands.is_empty() {
-            log::debug!(
-                "No user or system modules to process, build or rebuild in this iteration."
+            let r = execute_command(
+                program_data,
+                &translation_unit_cmd_args,
+                &flyweight_data.env_vars,
             );
+            header_unit.status = TranslationUnitStatus::from(&r);
+
+            check_command_result(
+                r,
+                keep_going,
+                format!("the build of the header unit: {:?}", header_unit.filename),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn process_user_modules_commands(
+        program_data: &ZorkModel<'_>,
+        flyweight_data: &FlyweightData,
+        generated_commands: &mut ModulesCommands<'_>,
+        reachability: &ModulesReachability,
+        keep_going: bool,
+    ) -> Result<()> {
+        let build_order = user_modules_build_order(program_data, generated_commands);
+
+        if build_order.is_empty() {
+            log::debug!("No user modules to process, build or rebuild in this iteration.");
             return Ok(());
         }
 
-        for translation_unit_cmd in translation_units_commands {
-            // Join the concrete args of any translation unit with the ones held in the flyweights
-            let translation_unit_cmd_args = flyweight_data
-                .general_args
+        // The header units are already built, so the ones that failed are known. And the module
+        // units are built after the ones that they import
+        let mut unusable = unusable_units(
+            generated_commands
+                .system_modules
                 .iter()
-                .chain(flyweight_data.shared_args.iter())
-                .chain(flyweight_data.std_references.iter())
-                .chain(flyweight_data.compile_but_dont_link.iter())
-                .chain(translation_unit_cmd.args.iter())
-                .collect::<Arguments>();
+                .chain(generated_commands.header_units.iter()),
+        );
 
-            let r = execute_command(
-                program_data,
-                &translation_unit_cmd_args,
-                &flyweight_data.env_vars,
-            );
+        for (index, is_implementation) in build_order {
+            let translation_unit_cmd = if is_implementation {
+                &mut generated_commands.implementations[index]
+            } else {
+                &mut generated_commands.interfaces[index]
+            };
+            let path = translation_unit_cmd.path();
+
+            let unusable_dependency = reachability
+                .imported_by(&path)
+                .find(|unit| unusable.contains(*unit))
+                .cloned();
+            if skip_on_unusable_dependency(translation_unit_cmd, unusable_dependency) {
+                unusable.insert(path);
+                continue;
+            }
+
+            let translation_unit_cmd_args = user_module_args(flyweight_data, translation_unit_cmd);
+
+            // The compiler launcher is only safe to use on the module implementation units, since
+            // the module interfaces have other byproducts (the BMIs) than the object files
+            let r = if is_implementation {
+                execute_compile_command(
+                    program_data,
+                    &translation_unit_cmd_args,
+                    &flyweight_data.env_vars,
+                )
+            } else {
+                execute_command(
+                    program_data,
+                    &translation_unit_cmd_args,
+                    &flyweight_data.env_vars,
+                )
+            };
             translation_unit_cmd.status = TranslationUnitStatus::from(&r);
 
-            if let Err(e) = r {
-                return Err(e);
-            } else if !r.as_ref().unwrap().success() {
-                let err = eyre!(
-                    "Ending the program, because the build of: {:?} failed",
-                    translation_unit_cmd.filename
-                );
-                return Err(err);
+            let built = check_command_result(
+                r,
+                keep_going,
+                format!("the build of: {:?}", translation_unit_cmd.filename),
+            )?;
+            if !built {
+                unusable.insert(path);
             }
         }
 
         Ok(())
     }
 
+    /// The paths of the given translation units that couldn't be built on the current iteration
+    pub(crate) fn unusable_units<'a, 'b: 'a>(
+        commands: impl Iterator<Item = &'a SourceCommandLine<'b>>,
+    ) -> HashSet<PathBuf> {
+        commands
+            .filter(|scl| scl.status.is_unusable())
+            .map(SourceCommandLine::path)
+            .collect()
+    }
+
+    /// Marks the given command as skipped when there's a dependency of it that couldn't be built
+    /// on the current iteration, returning whether it was skipped
+    pub(crate) fn skip_on_unusable_dependency(
+        scl: &mut SourceCommandLine<'_>,
+        unusable_dependency: Option<PathBuf>,
+    ) -> bool {
+        let Some(dependency) = unusable_dependency else {
+            return false;
+        };
+
+        log::error!(
+            "Skipping the build of: {:?}, because {:?} couldn't be built",
+            scl.filename,
+            dependency
+        );
+        scl.status = TranslationUnitStatus::Skipped;
+        true
+    }
+
+    /// Joins the concrete args of a modular standard library with the ones held in the flyweights
+    pub(crate) fn std_module_args<'a>(
+        flyweight_data: &'a FlyweightData<'_>,
+        std_lib: &'a SourceCommandLine<'_>,
+    ) -> Arguments<'a> {
+        flyweight_data
+            .general_args
+            .iter()
+            .chain(flyweight_data.shared_args.iter())
+            .chain(flyweight_data.compile_but_dont_link.iter()) // NOTE: non-required in Clang
+            .chain(flyweight_data.compile_options.iter())
+            .chain(flyweight_data.compile_args.iter())
+            .chain(std_lib.args.iter())
+            .collect::<Arguments>()
+    }
+
+    /// Joins the concrete args of a system module or a header unit with the ones held in the flyweights
+    pub(crate) fn header_unit_args<'a>(
+        flyweight_data: &'a FlyweightData<'_>,
+        header_unit: &'a SourceCommandLine<'_>,
+    ) -> Arguments<'a> {
+        flyweight_data
+            .general_args
+            .iter()
+            .chain(flyweight_data.shared_args.iter())
+            .chain(flyweight_data.std_references.iter())
+            .chain(flyweight_data.compile_but_dont_link.iter())
+            .chain(flyweight_data.compile_options.iter())
+            .chain(flyweight_data.compile_args.iter())
+            .chain(header_unit.args.iter())
+            .collect::<Arguments>()
+    }
+
+    /// Joins the concrete args of a user module unit with the ones held in the flyweights
+    pub(crate) fn user_module_args<'a>(
+        flyweight_data: &'a FlyweightData<'_>,
+        translation_unit_cmd: &'a SourceCommandLine<'_>,
+    ) -> Arguments<'a> {
+        flyweight_data
+            .general_args
+            .iter()
+            .chain(flyweight_data.shared_args.iter())
+            .chain(flyweight_data.std_references.iter())
+            .chain(flyweight_data.header_units_refs.iter())
+            .chain(flyweight_data.compile_but_dont_link.iter())
+            .chain(flyweight_data.compile_options.iter())
+            .chain(flyweight_data.compile_args.iter())
+            .chain(translation_unit_cmd.args.iter())
+            .collect::<Arguments>()
+    }
+
     pub(crate) fn get_std_modules_commands<'a, 'b>(
         generated_commands: &'b mut ModulesCommands<'a>,
     ) -> Vec<&'b mut SourceCommandLine<'a>> {
@@ -359,17 +1147,39 @@ mod helpers {
             .collect::<Vec<&mut SourceCommandLine>>()
     }
 
-    pub(crate) fn get_user_modules_translation_units_commands<'a, 'b>(
-        generated_commands: &'b mut ModulesCommands<'a>,
-    ) -> Vec<&'b mut SourceCommandLine<'a>> {
-        let system_modules = generated_commands.system_modules.as_mut_slice().iter_mut();
-        let interfaces = generated_commands.interfaces.as_mut_slice().iter_mut();
-        let implementations = generated_commands.implementations.as_mut_slice().iter_mut();
+    /// Returns the pending to build commands of the user declared modules in the order that
+    /// they must be built: the interfaces in their dependency order, and then the implementations.
+    ///
+    /// Every entry holds the index of the command on its list, and whether it's an implementation
+    pub(crate) fn user_modules_build_order(
+        program_data: &ZorkModel<'_>,
+        generated_commands: &ModulesCommands<'_>,
+    ) -> Vec<(usize, bool)> {
+        let pending = |commands: &[SourceCommandLine<'_>]| {
+            commands
+                .iter()
+                .enumerate()
+                .filter(|(_, scl)| scl.status.eq(&TranslationUnitStatus::PendingToBuild))
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>()
+        };
+        let mut interfaces = pending(&generated_commands.interfaces);
+        let implementations = pending(&generated_commands.implementations);
 
-        system_modules
-            .chain(interfaces)
-            .chain(implementations)
-            .filter(|scl| scl.status.eq(&TranslationUnitStatus::PendingToBuild))
-            .collect::<Vec<&mut SourceCommandLine>>()
+        // The cached commands are stored in the order that they were generated for the first time,
+        // so the interfaces are sorted again to build them in their dependency order
+        let declared_interfaces = &program_data.modules.interfaces;
+        interfaces.sort_by_key(|index| {
+            let path = generated_commands.interfaces[*index].path();
+            declared_interfaces
+                .iter()
+                .position(|ifc| ifc.path().eq(&path))
+        });
+
+        interfaces
+            .into_iter()
+            .map(|index| (index, false))
+            .chain(implementations.into_iter().map(|index| (index, true)))
+            .collect()
     }
 }
@@ -0,0 +1,174 @@
+//! Execution of the custom commands that the user declared to be run before and after
+//! the build process of the whole project, or of a concrete target
+
+use std::borrow::Cow;
+use std::fmt;
+
+use color_eyre::eyre::{eyre, Context, ContextCompat};
+use color_eyre::Result;
+
+use crate::cache::EnvVars;
+use crate::cli::output::executors::spawn_command;
+use crate::compiler::helpers;
+use crate::domain::commands::arguments::{Argument, Arguments};
+use crate::domain::target::TargetIdentifier;
+use crate::project_model::project::BuildHooks;
+use crate::project_model::target::TargetModel;
+use crate::project_model::ZorkModel;
+use crate::utils::expansion::{expand_variables, split_command_line};
+
+/// The moment of the build process where a set of custom commands is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    PreBuild,
+    PostBuild,
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookStage::PreBuild => write!(f, "pre_build"),
+            HookStage::PostBuild => write!(f, "post_build"),
+        }
+    }
+}
+
+/// A custom command declared by the user for a stage of the build process, with its variables
+/// already expanded
+#[derive(Debug)]
+pub struct HookCommand {
+    pub program: String,
+    pub args: Arguments<'static>,
+    /// The command as it's declared on the configuration file
+    declared: String,
+    /// The table where it's declared
+    declared_on: String,
+    stage: HookStage,
+}
+
+/// The commands declared for the given stage on the `[project]` table
+pub fn project_hooks(model: &ZorkModel, stage: HookStage) -> Result<Vec<HookCommand>> {
+    let variables = project_variables(model);
+    expand_hooks(&model.project.hooks, stage, &variables, "[project]")
+}
+
+/// The commands declared for the given stage on a target
+pub fn target_hooks(
+    model: &ZorkModel,
+    target_identifier: &TargetIdentifier,
+    target_model: &TargetModel,
+    stage: HookStage,
+) -> Result<Vec<HookCommand>> {
+    let mut variables = project_variables(model);
+    variables.push(("target_name", Cow::Borrowed(target_identifier.name())));
+    variables.push((
+        "target_output",
+        Cow::Owned(
+            helpers::target_output_path(model, target_identifier, target_model.kind)
+                .display()
+                .to_string(),
+        ),
+    ));
+
+    expand_hooks(
+        &target_model.hooks,
+        stage,
+        &variables,
+        &format!("[targets.{}]", target_identifier.name()),
+    )
+}
+
+/// Runs the commands declared for the given stage on the `[project]` table
+pub fn run_project_hooks(model: &ZorkModel, stage: HookStage, env_vars: &EnvVars) -> Result<()> {
+    run_hooks(model, &project_hooks(model, stage)?, env_vars)
+}
+
+/// Runs the commands declared for the given stage on a target
+pub fn run_target_hooks(
+    model: &ZorkModel,
+    target_identifier: &TargetIdentifier,
+    target_model: &TargetModel,
+    stage: HookStage,
+    env_vars: &EnvVars,
+) -> Result<()> {
+    let hooks = target_hooks(model, target_identifier, target_model, stage)?;
+    run_hooks(model, &hooks, env_vars)
+}
+
+fn project_variables<'a>(model: &'a ZorkModel) -> Vec<(&'static str, Cow<'a, str>)> {
+    vec![
+        (
+            "out_dir",
+            Cow::Owned(model.build.output_dir.display().to_string()),
+        ),
+        ("project_name", Cow::Borrowed(model.project.name.as_ref())),
+        (
+            "compiler",
+            Cow::Borrowed(model.compiler.cpp_compiler.as_ref()),
+        ),
+    ]
+}
+
+fn expand_hooks(
+    hooks: &BuildHooks,
+    stage: HookStage,
+    variables: &[(&str, Cow<str>)],
+    declared_on: &str,
+) -> Result<Vec<HookCommand>> {
+    let commands = match stage {
+        HookStage::PreBuild => &hooks.pre_build,
+        HookStage::PostBuild => &hooks.post_build,
+    };
+
+    commands
+        .iter()
+        .map(|command| {
+            let expanded = expand_variables(command, variables).with_context(|| {
+                format!("Invalid {stage} command of {declared_on}: {command:?}")
+            })?;
+            let words = split_command_line(&expanded).with_context(|| {
+                format!("Invalid {stage} command of {declared_on}: {command:?}")
+            })?;
+            let (program, args) = words
+                .split_first()
+                .with_context(|| format!("Found an empty {stage} command on {declared_on}"))?;
+
+            Ok(HookCommand {
+                program: program.clone(),
+                args: args.iter().cloned().map(Argument::from).collect(),
+                declared: command.to_string(),
+                declared_on: declared_on.to_string(),
+                stage,
+            })
+        })
+        .collect()
+}
+
+fn run_hooks(model: &ZorkModel, hooks: &[HookCommand], env_vars: &EnvVars) -> Result<()> {
+    if let Some(hook) = hooks.first() {
+        log::info!(
+            "Running the {} commands of {}",
+            hook.stage,
+            hook.declared_on
+        );
+    }
+
+    for hook in hooks {
+        let status = spawn_command(
+            model.compiler.cpp_compiler,
+            &hook.program,
+            &hook.args,
+            env_vars,
+        )?;
+        if !status.success() {
+            return Err(eyre!(
+                "Ending the program, because the {} command {:?} of {} failed",
+                hook.stage,
+                hook.declared,
+                hook.declared_on
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -1,2 +1,3 @@
 //! Defines operations or types that are related with send data to a system shell
 pub mod executors;
+pub mod hooks;
@@ -4,17 +4,20 @@
 //! file.
 
 use color_eyre::eyre::{Context, ContextCompat};
-use std::path::Path;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 
 use color_eyre::Result;
 
-use crate::domain::commands::arguments::Argument;
+use crate::domain::commands::arguments::{self, Argument, Arguments};
+use crate::domain::commands::command_lines::SourceCommandLine;
 use crate::domain::flyweight_data::FlyweightData;
-use crate::domain::target::TargetIdentifier;
-use crate::domain::translation_unit::TranslationUnitStatus;
-use crate::project_model::modules::SystemModule;
-use crate::project_model::target::TargetModel;
+use crate::domain::target::{TargetIdentifier, TargetKind};
+use crate::domain::translation_unit::{BuildReason, TranslationUnitStatus};
+use crate::project_model::modules::{HeaderUnitModel, SystemModule};
+use crate::project_model::target::{LinkOptions, TargetModel};
 use crate::utils::constants::error_messages;
+use crate::utils::{expansion, scanner};
 use crate::{
     cache::ZorkCache,
     domain::translation_unit::{TranslationUnit, TranslationUnitKind},
@@ -24,7 +27,6 @@ use crate::{
         sourceset::SourceFile,
         ZorkModel,
     },
-    utils::constants,
 };
 
 /// The core procedure. Generates the commands arguments that will be sent to the compiler
@@ -40,13 +42,21 @@ pub fn generate_commands_arguments<'a>(
     }
 
     // Build the std library as a module
-    generate_modular_stdlibs_cmds(model, cache);
+    generate_modular_stdlibs_cmds(model, cache)?;
 
     // System headers as modules
-    if model.compiler.cpp_compiler != CppCompiler::MSVC && !model.modules.sys_modules.is_empty() {
+    if !model.modules.sys_modules.is_empty() {
         generate_sys_modules_commands(model, cache)?;
     }
 
+    // The user headers built as header units
+    if !model.modules.header_units.is_empty() {
+        generate_header_units_commands(model, cache)?;
+    }
+
+    // The rules that generate files of the project
+    let regenerated_outputs = process_generators(model, cache)?;
+
     // Generates commands for the modules
     process_modules(model, cache)?;
 
@@ -54,25 +64,150 @@ pub fn generate_commands_arguments<'a>(
     // Generate commands for the declared targets
     process_targets(model, cache)?;
 
+    // The sources that will be generated again on this iteration must be rebuilt
+    if !regenerated_outputs.is_empty() {
+        cache
+            .generated_commands
+            .targets
+            .values_mut()
+            .flat_map(|target| target.sources.iter_mut())
+            .filter(|scl| regenerated_outputs.contains(&scl.path()))
+            .filter(|scl| scl.status.ne(&TranslationUnitStatus::PendingToBuild))
+            .for_each(|scl| scl.mark_pending(BuildReason::DependencyRebuilt));
+    }
+
+    // The build profile, or the environment variables read by the configuration files, may be
+    // expanded on any of the arguments, so the byproducts of the previous ones can't be reused
+    if cache.metadata.expanded_values_changed {
+        let generated_commands = &mut cache.generated_commands;
+        let modules = &mut generated_commands.modules;
+        modules
+            .cpp_stdlib
+            .iter_mut()
+            .chain(modules.c_compat_stdlib.iter_mut())
+            .chain(modules.system_modules.iter_mut())
+            .chain(modules.header_units.iter_mut())
+            .chain(modules.interfaces.iter_mut())
+            .chain(modules.implementations.iter_mut())
+            .chain(
+                generated_commands
+                    .targets
+                    .values_mut()
+                    .flat_map(|target| target.pch.iter_mut().chain(target.sources.iter_mut())),
+            )
+            .filter(|scl| {
+                matches!(
+                    scl.status,
+                    TranslationUnitStatus::Success | TranslationUnitStatus::Cached
+                )
+            })
+            .for_each(|scl| scl.mark_pending(BuildReason::ArgsChanged));
+    }
+
     Ok(())
 }
 
+/// Generates the command lines of the rules that generate files of the project. A rule is executed
+/// again whenever its command or any of its inputs changes, or when any of its outputs is missing.
+///
+/// *returns: <Vec<PathBuf>>* - the outputs of the rules that must be executed on the current iteration
+fn process_generators<'a>(
+    model: &'a ZorkModel<'a>,
+    cache: &mut ZorkCache<'a>,
+) -> Result<Vec<PathBuf>> {
+    let lpe = cache.metadata.last_program_execution;
+    let generators = &mut cache.generated_commands.generators;
+
+    // Discards the rules that were removed from the configuration file
+    generators.retain(|scl| {
+        model
+            .generators
+            .iter()
+            .any(|generator| generator.outputs[0].eq(&scl.path()))
+    });
+
+    let mut regenerated_outputs = Vec::new();
+    for generator in &model.generators {
+        let args = expansion::split_command_line(&generator.command)
+            .with_context(|| error_messages::FAILURE_GENERATORS)?
+            .into_iter()
+            .map(Argument::from)
+            .collect::<Arguments>();
+        let output = &generator.outputs[0];
+
+        let status = match generators.iter_mut().find(|scl| scl.path().eq(output)) {
+            Some(cached) => {
+                let reason = if cached.args.ne(&args) {
+                    Some(BuildReason::ArgsChanged)
+                } else if generator.outputs.iter().any(|output| !output.exists()) {
+                    Some(BuildReason::MissingOutputs)
+                } else if generator
+                    .inputs
+                    .iter()
+                    .any(|input| helpers::file_has_changes_on_fs(&lpe, input))
+                {
+                    Some(BuildReason::Modified)
+                } else {
+                    None
+                };
+                cached.args = args;
+                match reason {
+                    Some(reason) => cached.mark_pending(reason),
+                    None => {
+                        cached.status = helpers::compute_translation_unit_status(cached);
+                        cached.build_reason = cached
+                            .status
+                            .eq(&TranslationUnitStatus::PendingToBuild)
+                            .then_some(BuildReason::NotBuilt);
+                    }
+                }
+                cached.status
+            }
+            None => {
+                generators.push(SourceCommandLine {
+                    directory: output.parent().map(Path::to_path_buf).unwrap_or_default(),
+                    filename: output
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    args,
+                    status: TranslationUnitStatus::PendingToBuild,
+                    byproduct: Argument::from(output),
+                    build_reason: Some(BuildReason::New),
+                });
+                TranslationUnitStatus::PendingToBuild
+            }
+        };
+
+        if status.eq(&TranslationUnitStatus::PendingToBuild) {
+            regenerated_outputs.extend(generator.outputs.iter().cloned());
+        }
+    }
+
+    Ok(regenerated_outputs)
+}
+
 /// Generates the cmds for build the C++ standard libraries (std and std.compat) according to the specification
 /// of each compiler vendor
-fn generate_modular_stdlibs_cmds<'a>(model: &'a ZorkModel<'a>, cache: &mut ZorkCache<'a>) {
+fn generate_modular_stdlibs_cmds<'a>(
+    model: &'a ZorkModel<'a>,
+    cache: &mut ZorkCache<'a>,
+) -> Result<()> {
     match model.compiler.cpp_compiler {
         CppCompiler::CLANG => {
             if cache.compilers_metadata.clang.major > 17 {
-                modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::Cpp);
-                modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::CCompat);
+                modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::Cpp)?;
+                modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::CCompat)?;
             }
         }
         CppCompiler::MSVC => {
-            modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::Cpp);
-            modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::CCompat);
+            modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::Cpp)?;
+            modules::generate_modular_cpp_stdlib_args(model, cache, StdLibMode::CCompat)?;
         }
         _ => (),
     }
+
+    Ok(())
 }
 
 /// Procedure to generate the commands for the system headers of their standard C++ library
@@ -83,6 +218,13 @@ fn generate_sys_modules_commands<'a>(
     model: &'a ZorkModel<'a>,
     cache: &mut ZorkCache<'a>,
 ) -> Result<()> {
+    if model.compiler.cpp_compiler.eq(&CppCompiler::CLANG_CL) {
+        log::warn!(
+            "System headers as modules aren't yet supported for clang-cl. They won't be built"
+        );
+        return Ok(());
+    }
+
     process_kind_translation_units(
         model,
         cache,
@@ -92,6 +234,26 @@ fn generate_sys_modules_commands<'a>(
     .with_context(|| error_messages::FAILURE_SYSTEM_MODULES)
 }
 
+/// Procedure to generate the commands for the user headers declared as header units, so they
+/// can be imported by the other translation units of the project
+fn generate_header_units_commands<'a>(
+    model: &'a ZorkModel<'a>,
+    cache: &mut ZorkCache<'a>,
+) -> Result<()> {
+    if model.compiler.cpp_compiler.eq(&CppCompiler::CLANG_CL) {
+        log::warn!("Header units aren't yet supported for clang-cl. They won't be built");
+        return Ok(());
+    }
+
+    process_kind_translation_units(
+        model,
+        cache,
+        &model.modules.header_units,
+        TranslationUnitKind::HeaderUnit,
+    )
+    .with_context(|| error_messages::FAILURE_HEADER_UNITS)
+}
+
 /// The procedure that takes care of generating the [`SourceCommandLine`] to build the user's declared
 /// C++ standard names modules
 fn process_modules<'a>(model: &'a ZorkModel<'a>, cache: &mut ZorkCache<'a>) -> Result<()> {
@@ -124,15 +286,102 @@ fn process_targets<'a>(model: &'a ZorkModel<'a>, cache: &mut ZorkCache<'a>) -> R
         .iter()
         .filter(|(_, target_data)| target_data.enabled_for_current_program_iteration)
     {
-        // 1st - Generate the commands for the non-module sources
+        // 1st - Generate the command for the precompiled header of the target, if any
+        let pch_pending_to_build = generate_pch_cmd_args(model, cache, target)?;
+        // 2nd - Generate the commands for the non-module sources
         generate_sources_cmds_args(model, cache, target)?;
-        // 2nd - Generate the linker command for the 'target' declared by the user
+        // 3rd - Generate the linker command for the 'target' declared by the user
         generate_linkage_targets_commands(model, cache, target)?;
+
+        // A rebuilt precompiled header invalidates all the sources that uses it
+        if pch_pending_to_build {
+            cache
+                .generated_commands
+                .targets
+                .get_mut(target.0)
+                .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?
+                .sources
+                .iter_mut()
+                .filter(|scl| scl.status.ne(&TranslationUnitStatus::PendingToBuild))
+                .for_each(|scl| scl.mark_pending(BuildReason::DependencyRebuilt));
+        }
     }
 
     Ok(())
 }
 
+/// Generates the command for build the precompiled header of a target, which is rebuilt whenever the
+/// header, or any of the files that it includes, changes.
+///
+/// *returns: <bool>* - true if the precompiled header must be built on the current iteration
+fn generate_pch_cmd_args<'a>(
+    model: &'a ZorkModel<'a>,
+    cache: &mut ZorkCache<'a>,
+    target: (&'a TargetIdentifier<'a>, &'a TargetModel<'a>),
+) -> Result<bool> {
+    let (target_identifier, target_data) = target;
+    let compiler = model.compiler.cpp_compiler;
+
+    let pch = match &target_data.pch {
+        Some(_) if compiler.eq(&CppCompiler::CLANG_CL) => {
+            log::warn!("Precompiled headers aren't yet supported for clang-cl. The `pch` of target {:?} won't be built", target_identifier.name());
+            None
+        }
+        pch => pch.as_ref(),
+    };
+
+    let Some(pch) = pch else {
+        // The precompiled header may have been removed from the configuration file
+        if let Some(cached_target) = cache.generated_commands.targets.get_mut(target_identifier) {
+            cached_target.pch = None;
+        }
+        return Ok(false);
+    };
+
+    log::info!(
+        "Generating the command for the precompiled header of target: {:?}",
+        target_identifier.name()
+    );
+
+    let for_kind = TranslationUnitKind::PrecompiledHeader(target_identifier);
+    process_kind_translation_unit(model, cache, pch, &for_kind)
+        .with_context(|| error_messages::FAILURE_PRECOMPILED_HEADER)?;
+
+    let lpe = cache.metadata.last_program_execution;
+    let generated_cmd = cache
+        .get_cmd_for_translation_unit_kind(pch, &for_kind)
+        .with_context(|| error_messages::FAILURE_PRECOMPILED_HEADER)?;
+
+    if generated_cmd
+        .status
+        .ne(&TranslationUnitStatus::PendingToBuild)
+    {
+        let include_dirs: Vec<PathBuf> = model
+            .compiler
+            .options
+            .include_dirs
+            .iter()
+            .chain(target_data.options.include_dirs.iter())
+            .cloned()
+            .collect();
+        let includes_changed = scanner::scan_included_files(&pch.path(), &include_dirs)?
+            .iter()
+            .any(|included| helpers::file_has_changes_on_fs(&lpe, included));
+
+        if includes_changed {
+            log::trace!(
+                "A file included by the precompiled header {:?} changed since the last iteration",
+                pch.path()
+            );
+            generated_cmd.mark_pending(BuildReason::Modified);
+        }
+    }
+
+    Ok(generated_cmd
+        .status
+        .eq(&TranslationUnitStatus::PendingToBuild))
+}
+
 /// Processor for generate the commands of the non-modular translation units
 ///
 /// *NOTE*: This will be changed on the future, when we decide how we should architecture the implementation
@@ -185,30 +434,84 @@ fn generate_linkage_targets_commands<'a>(
         .linker;
 
     let compiler = &model.compiler.cpp_compiler;
-    let out_dir: &Path = model.build.output_dir.as_ref();
 
-    let target_output = Argument::from(
-        out_dir
-            .join(compiler.as_ref())
-            .join(target_identifier.name())
-            .with_extension(constants::BINARY_EXTENSION),
-    );
+    let is_archived =
+        target_details.kind.eq(&TargetKind::StaticLib) && model.compiler.archiver().is_some();
+    let target_output = Argument::from(helpers::target_output_path(
+        model,
+        target_identifier,
+        target_details.kind,
+    ));
 
     // Check if its necessary to change the target output details
     if linker.target.ne(&target_output) {
         match compiler {
+            // The archiver takes care about the output of the static libraries on its own way
+            _ if is_archived => linker.target = target_output,
             CppCompiler::CLANG | CppCompiler::GCC => linker.target = target_output,
-            CppCompiler::MSVC => linker.target = Argument::from(format!("/Fe{}", target_output)),
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => {
+                linker.target = Argument::from(format!("/Fe{}", target_output))
+            }
         };
     }
 
-    // Check if the extra args passed by the user to the linker has changed from previous
-    // iterations
-    if Iterator::ne(linker.extra_args.iter(), target_details.extra_args.iter()) {
-        linker.extra_args.clear();
-        linker
-            .extra_args
-            .extend_from_to_argument_slice(&target_details.extra_args);
+    // Check if the deprecated extra args of the target, that are passed to the linker too,
+    // has changed from previous iterations
+    if linker
+        .extra_args
+        .as_slice()
+        .ne(target_details.extra_args.as_slice())
+    {
+        linker.extra_args = Arguments::from_vec(target_details.extra_args.clone());
+    }
+
+    // Check if the libraries, their search paths or the rest of the linker only arguments
+    // (the ones declared for every target and the ones for this concrete one) has changed
+    // from previous iterations
+    // The products of the library targets that this one depends on are linked as any other library
+    let link_libraries = target_details
+        .dependencies
+        .iter()
+        .map(|dependency| {
+            let dependency_kind = model
+                .targets
+                .get(dependency)
+                .map(|dependency_model| dependency_model.kind)
+                .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?;
+            let mut output = helpers::target_output_path(model, dependency, dependency_kind);
+            if dependency_kind.eq(&TargetKind::DyLib)
+                && matches!(compiler, CppCompiler::MSVC | CppCompiler::CLANG_CL)
+            {
+                output.set_extension("lib"); // the import library of the dll
+            }
+            Ok(Cow::Owned(output.display().to_string()))
+        })
+        .chain(
+            target_details
+                .link_options
+                .link_libraries
+                .iter()
+                .cloned()
+                .map(Ok),
+        )
+        .collect::<Result<Vec<_>>>()?;
+
+    let link_options = arguments::link_options_args(
+        *compiler,
+        &LinkOptions {
+            link_libraries,
+            link_args: model
+                .compiler
+                .link_args
+                .iter()
+                .chain(target_details.link_options.link_args.iter())
+                .cloned()
+                .collect(),
+            ..target_details.link_options.clone()
+        },
+    );
+    if linker.link_options.ne(&link_options) {
+        linker.link_options = link_options;
     }
 
     Ok(())
@@ -242,53 +545,116 @@ fn process_kind_translation_unit<'a, T: TranslationUnit<'a>>(
 ) -> Result<()> {
     let lpe = cache.metadata.last_program_execution;
 
+    if cache
+        .get_cmd_for_translation_unit_kind(translation_unit, for_kind)
+        .is_none()
+    {
+        cache.metadata.generate_compilation_database = true;
+        let generated_cmd =
+            generate_translation_unit_cmd(model, cache, translation_unit, for_kind)?;
+        return cache.add_cmd_for_translation_unit_kind(generated_cmd, for_kind);
+    }
+
+    // The configuration file may have changed the arguments of an already cached command line
+    // (ex: the per-file declared ones), so they are regenerated and compared against the cached ones
+    let regenerated_args = if cache.metadata.cfg_modified {
+        Some(generate_translation_unit_cmd(model, cache, translation_unit, for_kind)?.args)
+    } else {
+        None
+    };
+
+    let mut args_changed = false;
     if let Some(generated_cmd) = cache.get_cmd_for_translation_unit_kind(translation_unit, for_kind)
     {
-        let build_translation_unit =
-            helpers::determine_translation_unit_status(&lpe, generated_cmd);
+        let (mut build_translation_unit, mut build_reason) = match for_kind {
+            // The system headers aren't files of the project, so their changes aren't tracked
+            TranslationUnitKind::SystemHeader => {
+                helpers::cached_translation_unit_status(generated_cmd)
+            }
+            _ => helpers::determine_translation_unit_status(&lpe, generated_cmd),
+        };
+
+        if let Some(args) = regenerated_args.filter(|args| generated_cmd.args.ne(args)) {
+            log::trace!(
+                "The arguments of {:?} changed since the last iteration",
+                &translation_unit.path()
+            );
+            generated_cmd.args = args;
+            build_translation_unit = TranslationUnitStatus::PendingToBuild;
+            build_reason = Some(BuildReason::ArgsChanged);
+            args_changed = true;
+        }
 
         if build_translation_unit.ne(&TranslationUnitStatus::PendingToBuild) {
             log::trace!("Source file: {:?} was not modified since the last iteration. No need to rebuilt it again.", &translation_unit.path());
         }
 
         generated_cmd.status = build_translation_unit;
-    } else {
+        generated_cmd.build_reason = build_reason;
+    }
+
+    if args_changed {
         cache.metadata.generate_compilation_database = true;
-        let tu_with_erased_type = translation_unit.as_any();
-
-        match &for_kind {
-            TranslationUnitKind::ModuleInterface => {
-                let resolved_tu =
-                    transient::Downcast::downcast_ref::<ModuleInterfaceModel>(tu_with_erased_type)
-                        .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
-                modules::generate_module_interface_cmd(model, cache, resolved_tu);
-            }
-            TranslationUnitKind::ModuleImplementation => {
-                let resolved_tu = transient::Downcast::downcast_ref::<ModuleImplementationModel>(
-                    tu_with_erased_type,
-                )
-                .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
-                modules::generate_module_implementation_cmd(model, cache, resolved_tu)
-            }
-            TranslationUnitKind::SourceFile(related_target) => {
-                let resolved_tu =
-                    transient::Downcast::downcast_ref::<SourceFile>(tu_with_erased_type)
-                        .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
-                sources::generate_sources_arguments(model, cache, resolved_tu, related_target)?;
-            }
-            TranslationUnitKind::SystemHeader => {
-                let resolved_tu =
-                    transient::Downcast::downcast_ref::<SystemModule>(tu_with_erased_type)
-                        .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
-                modules::generate_sys_module_cmd(model, cache, resolved_tu)
-            }
-            _ => (),
-        }
-    };
+    }
 
     Ok(())
 }
 
+/// Generates the [`SourceCommandLine`] of the given [`TranslationUnit`], dispatching it to the generator
+/// of its [`TranslationUnitKind`]
+fn generate_translation_unit_cmd<'a, T: TranslationUnit<'a>>(
+    model: &'a ZorkModel<'a>,
+    cache: &ZorkCache<'a>,
+    translation_unit: &'a T,
+    for_kind: &TranslationUnitKind<'a>,
+) -> Result<SourceCommandLine<'a>> {
+    let tu_with_erased_type = translation_unit.as_any();
+
+    Ok(match &for_kind {
+        TranslationUnitKind::ModuleInterface => {
+            let resolved_tu =
+                transient::Downcast::downcast_ref::<ModuleInterfaceModel>(tu_with_erased_type)
+                    .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            modules::generate_module_interface_cmd(model, cache, resolved_tu)
+        }
+        TranslationUnitKind::ModuleImplementation => {
+            let resolved_tu =
+                transient::Downcast::downcast_ref::<ModuleImplementationModel>(tu_with_erased_type)
+                    .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            modules::generate_module_implementation_cmd(model, cache, resolved_tu)
+        }
+        TranslationUnitKind::SourceFile(for_target) => {
+            let resolved_tu = transient::Downcast::downcast_ref::<SourceFile>(tu_with_erased_type)
+                .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            let target = model
+                .targets
+                .get(*for_target)
+                .with_context(|| error_messages::TARGET_ENTRY_NOT_FOUND)?;
+            sources::generate_sources_arguments(model, (for_target, target), resolved_tu)
+        }
+        TranslationUnitKind::PrecompiledHeader(for_target) => {
+            let resolved_tu = transient::Downcast::downcast_ref::<SourceFile>(tu_with_erased_type)
+                .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            sources::generate_pch_arguments(model, for_target, resolved_tu)?
+        }
+        TranslationUnitKind::SystemHeader => {
+            let resolved_tu =
+                transient::Downcast::downcast_ref::<SystemModule>(tu_with_erased_type)
+                    .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            modules::generate_sys_module_cmd(model, resolved_tu)
+        }
+        TranslationUnitKind::HeaderUnit => {
+            let resolved_tu =
+                transient::Downcast::downcast_ref::<HeaderUnitModel>(tu_with_erased_type)
+                    .with_context(|| helpers::wrong_downcast_msg(translation_unit))?;
+            modules::generate_header_unit_cmd(model, resolved_tu)?
+        }
+        TranslationUnitKind::ModularStdLib(_) => {
+            unreachable!("The modular standard libraries have their own generation procedure")
+        }
+    })
+}
+
 /// Command line arguments generators procedures for C++ standard modules
 mod modules {
     use std::path::{Path, PathBuf};
@@ -296,28 +662,29 @@ mod modules {
     use crate::cache::ZorkCache;
     use crate::compiler::helpers;
     use crate::compiler::helpers::generate_bmi_file_path;
-    use crate::domain::commands::arguments::{clang_args, msvc_args, Arguments};
+    use crate::domain::commands::arguments::{clang_args, clang_cl_args, msvc_args, Arguments};
     use crate::domain::commands::command_lines::SourceCommandLine;
-    use crate::domain::translation_unit::{TranslationUnit, TranslationUnitStatus};
+    use crate::domain::translation_unit::{BuildReason, TranslationUnit, TranslationUnitStatus};
     use crate::project_model::compiler::{CppCompiler, StdLibMode};
     use crate::project_model::modules::{
-        ModuleImplementationModel, ModuleInterfaceModel, SystemModule,
+        HeaderUnitModel, ModuleImplementationModel, ModuleInterfaceModel, SystemModule,
     };
     use crate::project_model::ZorkModel;
     use crate::utils::constants::dir_names;
+    use color_eyre::eyre::{eyre, Result};
 
     /// Generates the expected arguments for precompile the BMIs depending on self
     pub fn generate_module_interface_cmd<'a>(
         model: &'a ZorkModel<'a>,
-        cache: &mut ZorkCache<'a>,
+        cache: &ZorkCache<'a>,
         interface: &'a ModuleInterfaceModel<'a>,
-    ) {
+    ) -> SourceCommandLine<'a> {
         let mut arguments = Arguments::default();
         let compiler = model.compiler.cpp_compiler;
         let out_dir: &Path = model.build.output_dir.as_ref();
 
         // The Path of the generated binary module interface
-        let binary_module_ifc =
+        let mut binary_module_ifc =
             helpers::generate_module_output_filename(compiler, out_dir, interface);
 
         match compiler {
@@ -357,6 +724,25 @@ mod modules {
                 }
                 arguments.push("/TP");
             }
+            CppCompiler::CLANG_CL => {
+                // Single phase compilation. The BMI is emitted as a side product of the
+                // generation of the object file, which is the one sent to the linker
+                arguments.push("/TP");
+                arguments.push(format!(
+                    "/clang:-fmodule-output={}",
+                    binary_module_ifc.display()
+                ));
+                arguments.extend(clang_cl_args::add_direct_module_interfaces_dependencies(
+                    &interface.dependencies,
+                    out_dir,
+                ));
+
+                // Placed along the BMI, so it doesn't collide with the one of the implementation
+                // units of the module, that usually share the file stem of the interface
+                binary_module_ifc =
+                    binary_module_ifc.with_extension(compiler.get_obj_file_extension());
+                arguments.push(format!("/Fo{}", binary_module_ifc.display()));
+            }
             CppCompiler::GCC => {
                 arguments.push("-x");
                 arguments.push("c++");
@@ -368,17 +754,17 @@ mod modules {
 
         // The input file
         arguments.push(interface.path());
+        arguments.extend_from_slice(&interface.extra_args);
 
-        let cmd_line = SourceCommandLine::new(interface, arguments, binary_module_ifc);
-        cache.generated_commands.modules.interfaces.push(cmd_line);
+        SourceCommandLine::new(interface, arguments, binary_module_ifc)
     }
 
     /// Generates the required arguments for compile the implementation module files
     pub fn generate_module_implementation_cmd<'a>(
         model: &'a ZorkModel<'a>,
-        cache: &mut ZorkCache<'a>,
+        cache: &ZorkCache<'a>,
         implementation: &'a ModuleImplementationModel<'a>,
-    ) {
+    ) -> SourceCommandLine<'a> {
         let compiler = model.compiler.cpp_compiler;
         let out_dir = model.build.output_dir.as_ref();
 
@@ -404,6 +790,15 @@ mod modules {
                 // The output .obj file
                 arguments.push(format!("/Fo{}", obj_file_path.display()));
             }
+            CppCompiler::CLANG_CL => {
+                // The output .obj file
+                arguments.push(format!("/Fo{}", obj_file_path.display()));
+
+                arguments.extend(clang_cl_args::add_direct_module_interfaces_dependencies(
+                    &implementation.dependencies,
+                    out_dir,
+                ));
+            }
             CppCompiler::GCC => {
                 // The output file
                 arguments.push("-o");
@@ -411,58 +806,132 @@ mod modules {
             }
         }
 
-        let cmd = SourceCommandLine::new(implementation.to_owned(), arguments, obj_file_path);
-        cache.generated_commands.modules.implementations.push(cmd);
+        arguments.extend_from_slice(&implementation.extra_args);
+
+        SourceCommandLine::new(implementation, arguments, obj_file_path)
     }
 
     /// System headers can be imported as modules, but they must be built before being imported.
     ///
-    /// This feature is supported by `GCC` and `Clang`
+    /// This feature is supported by `GCC`, `Clang` and `MSVC`. The latter builds them as header units,
+    /// which must be explicitly referenced by their importers (see [`helpers::header_units_references`])
     pub(crate) fn generate_sys_module_cmd<'a>(
         model: &'a ZorkModel<'a>,
-        cache: &mut ZorkCache<'a>,
         sys_module: &'a SystemModule<'a>,
-    ) {
+    ) -> SourceCommandLine<'a> {
+        let compiler = model.compiler.cpp_compiler;
         let sys_module_name = &sys_module.file_stem;
-        let generated_bmi_path = generate_bmi_file_path(
-            &model.build.output_dir,
-            model.compiler.cpp_compiler,
-            sys_module_name,
-        );
 
         let mut args = Arguments::default();
-        args.push("-x");
-        args.push("c++-system-header");
-        args.push(sys_module_name);
-
-        match model.compiler.cpp_compiler {
-            CppCompiler::CLANG => {
-                args.push("-o");
-                args.push(&generated_bmi_path);
+        let generated_bmi_path = match compiler {
+            CppCompiler::MSVC => {
+                let ifc = helpers::generate_sys_module_ifc_path(
+                    compiler,
+                    &model.build.output_dir,
+                    sys_module_name,
+                );
+                args.push("/exportHeader");
+                args.push("/headerName:angle");
+                args.push(sys_module_name);
+                args.push("/ifcOutput");
+                args.push(&ifc);
+                args.push(format!(
+                    "/Fo{}",
+                    ifc.with_extension(compiler.get_obj_file_extension())
+                        .display()
+                ));
+                ifc
             }
-            CppCompiler::GCC => {
-                // `GCC` system headers built as modules goes directly to their `gcm.cache`
-                args.push("-fmodules-ts");
+            _ => {
+                let bmi =
+                    generate_bmi_file_path(&model.build.output_dir, compiler, sys_module_name);
+                args.push("-x");
+                args.push("c++-system-header");
+                args.push(sys_module_name);
+
+                if compiler.eq(&CppCompiler::CLANG) {
+                    args.push("-o");
+                    args.push(&bmi);
+                } else {
+                    // `GCC` system headers built as modules goes directly to their `gcm.cache`
+                    args.push("-fmodules-ts");
+                }
+                bmi
             }
-            _ => {}
         };
 
-        let cmd = SourceCommandLine {
+        SourceCommandLine {
             directory: PathBuf::default(), // NOTE: While we don't implement the lookup of the
             // system headers
-            filename: sys_module.to_string(),
+            filename: sys_module.filename(),
             args,
             status: TranslationUnitStatus::PendingToBuild,
             byproduct: generated_bmi_path.into(),
-        };
-        cache.generated_commands.modules.system_modules.push(cmd);
+            build_reason: Some(BuildReason::New),
+        }
+    }
+
+    /// Generates the arguments for build a header of the user's project as a header unit.
+    ///
+    /// `GCC` places the resultant BMI on its `gcm.cache`, so it is found by the importers
+    /// without any reference to it, while `Clang` and `MSVC` needs one explicit reference
+    /// per header unit on every importer (see [`helpers::header_units_references`])
+    pub(crate) fn generate_header_unit_cmd<'a>(
+        model: &'a ZorkModel<'a>,
+        header_unit: &'a HeaderUnitModel<'a>,
+    ) -> Result<SourceCommandLine<'a>> {
+        let compiler = model.compiler.cpp_compiler;
+        let out_dir: &Path = model.build.output_dir.as_ref();
+        let generated_bmi_path =
+            helpers::generate_header_unit_bmi_path(compiler, out_dir, header_unit);
+
+        let mut args = Arguments::default();
+        match compiler {
+            CppCompiler::CLANG => {
+                args.push("-fmodule-header=user");
+                args.push("-x");
+                args.push("c++-header");
+                args.push(header_unit.path());
+                args.push("-o");
+                args.push(&generated_bmi_path);
+            }
+            CppCompiler::MSVC => {
+                args.push("/exportHeader");
+                args.push(header_unit.path());
+                args.push("/ifcOutput");
+                args.push(&generated_bmi_path);
+                args.push(format!(
+                    "/Fo{}",
+                    generated_bmi_path
+                        .with_extension(compiler.get_obj_file_extension())
+                        .display()
+                ));
+            }
+            CppCompiler::GCC => {
+                args.push("-x");
+                args.push("c++-user-header");
+                args.push(header_unit.path());
+            }
+            CppCompiler::CLANG_CL => {
+                return Err(eyre!(
+                    "Header units aren't supported for clang-cl: {:?}",
+                    header_unit.path()
+                ));
+            }
+        }
+
+        Ok(SourceCommandLine::new(
+            header_unit,
+            args,
+            generated_bmi_path,
+        ))
     }
 
     pub(crate) fn generate_modular_cpp_stdlib_args<'a>(
         model: &'a ZorkModel<'a>,
         cache: &mut ZorkCache<'a>,
         stdlib_mode: StdLibMode,
-    ) {
+    ) -> Result<()> {
         let cached_stdlib_cmd = cache.get_cpp_stdlib_cmd_by_kind(stdlib_mode);
         if cached_stdlib_cmd.is_none()
             && cached_stdlib_cmd
@@ -480,64 +949,112 @@ mod modules {
                 CppCompiler::CLANG => clang_args::generate_std_cmd(cache, stdlib_mode),
                 CppCompiler::MSVC => msvc_args::generate_std_cmd(cache, stdlib_mode),
                 CppCompiler::GCC => todo!(),
+                CppCompiler::CLANG_CL => {
+                    return Err(eyre!(
+                        "The {} isn't supported for clang-cl",
+                        stdlib_mode.printable_info()
+                    ));
+                }
             };
             cache.set_cpp_stdlib_cmd_by_kind(stdlib_mode, Some(scl));
         }
+
+        Ok(())
     }
 }
 
 /// Specific operations over source files
 mod sources {
-    use crate::cache::ZorkCache;
     use crate::domain::commands::arguments::Arguments;
     use crate::domain::commands::command_lines::SourceCommandLine;
     use crate::domain::target::TargetIdentifier;
     use crate::domain::translation_unit::TranslationUnit;
     use crate::project_model::sourceset::SourceFile;
+    use crate::project_model::target::TargetModel;
     use crate::project_model::{compiler::CppCompiler, ZorkModel};
-    use crate::utils::constants::error_messages;
-    use color_eyre::eyre::{ContextCompat, Result};
+    use color_eyre::eyre::{eyre, Result};
 
     use super::helpers;
 
     /// Generates the command line arguments for non-module source files
     pub fn generate_sources_arguments<'a>(
         model: &'a ZorkModel<'a>,
-        cache: &mut ZorkCache<'a>,
+        target: (&TargetIdentifier, &'a TargetModel<'a>),
         source: &'a SourceFile<'a>,
-        target_identifier: &TargetIdentifier<'a>,
-    ) -> Result<()> {
+    ) -> SourceCommandLine<'a> {
         let compiler = model.compiler.cpp_compiler;
         let out_dir = model.build.output_dir.as_ref();
 
         let mut arguments = Arguments::default();
 
-        let obj_file = helpers::generate_obj_file(compiler, out_dir, source);
+        let obj_file = helpers::generate_source_obj_file(compiler, out_dir, target.0, source);
         match compiler {
             CppCompiler::CLANG | CppCompiler::GCC => {
                 arguments.push("-o");
                 arguments.push(&obj_file);
             }
-            CppCompiler::MSVC => arguments.push(format!("/Fo{}", obj_file.display())),
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => {
+                arguments.push(format!("/Fo{}", obj_file.display()))
+            }
         }
+
+        if let Some(pch) = &target.1.pch {
+            arguments.extend(helpers::pch_usage_args(compiler, out_dir, target.0, pch));
+        }
+
         arguments.push(source.path());
+        arguments.extend_from_slice(&source.extra_args);
 
-        let command_line = SourceCommandLine::new(source, arguments, obj_file);
-        cache
-            .generated_commands
-            .targets
-            .get_mut(target_identifier)
-            .with_context(|| {
-                format!(
-                    "{}: {:?}",
-                    error_messages::TARGET_ENTRY_NOT_FOUND,
-                    target_identifier
-                )
-            })?
-            .sources
-            .push(command_line);
+        SourceCommandLine::new(source, arguments, obj_file)
+    }
 
-        Ok(())
+    /// Generates the command line arguments for build the precompiled header of a target
+    pub fn generate_pch_arguments<'a>(
+        model: &'a ZorkModel<'a>,
+        target_identifier: &TargetIdentifier,
+        header: &'a SourceFile<'a>,
+    ) -> Result<SourceCommandLine<'a>> {
+        let compiler = model.compiler.cpp_compiler;
+        let out_dir = model.build.output_dir.as_ref();
+        let pch_file =
+            helpers::generate_pch_file_path(compiler, out_dir, target_identifier, header);
+
+        let mut arguments = Arguments::default();
+        match compiler {
+            CppCompiler::CLANG | CppCompiler::GCC => {
+                if compiler.eq(&CppCompiler::GCC) {
+                    // Otherwise, GCC builds the header as a header unit
+                    arguments.push("-fno-modules-ts");
+                }
+                arguments.push("-x");
+                arguments.push("c++-header");
+                arguments.push(header.path());
+                arguments.push("-o");
+                arguments.push(&pch_file);
+            }
+            CppCompiler::MSVC => {
+                // The object file generated along the precompiled header must be sent to the linker
+                arguments.push("/TP");
+                arguments.push(format!("/Yc{}", header.path().display()));
+                arguments.push(format!("/FI{}", header.path().display()));
+                arguments.push(format!("/Fp{}", pch_file.display()));
+                arguments.push(format!(
+                    "/Fo{}",
+                    pch_file
+                        .with_extension(compiler.get_obj_file_extension())
+                        .display()
+                ));
+                arguments.push(header.path());
+            }
+            CppCompiler::CLANG_CL => {
+                return Err(eyre!(
+                    "Precompiled headers aren't supported for clang-cl: {:?}",
+                    header.path()
+                ));
+            }
+        }
+
+        Ok(SourceCommandLine::new(header, arguments, pch_file))
     }
 }
 
@@ -548,8 +1065,9 @@ mod sources {
 /// This module is actually public(crate) reexported since we need to
 pub(crate) mod helpers {
     use super::*;
+    use crate::domain::commands::arguments::Arguments;
     use crate::domain::commands::command_lines::SourceCommandLine;
-    use crate::domain::translation_unit::TranslationUnitStatus;
+    use crate::domain::translation_unit::{BuildReason, TranslationUnitStatus};
     use crate::utils::constants::dir_names;
     use chrono::{DateTime, Utc};
     use std::path::PathBuf;
@@ -577,6 +1095,150 @@ pub(crate) mod helpers {
         generate_bmi_file_path(out_dir, compiler, &module_filename)
     }
 
+    /// Generates the [`PathBuf`] of the final product of a target
+    pub(crate) fn target_output_path(
+        model: &ZorkModel,
+        target_identifier: &TargetIdentifier,
+        kind: TargetKind,
+    ) -> PathBuf {
+        let out_dir: &Path = model.build.output_dir.as_ref();
+        out_dir
+            .join(model.compiler.cpp_compiler.as_ref())
+            .join(target_identifier.as_path())
+            .with_extension(if kind.eq(&TargetKind::StaticLib) {
+                model.compiler.static_lib_extension()
+            } else {
+                model.compiler.toolchain.binary_extension()
+            })
+    }
+
+    /// Generates the [`PathBuf`] of the precompiled header of a target. `GCC` looks for the
+    /// precompiled header next to the included one, adding the `.gch` extension to its filename
+    pub(crate) fn generate_pch_file_path(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        target_identifier: &TargetIdentifier,
+        header: &SourceFile,
+    ) -> PathBuf {
+        let extension = match compiler {
+            CppCompiler::GCC => "gch",
+            _ => "pch",
+        };
+
+        out_dir
+            .join(compiler.as_ref())
+            .join(dir_names::PCH)
+            .join(target_identifier.as_path())
+            .join(format!("{}.{extension}", header.filename()))
+    }
+
+    /// Generates the arguments that makes the sources of a target use its precompiled header
+    pub(crate) fn pch_usage_args<'a>(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        target_identifier: &TargetIdentifier,
+        header: &SourceFile,
+    ) -> Arguments<'a> {
+        let pch_file = generate_pch_file_path(compiler, out_dir, target_identifier, header);
+        let mut arguments = Arguments::default();
+
+        match compiler {
+            CppCompiler::CLANG => {
+                arguments.push("-include-pch");
+                arguments.push(pch_file);
+            }
+            CppCompiler::GCC => {
+                // GCC rejects a precompiled header built with a different modules mode, so the
+                // sources of a target with a precompiled header can't import modules with GCC
+                arguments.push("-fno-modules-ts");
+                arguments.push("-Winvalid-pch");
+                arguments.push("-include");
+                arguments.push(pch_file.with_extension(""));
+            }
+            CppCompiler::MSVC => {
+                arguments.push(format!("/Yu{}", header.path().display()));
+                arguments.push(format!("/FI{}", header.path().display()));
+                arguments.push(format!("/Fp{}", pch_file.display()));
+            }
+            CppCompiler::CLANG_CL => (),
+        }
+
+        arguments
+    }
+
+    /// Generates the [`PathBuf`] of the resultant binary module interface of a user header built
+    /// as a header unit. The filename keeps the extension of the header, so headers that only
+    /// differs on their extension doesn't collide
+    pub(crate) fn generate_header_unit_bmi_path(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        header_unit: &HeaderUnitModel,
+    ) -> PathBuf {
+        header_units_dir(compiler, out_dir).join(format!(
+            "{}.{}",
+            header_unit.filename(),
+            compiler.get_typical_bmi_extension()
+        ))
+    }
+
+    /// Generates the [`PathBuf`] of the `.ifc` of a system header built as a header unit by `MSVC`
+    pub(crate) fn generate_sys_module_ifc_path(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        sys_module_name: &str,
+    ) -> PathBuf {
+        header_units_dir(compiler, out_dir).join(format!(
+            "{sys_module_name}.{}",
+            compiler.get_typical_bmi_extension()
+        ))
+    }
+
+    fn header_units_dir(compiler: CppCompiler, out_dir: &Path) -> PathBuf {
+        out_dir
+            .join(compiler.as_ref())
+            .join(dir_names::MODULES)
+            .join(dir_names::HEADER_UNITS)
+    }
+
+    /// Generates the arguments that makes the header units visible to their importers. The system
+    /// headers built as modules are only referenced for `MSVC`, since `Clang` and `GCC` finds them
+    /// on their own
+    pub(crate) fn header_units_references<'a>(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        header_units: &[HeaderUnitModel],
+        sys_modules: &[SystemModule],
+    ) -> Arguments<'a> {
+        let mut references = Arguments::default();
+
+        if compiler.eq(&CppCompiler::MSVC) {
+            for sys_module in sys_modules {
+                let ifc = generate_sys_module_ifc_path(compiler, out_dir, &sys_module.file_stem);
+                references.push("/headerUnit:angle");
+                references.push(format!("{}={}", sys_module.file_stem, ifc.display()));
+            }
+        }
+
+        for header_unit in header_units {
+            let bmi = generate_header_unit_bmi_path(compiler, out_dir, header_unit);
+            match compiler {
+                CppCompiler::CLANG => references.push(format!("-fmodule-file={}", bmi.display())),
+                CppCompiler::MSVC => {
+                    references.push("/headerUnit");
+                    references.push(format!(
+                        "{}={}",
+                        header_unit.path().display(),
+                        bmi.display()
+                    ));
+                }
+                // GCC finds them on its gcm.cache, and they aren't built for clang-cl
+                CppCompiler::GCC | CppCompiler::CLANG_CL => break,
+            }
+        }
+
+        references
+    }
+
     /// Generates the [`PathBuf`] of the resultant binary module interface file of a C++ module interface
     pub(crate) fn generate_bmi_file_path(
         out_dir: &Path,
@@ -611,22 +1273,37 @@ pub(crate) mod helpers {
             .with_extension(compiler.get_obj_file_extension())
     }
 
+    /// Generates the [`PathBuf`] of the `.obj` file of a source of a target. The object files of
+    /// the sources of the workspace members are nested under a directory named after their member
+    pub(crate) fn generate_source_obj_file(
+        compiler: CppCompiler,
+        out_dir: &Path,
+        target_identifier: &TargetIdentifier,
+        source: &SourceFile,
+    ) -> PathBuf {
+        let obj_file = generate_obj_file(compiler, out_dir, source);
+        match (target_identifier.member(), obj_file.file_name()) {
+            (Some(member), Some(filename)) => obj_file.with_file_name(member).join(filename),
+            _ => obj_file,
+        }
+    }
+
     /// Template factory function to call the inspectors of the status of a file on the fs that
     /// is represented within `Zork++` as some kind of [`TranslationUnit`] and the status flags
     /// tracked on the entities like [`SourceCommandLine::status`] and others from the [`ZorkCache`]
     /// as well to determine when a concrete user declared file must be sent to the compiler in order
     /// to be built, or we can skip it
     ///
-    /// *returns: <[`TranslationUnitStatus`]>* - The state that should be set to the current
-    /// [`SourceCommandLine`] in order to be handled
+    /// *returns: <([`TranslationUnitStatus`], Option<[`BuildReason`]>)>* - The state that should be set
+    /// to the current [`SourceCommandLine`] in order to be handled, and why it must be built, if so
     pub(crate) fn determine_translation_unit_status(
         last_process_execution: &DateTime<Utc>,
         cached_source_cmd: &SourceCommandLine,
-    ) -> TranslationUnitStatus {
+    ) -> (TranslationUnitStatus, Option<BuildReason>) {
         // In case the user deleted the translation unit from the fs but not from the Zork++ cfg file
         let translation_unit_has_been_deleted = !cached_source_cmd.path().exists();
         if translation_unit_has_been_deleted {
-            return TranslationUnitStatus::ToDelete;
+            return (TranslationUnitStatus::ToDelete, None);
         }
 
         // In case the file suffered changes
@@ -634,12 +1311,27 @@ pub(crate) mod helpers {
             translation_unit_has_changes_on_fs(last_process_execution, cached_source_cmd);
 
         if need_to_build {
-            TranslationUnitStatus::PendingToBuild
+            (
+                TranslationUnitStatus::PendingToBuild,
+                Some(BuildReason::Modified),
+            )
         } else {
-            compute_translation_unit_status(cached_source_cmd)
+            cached_translation_unit_status(cached_source_cmd)
         }
     }
 
+    /// The status of a cached command whose translation unit didn't change since the last
+    /// iteration, which is only built again when it didn't succeed on it
+    pub(crate) fn cached_translation_unit_status(
+        cached_source_cmd: &SourceCommandLine,
+    ) -> (TranslationUnitStatus, Option<BuildReason>) {
+        let status = compute_translation_unit_status(cached_source_cmd);
+        let reason = status
+            .eq(&TranslationUnitStatus::PendingToBuild)
+            .then_some(BuildReason::NotBuilt);
+        (status, reason)
+    }
+
     /// Checks whenever a [`TranslationUnit`] has been modified on the filesystem and its changes
     /// was made *after* the last time that `Zork++` made a run.
     ///
@@ -649,7 +1341,11 @@ pub(crate) mod helpers {
         last_process_execution: &DateTime<Utc>,
         cached_source_cmd: &SourceCommandLine,
     ) -> bool {
-        let file = cached_source_cmd.path();
+        file_has_changes_on_fs(last_process_execution, &cached_source_cmd.path())
+    }
+
+    /// Checks whenever a file has been modified *after* the last time that `Zork++` made a run
+    pub fn file_has_changes_on_fs(last_process_execution: &DateTime<Utc>, file: &Path) -> bool {
         let file_metadata = file.metadata();
 
         // If exists and was successful, let's see if has been modified after the program last iteration
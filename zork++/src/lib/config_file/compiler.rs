@@ -10,7 +10,9 @@ use crate::project_model;
 ///
 /// * `cpp_compiler` - One of the available compilers within Zork++
 ///     They are represented by an enumerated type named [`CppCompiler`],
-///     that holds the different options where the user can choose
+///     that holds the different options where the user can choose.
+///     `clang-cl` is accepted as well, which uses the MSVC style flags for the
+///     regular compilation work, but Clang's ones for the modules related work
 ///
 /// * `driver_path` - The specific command line terminal identifier that will
 ///     call the compiler's binary. ie: clang++-15 will call a specific installation
@@ -25,20 +27,42 @@ use crate::project_model;
 /// * `std_lib` - The concrete C++ standard library (vendor specific)
 ///     to link the built code against
 ///
-/// * `extra_args` - A comma separated list of strings that will be passed
-///     to the generated command lines. This ones here will be placed in every
-///     command line generated by Zork++.
-///     For example, if *['-O3', '-Wall']*
-///     are included here, this will be wired in the main command line (the executable),
-///     the ones generated for compile modules (both interfaces and implementations)
-///     and for the command line generated for build the specified test suite and
-///     the test executable
+/// * `defines` - Preprocessor macro definitions, written as `NAME` or `NAME=VALUE`
+///
+/// * `include_dirs` - Directories (relative to the code root) where the compiler will look for headers
+///
+/// * `system_include_dirs` - Same as `include_dirs`, but the compiler will treat the headers found
+///   there as system ones, so no warnings will be reported for them
+///
+/// * `warnings` - The warnings level. One of `all`, `extra` or `error`. Each level includes the
+///   previous ones, and `error` treats the warnings as errors
+///
+/// * `optimization` - The optimization level. One of `0`, `1`, `2`, `3` or `s` (optimize for size)
+///
+/// * `debug_info` - Whenever the debug information must be generated
+///
+/// The options from `defines` to `debug_info` are translated by Zork++ into the syntax of the
+/// selected compiler, so they won't break when switching between compilers
+///
+/// * `launcher` - A program which the compile commands will be prefixed with, like
+///   a compiler cache (ex: `ccache` or `sccache`). It's not applied on the precompilation
+///   of the module interfaces nor on the linker invocations
+///
+/// * `compile_args` - A comma separated list of strings that will be passed
+///   to every compile command line generated by Zork++ (module interfaces,
+///   module implementations and the sources of every target), but not to
+///   the linker command lines
+///
+/// * `link_args` - A comma separated list of strings that will be passed
+///   only to the linker command line of every target
+///
+/// * `extra_args` - Deprecated, in favour of `compile_args` and `link_args`. Passed to both the compile and the link command lines
 ///
 /// ### Tests
 ///
 /// ```rust
 /// use zork::config_file::compiler::{
-///     CompilerAttribute, CppCompiler, LanguageLevel, StdLib
+///     CompilerAttribute, CppCompiler, LanguageLevel, OptimizationLevel, StdLib, WarningsLevel
 /// };
 ///
 /// const CONFIG_FILE_MOCK: &str = r#"
@@ -49,6 +73,15 @@ use crate::project_model;
 ///     std_lib = 'libcpp'
 ///     std_lib_installed_dir = '/usr/include/c++/v1'
 ///     extra_args = ['-O3', '-Wall']
+///     compile_args = ['-fno-rtti']
+///     link_args = ['-static']
+///     launcher = 'ccache'
+///     defines = ['ZORK', 'VERSION=2']
+///     include_dirs = ['include']
+///     system_include_dirs = ['deps/include']
+///     warnings = 'extra'
+///     optimization = '2'
+///     debug_info = true
 ///"#;
 ///
 /// let config: CompilerAttribute = toml::from_str(CONFIG_FILE_MOCK)
@@ -61,6 +94,15 @@ use crate::project_model;
 /// assert_eq!(config.std_lib_installed_dir, Some("/usr/include/c++/v1"));
 /// assert_eq!(config.extra_args, Some(vec!["-O3", "-Wall"]));
 /// assert_eq!(config.system_headers_path, None);
+/// assert_eq!(config.compile_args, Some(vec!["-fno-rtti"]));
+/// assert_eq!(config.link_args, Some(vec!["-static"]));
+/// assert_eq!(config.launcher, Some("ccache"));
+/// assert_eq!(config.defines, Some(vec!["ZORK", "VERSION=2"]));
+/// assert_eq!(config.include_dirs, Some(vec!["include"]));
+/// assert_eq!(config.system_include_dirs, Some(vec!["deps/include"]));
+/// assert_eq!(config.warnings, Some(WarningsLevel::EXTRA));
+/// assert_eq!(config.optimization, Some(OptimizationLevel::O2));
+/// assert_eq!(config.debug_info, Some(true));
 /// ```
 ///
 /// > Note: TOML table are toml commented (#) to allow us to parse
@@ -84,7 +126,22 @@ pub struct CompilerAttribute<'a> {
     #[serde(borrow)]
     pub extra_args: Option<Vec<&'a str>>,
     #[serde(borrow)]
+    pub compile_args: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub link_args: Option<Vec<&'a str>>,
+    #[serde(borrow)]
     pub system_headers_path: Option<&'a str>,
+    #[serde(borrow)]
+    pub launcher: Option<&'a str>,
+    #[serde(borrow)]
+    pub defines: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub include_dirs: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub system_include_dirs: Option<Vec<&'a str>>,
+    pub warnings: Option<WarningsLevel>,
+    pub optimization: Option<OptimizationLevel>,
+    pub debug_info: Option<bool>,
 }
 
 /// The C++ compilers available within Zork++
@@ -97,6 +154,14 @@ pub enum CppCompiler {
     MSVC,
     #[serde(alias = "GCC", alias = "Gcc", alias = "gcc")]
     GCC,
+    #[serde(
+        alias = "CLANG_CL",
+        alias = "ClangCl",
+        alias = "clang-cl",
+        alias = "clang_cl"
+    )]
+    #[allow(non_camel_case_types)]
+    CLANG_CL,
 }
 
 // Clippy warns to prefer implementing the From trait instead of Into.
@@ -108,6 +173,7 @@ impl Into<project_model::compiler::CppCompiler> for CppCompiler {
             CppCompiler::CLANG => project_model::compiler::CppCompiler::CLANG,
             CppCompiler::MSVC => project_model::compiler::CppCompiler::MSVC,
             CppCompiler::GCC => project_model::compiler::CppCompiler::GCC,
+            CppCompiler::CLANG_CL => project_model::compiler::CppCompiler::CLANG_CL,
         }
     }
 }
@@ -171,3 +237,57 @@ impl Into<project_model::compiler::StdLib> for StdLib {
         }
     }
 }
+
+/// The levels of the warnings reported by the compiler
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningsLevel {
+    #[serde(alias = "all")]
+    ALL,
+    #[serde(alias = "extra")]
+    EXTRA,
+    #[serde(alias = "error")]
+    ERROR,
+}
+
+// Clippy warns to prefer implementing the From trait instead of Into.
+// That would require that the project model know about config_file details, which is ugly.
+#[allow(clippy::from_over_into)]
+impl Into<project_model::compiler::WarningsLevel> for WarningsLevel {
+    fn into(self) -> project_model::compiler::WarningsLevel {
+        match self {
+            WarningsLevel::ALL => project_model::compiler::WarningsLevel::ALL,
+            WarningsLevel::EXTRA => project_model::compiler::WarningsLevel::EXTRA,
+            WarningsLevel::ERROR => project_model::compiler::WarningsLevel::ERROR,
+        }
+    }
+}
+
+/// The optimization levels applied by the compiler over the generated code
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    #[serde(alias = "0")]
+    O0,
+    #[serde(alias = "1")]
+    O1,
+    #[serde(alias = "2")]
+    O2,
+    #[serde(alias = "3")]
+    O3,
+    #[serde(alias = "s")]
+    OS,
+}
+
+// Clippy warns to prefer implementing the From trait instead of Into.
+// That would require that the project model know about config_file details, which is ugly.
+#[allow(clippy::from_over_into)]
+impl Into<project_model::compiler::OptimizationLevel> for OptimizationLevel {
+    fn into(self) -> project_model::compiler::OptimizationLevel {
+        match self {
+            OptimizationLevel::O0 => project_model::compiler::OptimizationLevel::O0,
+            OptimizationLevel::O1 => project_model::compiler::OptimizationLevel::O1,
+            OptimizationLevel::O2 => project_model::compiler::OptimizationLevel::O2,
+            OptimizationLevel::O3 => project_model::compiler::OptimizationLevel::O3,
+            OptimizationLevel::OS => project_model::compiler::OptimizationLevel::OS,
+        }
+    }
+}
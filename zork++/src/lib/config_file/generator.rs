@@ -0,0 +1,50 @@
+//! file that contains the configuration options available
+//! within Zork++ to declare the rules that generate source files
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// [`GeneratorAttribute`] - A rule that generates files of the project through a custom
+/// command (ex: `protoc`, or any other IDL compiler), declared as an entry of the
+/// `[[generators]]` array of tables
+///
+/// * `command` - The command that generates the files. Like on any other string value, the
+///   variables of the configuration file (ex: `${out_dir}`) are expanded on it
+/// * `inputs` - The files (relative to the code root, or glob patterns) read by the command.
+///   The command is only executed again when any of them, or the command itself, changes
+/// * `outputs` - The files generated by the command, relative to the code root. The
+///   `${out_dir}` variable can be used for placing them on the output directory
+/// * `target` - The target whose sources are extended with the generated C++ source files
+///
+/// ```rust
+/// use zork::config_file::generator::GeneratorAttribute;
+///
+/// const CONFIG_FILE_MOCK: &str = r#"
+///     #[[generators]]
+///     command = 'protoc --cpp_out=${out_dir}/gen proto/messages.proto'
+///     inputs = ['proto/messages.proto']
+///     outputs = ['${out_dir}/gen/messages.pb.cc', '${out_dir}/gen/messages.pb.h']
+///     target = 'app'
+///"#;
+///
+/// let config: GeneratorAttribute = toml::from_str(CONFIG_FILE_MOCK)
+///    .expect("A failure happened parsing the Zork toml file");
+///
+/// assert_eq!(config.command, "protoc --cpp_out=${out_dir}/gen proto/messages.proto");
+/// assert_eq!(config.inputs, Some(vec!["proto/messages.proto"]));
+/// assert_eq!(config.outputs, vec!["${out_dir}/gen/messages.pb.cc", "${out_dir}/gen/messages.pb.h"]);
+/// assert_eq!(config.target, Some("app"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GeneratorAttribute<'a> {
+    #[serde(borrow)]
+    pub command: Cow<'a, str>,
+    #[serde(borrow)]
+    pub inputs: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub outputs: Vec<&'a str>,
+    #[serde(borrow)]
+    pub target: Option<&'a str>,
+}
@@ -2,28 +2,36 @@
 //! parsed data lives.
 pub mod build;
 pub mod compiler;
+pub mod generator;
 pub mod modules;
 pub mod project;
+pub mod schema;
 pub mod target;
+pub mod toolchain;
+pub mod workspace;
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use self::{
-    build::BuildAttribute, compiler::CompilerAttribute, modules::ModulesAttribute,
-    project::ProjectAttribute, target::TargetAttribute,
+    build::BuildAttribute, compiler::CompilerAttribute, generator::GeneratorAttribute,
+    modules::ModulesAttribute, project::ProjectAttribute, target::TargetAttribute,
+    toolchain::ToolchainAttribute, workspace::WorkspaceAttribute,
 };
 
 /// ```rust
 /// use zork::config_file::{
 ///     ZorkConfigFile,
 ///     compiler::{CppCompiler, LanguageLevel},
-///     target::TargetAttribute
+///     target::{TargetAttribute, TargetSource}
 /// };
 /// use zork::domain::target::TargetKind;
 /// use indexmap::IndexMap;
 ///
 /// const CONFIG_FILE_MOCK: &str = r#"
+///     extends = 'zork_base.toml'
+///     include = [ 'zork_warnings.toml' ]
+///
 ///     [project]
 ///     name = 'Zork++ serde tests'
 ///     authors = ['zerodaycode.gz@gmail.com']
@@ -49,6 +57,9 @@ use self::{
 /// let config: ZorkConfigFile = toml::from_str(CONFIG_FILE_MOCK)
 ///     .expect("A failure happened parsing the Zork toml file");
 ///
+/// assert_eq!(config.extends, Some("zork_base.toml"));
+/// assert_eq!(config.include, Some(vec!["zork_warnings.toml"]));
+///
 /// let compiler_attribute = &config.compiler;
 /// assert_eq!(compiler_attribute.cpp_compiler, CppCompiler::CLANG);
 /// assert_eq!(compiler_attribute.cpp_standard, LanguageLevel::CPP20);
@@ -59,27 +70,35 @@ use self::{
 /// let executable_target: &TargetAttribute<'_> = targets.get("executable").expect("Target named
 ///     'executable' not found on the configuration");
 /// assert!(executable_target.output_name.unwrap().contains("final binary"));
-/// assert!(executable_target.sources.contains(&"main.cpp"));
+/// assert!(executable_target.sources.contains(&TargetSource::Path("main.cpp")));
 /// assert!(executable_target.extra_args.as_ref().unwrap().contains(&"-Wall"));
 /// assert!(executable_target.kind.unwrap_or_default().eq(&TargetKind::Executable));
 ///
 /// let tests_target: &TargetAttribute<'_> = targets.get("tests").expect("Target named
 ///     'tests' not found on the configuration");
-/// assert!(tests_target.sources.contains(&"tests_main.cpp"));
+/// assert!(tests_target.sources.contains(&TargetSource::Path("tests_main.cpp")));
 /// assert!(tests_target.extra_args.is_none());
 /// assert!(tests_target.kind.unwrap_or_default().eq(&TargetKind::Executable));
 ///
 /// let other_tests_target: &TargetAttribute<'_> = targets.get("other_tests").expect("Target named
 ///     'other_tests' not found on the configuration");
-/// assert!(other_tests_target.sources.contains(&"other_tests_main.cpp"));
+/// assert!(other_tests_target.sources.contains(&TargetSource::Path("other_tests_main.cpp")));
 /// assert!(other_tests_target.extra_args.is_none());
 /// assert!(other_tests_target.kind.unwrap_or_default().eq(&TargetKind::Executable));
 /// ```
 /// The [`ZorkConfigFile`] is the type that holds
 /// the whole hierarchy of Zork++ config file attributes
 /// and properties
+///
+/// The `extends` and `include` attributes are the paths of the configuration files that this one
+/// inherits from, which are already merged into it when it's read by
+/// [`crate::utils::inheritance::read_config_file`]
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ZorkConfigFile<'a> {
+    #[serde(borrow)]
+    pub extends: Option<&'a str>,
+    #[serde(borrow)]
+    pub include: Option<Vec<&'a str>>,
     #[serde(borrow)]
     pub project: ProjectAttribute<'a>,
     #[serde(borrow)]
@@ -88,8 +107,14 @@ pub struct ZorkConfigFile<'a> {
     pub build: Option<BuildAttribute<'a>>,
     #[serde(borrow)]
     pub modules: Option<ModulesAttribute<'a>>,
-    #[serde(deserialize_with = "deserialize_targets")]
+    #[serde(borrow)]
+    pub toolchain: Option<ToolchainAttribute<'a>>,
+    #[serde(default, deserialize_with = "deserialize_targets")]
     pub targets: IndexMap<&'a str, TargetAttribute<'a>>,
+    #[serde(borrow)]
+    pub generators: Option<Vec<GeneratorAttribute<'a>>>,
+    #[serde(borrow)]
+    pub workspace: Option<WorkspaceAttribute<'a>>,
 }
 
 fn deserialize_targets<'de, D>(
@@ -102,6 +127,28 @@ where
     Ok(helper)
 }
 
+/// The layout of a configuration file seen only through its `[workspace]` table
+#[derive(Deserialize, Debug)]
+struct WorkspaceRoot<'a> {
+    #[serde(borrow)]
+    workspace: Option<WorkspaceAttribute<'a>>,
+}
+
+/// Parses just the `[workspace]` table of a configuration file, if it's present
+///
+/// ```rust
+/// use zork::config_file::workspace_from_file;
+///
+/// let workspace = workspace_from_file("[project]\nname = 'root'\n[workspace]\nmembers = ['libs/*']")
+///     .expect("A failure happened parsing the Zork toml file");
+/// assert_eq!(workspace.map(|ws| ws.members), Some(vec!["libs/*"]));
+/// ```
+pub fn workspace_from_file(
+    cfg: &'_ str,
+) -> Result<Option<WorkspaceAttribute<'_>>, toml::de::Error> {
+    <WorkspaceRoot>::deserialize(&mut toml::Deserializer::new(cfg)).map(|root| root.workspace)
+}
+
 pub fn zork_cfg_from_file(cfg: &'_ str) -> Result<ZorkConfigFile<'_>, toml::de::Error> {
     <ZorkConfigFile>::deserialize(&mut toml::Deserializer::new(cfg))
 }
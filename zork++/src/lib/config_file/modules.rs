@@ -1,14 +1,26 @@
 //!!  The core section to instruct the compiler to work with C++20 modules. The most important are the base path to the interfaces and implementation files
 
-use serde::{Deserialize, Serialize};
+use core::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// [`ModulesAttribute`] -  The core section to instruct the compiler to work with C++20 modules.
 /// * `base_ifcs_dir`- Base directory to shortcut the path of the implementation files
 /// * `interfaces` - A list to define the module interface translation units for the project
 /// * `base_impls_dir` - Base directory to shortcut the path of the implementation files
 /// * `implementations` - A list to define the module interface translation units for the project
+///
+/// The entries of `interfaces` and `implementations` can be written as tables, or just as the path
+/// of the file. In both cases, the path can be a glob pattern (ex: `ifc/**/*.cppm`). The module names,
+/// partitions and dependencies of the files matched by a glob are inferred from their contents (or
+/// from their names, following the `module_name-partition_name` convention), and any file that is
+/// also explicitly declared takes the properties of its explicit declaration
 /// * `sys_modules` - An array field explicitly declare which system headers
 ///     must be precompiled in order to make the importable translation units
+/// * `header_units` - The headers of the project (relative to the code root) that must be built
+///   as header units, so they can be imported (ex: `import "foo.h";`). Glob patterns are allowed
 ///
 /// ### Tests
 ///
@@ -17,13 +29,14 @@ use serde::{Deserialize, Serialize};
 /// const CONFIG_FILE_MOCK: &str = r#"
 ///     base_ifcs_dir = "./ifc"
 ///     interfaces = [
-///         { file = 'math.cppm' }, { file = 'some_module.cppm', module_name = 'math' }
+///         { file = 'math.cppm' }, { file = 'some_module.cppm', module_name = 'math' }, 'ifc/**/*.cppm'
 ///     ]
 ///     base_impls_dir = './src'
 ///     implementations = [
 ///         { file = 'math.cpp' }, { file = 'some_module_impl.cpp', dependencies = ['iostream'] }
 ///     ]
 ///     sys_modules = ['iostream', 'vector', 'string', 'type_traits', 'functional']
+///     header_units = ['include/foo.h', 'include/utils/*.hpp']
 /// "#;
 ///
 /// let config: ModulesAttribute = toml::from_str(CONFIG_FILE_MOCK)
@@ -38,6 +51,9 @@ use serde::{Deserialize, Serialize};
 /// let ifc_1 = &ifcs[1];
 /// assert_eq!(ifc_1.file, "some_module.cppm");
 /// assert_eq!(ifc_1.module_name, Some("math"));
+/// let ifc_2 = &ifcs[2];
+/// assert_eq!(ifc_2.file, "ifc/**/*.cppm");
+/// assert_eq!(ifc_2.module_name, None);
 ///
 ///
 /// assert_eq!(config.base_impls_dir, Some("./src"));
@@ -57,19 +73,24 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(&gcc_sys_headers[2], &"string");
 /// assert_eq!(&gcc_sys_headers[3], &"type_traits");
 /// assert_eq!(&gcc_sys_headers[4], &"functional");
+///
+/// let header_units = config.header_units.unwrap();
+/// assert_eq!(header_units, vec!["include/foo.h", "include/utils/*.hpp"]);
 /// ```
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 pub struct ModulesAttribute<'a> {
     #[serde(borrow)]
     pub base_ifcs_dir: Option<&'a str>,
-    #[serde(borrow)]
+    #[serde(borrow, default, deserialize_with = "file_or_table")]
     pub interfaces: Option<Vec<ModuleInterface<'a>>>,
     #[serde(borrow)]
     pub base_impls_dir: Option<&'a str>,
-    #[serde(borrow)]
+    #[serde(borrow, default, deserialize_with = "file_or_table")]
     pub implementations: Option<Vec<ModuleImplementation<'a>>>,
     #[serde(borrow)]
     pub sys_modules: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub header_units: Option<Vec<&'a str>>,
 }
 
 /// [`ModuleInterface`] -  A module interface structure for dealing
@@ -88,6 +109,10 @@ pub struct ModulesAttribute<'a> {
 ///
 /// * `dependencies` - An optional array field for declare the module interfaces
 ///     in which this file is dependent on
+///
+/// * `extra_args` - Arguments that will be only passed to the command line that builds this file
+///
+/// * `exclude_from_build` - When `true`, the interface is ignored by the build process
 /// ### Tests
 /// ```rust
 /// use zork::config_file::modules::ModulesAttribute;
@@ -97,7 +122,8 @@ pub struct ModulesAttribute<'a> {
 ///         { file = 'math.cppm' },
 ///         { file = 'some_module.cppm', module_name = 'math' },
 ///         { file = 'a.cppm', module_name = 'module', dependencies = ['math', 'type_traits', 'iostream'] },
-///         { file = 'some_module_part.cppm', module_name = 'math_part', dependencies = ['math'] }
+///         { file = 'some_module_part.cppm', module_name = 'math_part', dependencies = ['math'] },
+///         { file = 'legacy.cppm', extra_args = ['-w'], exclude_from_build = false }
 ///     ]
 /// "#;
 ///
@@ -128,6 +154,15 @@ pub struct ModulesAttribute<'a> {
 /// let ifc_3 = &ifcs[3];
 /// assert_eq!(ifc_3.file, "some_module_part.cppm");
 /// assert_eq!(ifc_3.module_name, Some("math_part"));
+/// assert_eq!(ifc_3.extra_args, None);
+///
+/// let ifc_4 = &ifcs[4];
+/// assert_eq!(ifc_4.file, "legacy.cppm");
+/// assert_eq!(ifc_4.extra_args, Some(vec!["-w"]));
+/// assert_eq!(ifc_4.exclude_from_build, Some(false));
+///
+/// let unknown_field = toml::from_str::<ModulesAttribute>("interfaces = [{ file = 'a.cppm', module = 'a' }]");
+/// assert!(unknown_field.unwrap_err().to_string().contains("unknown field `module`"));
 /// ```
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -140,6 +175,18 @@ pub struct ModuleInterface<'a> {
     pub partition: Option<ModulePartition<'a>>,
     #[serde(borrow)]
     pub dependencies: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub extra_args: Option<Vec<&'a str>>,
+    pub exclude_from_build: Option<bool>,
+}
+
+impl<'a> From<&'a str> for ModuleInterface<'a> {
+    fn from(file: &'a str) -> Self {
+        Self {
+            file,
+            ..Default::default()
+        }
+    }
 }
 
 /// [`ModulePartition`] - Type for dealing with the parse work
@@ -170,6 +217,8 @@ pub struct ModulePartition<'a> {
 /// * `file`- The path of a primary module interface (relative to base_ifcs_path)
 /// * `dependencies` - An optional array field for declare the module interfaces
 ///     in which this file is dependent on
+/// * `extra_args` - Arguments that will be only passed to the command line that builds this file
+/// * `exclude_from_build` - When `true`, the implementation is ignored by the build process
 ///
 /// ### Tests
 /// ```rust
@@ -178,7 +227,8 @@ pub struct ModulePartition<'a> {
 /// const CONFIG_FILE_MOCK: &str = r#"
 ///     implementations = [
 ///         { file = 'math.cppm', is_partition = false },
-///         { file = 'a.cppm', dependencies = ['math', 'type_traits', 'iostream'] }
+///         { file = 'a.cppm', dependencies = ['math', 'type_traits', 'iostream'] },
+///         { file = 'legacy.cpp', extra_args = ['-w'], exclude_from_build = true }
 ///     ]
 /// "#;
 ///
@@ -196,6 +246,11 @@ pub struct ModulePartition<'a> {
 /// assert_eq!(deps[0], "math");
 /// assert_eq!(deps[1], "type_traits");
 /// assert_eq!(deps[2], "iostream");
+///
+/// let impl_2 = &impls[2];
+/// assert_eq!(impl_2.file, "legacy.cpp");
+/// assert_eq!(impl_2.extra_args, Some(vec!["-w"]));
+/// assert_eq!(impl_2.exclude_from_build, Some(true));
 /// ```
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 pub struct ModuleImplementation<'a> {
@@ -203,4 +258,68 @@ pub struct ModuleImplementation<'a> {
     pub file: &'a str,
     #[serde(borrow)]
     pub dependencies: Option<Vec<&'a str>>,
+    #[serde(borrow)]
+    pub extra_args: Option<Vec<&'a str>>,
+    pub exclude_from_build: Option<bool>,
+}
+
+impl<'a> From<&'a str> for ModuleImplementation<'a> {
+    fn from(file: &'a str) -> Self {
+        Self {
+            file,
+            ..Default::default()
+        }
+    }
+}
+
+/// Any of the entries of the `interfaces` or `implementations` arrays, that can be declared
+/// just as the path of the file, or as a table
+enum ModuleDeclaration<'a, T> {
+    File(&'a str),
+    Table(T),
+}
+
+/// Hand written, instead of an untagged enum, so the errors of the tables (ex: an unknown field)
+/// aren't hidden behind a generic error about the entry not matching any of the variants
+impl<'de: 'a, 'a, T: Deserialize<'de>> Deserialize<'de> for ModuleDeclaration<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DeclarationVisitor<'a, T>(PhantomData<(&'a (), T)>);
+
+        impl<'de: 'a, 'a, T: Deserialize<'de>> Visitor<'de> for DeclarationVisitor<'a, T> {
+            type Value = ModuleDeclaration<'a, T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the path of a file, or a table")
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, file: &'de str) -> Result<Self::Value, E> {
+                Ok(ModuleDeclaration::File(file))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, table: A) -> Result<Self::Value, A::Error> {
+                T::deserialize(de::value::MapAccessDeserializer::new(table))
+                    .map(ModuleDeclaration::Table)
+            }
+        }
+
+        deserializer.deserialize_any(DeclarationVisitor(PhantomData))
+    }
+}
+
+fn file_or_table<'de: 'a, 'a, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + From<&'a str>,
+{
+    let declarations = Option::<Vec<ModuleDeclaration<'a, T>>>::deserialize(deserializer)?;
+
+    Ok(declarations.map(|declarations| {
+        declarations
+            .into_iter()
+            .map(|declaration| match declaration {
+                ModuleDeclaration::File(file) => T::from(file),
+                ModuleDeclaration::Table(table) => table,
+            })
+            .collect()
+    }))
 }
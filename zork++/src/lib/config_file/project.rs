@@ -1,11 +1,17 @@
 //! Metadata about the user's project
 
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
 /// [`ProjectAttribute`] - Metadata about the user's project
 /// * `name` - The C++ project's name
 /// * `authors` - A comma separated list of strings indicating the
 ///     authors that are responsible for the project
+/// * `pre_build` - Commands executed before building any translation unit of the project
+///   (ex: code generators). The `${out_dir}`, `${project_name}` and `${compiler}` variables
+///   are expanded on them
+/// * `post_build` - Commands executed once all the targets have been built
 ///
 /// ### Tests
 ///
@@ -17,6 +23,7 @@ use serde::{Deserialize, Serialize};
 ///     name = 'Zork++ serde tests'
 ///     authors = ['zerodaycode.gz@gmail.com']
 ///     compilation_db = true
+///     pre_build = ['protoc --cpp_out=${out_dir}/gen proto/messages.proto', "echo \"done\""]
 ///"#;
 ///
 /// let config: ProjectAttribute = toml::from_str(CONFIG_FILE_MOCK)
@@ -26,6 +33,14 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(config.authors, Some(vec!["zerodaycode.gz@gmail.com"]));
 /// assert_eq!(config.compilation_db, Some(true));
 /// assert_eq!(config.code_root, None);
+/// assert_eq!(
+///     config.pre_build,
+///     Some(vec![
+///         "protoc --cpp_out=${out_dir}/gen proto/messages.proto".into(),
+///         r#"echo "done""#.into()
+///     ])
+/// );
+/// assert_eq!(config.post_build, None);
 /// ```
 ///
 /// > Note: TOML table are toml commented (#) to allow us to parse
@@ -46,4 +61,8 @@ pub struct ProjectAttribute<'a> {
     pub compilation_db: Option<bool>,
     #[serde(borrow)]
     pub code_root: Option<&'a str>,
+    #[serde(borrow)]
+    pub pre_build: Option<Vec<Cow<'a, str>>>,
+    #[serde(borrow)]
+    pub post_build: Option<Vec<Cow<'a, str>>>,
 }
@@ -0,0 +1,461 @@
+//! The JSON Schema of the configuration files, so the editors that validates TOML files against
+//! one (like `Even Better TOML`) can check and autocomplete them. It's exported through the
+//! `zork schema` command.
+//!
+//! The attributes required on a complete configuration file aren't enforced by the schema, since
+//! a configuration file may only hold a layer of an inheritance (see
+//! [`crate::utils::inheritance`]), nor the ones of the tables that can be partially overridden
+
+use serde_json::{json, Map, Value};
+
+use crate::utils::conditionals::CONDITIONAL_SECTIONS;
+use crate::utils::inheritance::REPLACE_MARKER;
+
+/// The values accepted for [`super::compiler::CppCompiler`], including the serde aliases
+pub const CPP_COMPILERS: &[&str] = &[
+    "CLANG", "Clang", "clang", "MSVC", "Msvc", "msvc", "GCC", "Gcc", "gcc", "CLANG_CL", "ClangCl",
+    "clang-cl", "clang_cl",
+];
+/// The values accepted for [`super::compiler::LanguageLevel`], including the serde aliases
+pub const LANGUAGE_LEVELS: &[&str] = &[
+    "CPP20", "20", "CPP23", "23", "CPP2A", "2a", "CPP2B", "2b", "LATEST", "latest",
+];
+/// The values accepted for [`super::compiler::StdLib`], including the serde aliases
+pub const STD_LIBS: &[&str] = &[
+    "STDLIBCPP",
+    "libstdc++",
+    "gccstdlib",
+    "libstdcpp",
+    "LIBCPP",
+    "libc++",
+    "libcpp",
+];
+/// The values accepted for [`super::compiler::WarningsLevel`], including the serde aliases
+pub const WARNINGS_LEVELS: &[&str] = &["ALL", "all", "EXTRA", "extra", "ERROR", "error"];
+/// The values accepted for [`super::compiler::OptimizationLevel`], including the serde aliases
+pub const OPTIMIZATION_LEVELS: &[&str] = &["O0", "0", "O1", "1", "O2", "2", "O3", "3", "OS", "s"];
+/// The values accepted for [`crate::domain::target::TargetKind`], including the serde aliases
+pub const TARGET_KINDS: &[&str] = &[
+    "Executable",
+    "executable",
+    "exe",
+    "StaticLib",
+    "static lib",
+    "static-lib",
+    "static_lib",
+    "staticlib",
+    "DyLib",
+    "DynamicLib",
+    "dynamic lib",
+    "dyn-lib",
+    "dyn_lib",
+    "dylib",
+];
+
+/// Generates the JSON Schema of the configuration files
+///
+/// ```rust
+/// use zork::config_file::schema::json_schema;
+///
+/// let schema = json_schema();
+/// let compiler = &schema["definitions"]["compiler"]["properties"]["cpp_compiler"];
+/// assert!(compiler["enum"].as_array().unwrap().contains(&"clang-cl".into()));
+/// ```
+pub fn json_schema() -> Value {
+    let definitions = [
+        ("config_file", config_file()),
+        ("project", project()),
+        ("compiler", compiler()),
+        ("build", build()),
+        ("toolchain", toolchain()),
+        ("workspace", workspace()),
+        ("modules", modules()),
+        ("module_interface", module_interface()),
+        ("module_partition", module_partition()),
+        ("module_implementation", module_implementation()),
+        ("target", target()),
+        ("source_file", source_file()),
+        ("generator", generator()),
+    ];
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Zork++ configuration file",
+        "$ref": "#/definitions/config_file",
+        "definitions": definitions
+            .into_iter()
+            .map(|(name, definition)| (name.to_string(), definition))
+            .collect::<Map<_, _>>(),
+    })
+}
+
+fn config_file() -> Value {
+    table(
+        "The whole hierarchy of attributes of a configuration file",
+        json!({
+            "extends": string("The path of a configuration file to inherit from"),
+            "include": strings("The paths of other configuration files to merge in"),
+            "project": reference("project"),
+            "compiler": reference("compiler"),
+            "build": reference("build"),
+            "modules": reference("modules"),
+            "toolchain": reference("toolchain"),
+            "targets": {
+                "description": "The final products of the project, by name",
+                "type": "object",
+                "additionalProperties": reference("target"),
+            },
+            "generators": {
+                "description": "The custom commands that generate files of the project",
+                "type": "array",
+                "items": reference("generator"),
+            },
+            "workspace": reference("workspace"),
+            CONDITIONAL_SECTIONS: {
+                "description": "Sections applied when their `cfg(...)` predicate holds",
+                "type": "object",
+                "propertyNames": { "pattern": "^cfg\\(.*\\)$" },
+                "additionalProperties": reference("config_file"),
+            },
+        }),
+    )
+}
+
+fn project() -> Value {
+    table(
+        "The general attributes of the project",
+        json!({
+            "name": string("The name of the project"),
+            "authors": strings("The authors of the project"),
+            "compilation_db": boolean("Generates the compile_commands.json compilation database"),
+            "code_root": string("The root of the sources, relative to the project's root"),
+            "pre_build": strings("Commands executed before building the project"),
+            "post_build": strings("Commands executed after building the project"),
+        }),
+    )
+}
+
+fn compiler() -> Value {
+    let mut compiler = table(
+        "The compiler and the options applied to the whole project",
+        json!({
+            "cpp_compiler": values("The compiler frontend", CPP_COMPILERS),
+            "driver_path": string("The path of the compiler's driver"),
+            "cpp_standard": values("The C++ standard", LANGUAGE_LEVELS),
+            "std_lib": values("The standard library to link against", STD_LIBS),
+            "std_lib_installed_dir": string("The installation of the modular standard library"),
+            "extra_args": strings("Deprecated, in favour of `compile_args` and `link_args`. Passed to both the compile and the link command lines"),
+            "compile_args": strings("Arguments for the compile command lines"),
+            "link_args": strings("Arguments for the linker command lines"),
+            "system_headers_path": string("The location of the system headers"),
+            "launcher": string("A program prefixing the compile commands, like ccache"),
+            "defines": strings("Preprocessor definitions"),
+            "include_dirs": strings("Include directories"),
+            "system_include_dirs": strings("System include directories"),
+            "warnings": values("The warnings level", WARNINGS_LEVELS),
+            "optimization": values("The optimization level", OPTIMIZATION_LEVELS),
+            "debug_info": boolean("Generates debug information"),
+        }),
+    );
+    // The `[compiler.<compiler>]` sections, applied when their compiler is the selected one
+    compiler["patternProperties"] = json!({
+        format!("^({})$", CPP_COMPILERS.join("|")): reference("compiler"),
+    });
+    compiler
+}
+
+fn build() -> Value {
+    table(
+        "The attributes of the build",
+        json!({
+            "output_dir": string("The directory of the build products"),
+        }),
+    )
+}
+
+fn toolchain() -> Value {
+    table(
+        "The cross-compilation toolchain",
+        json!({
+            "target": string("The target triple"),
+            "sysroot": string("The sysroot of the target"),
+            "linker": string("The linker"),
+            "archiver": string("The archiver of the static libraries"),
+            "target_os": string("The operating system of the target"),
+        }),
+    )
+}
+
+fn workspace() -> Value {
+    table(
+        "The projects built along with this one",
+        json!({
+            "members": strings("The directories of the members, or glob patterns"),
+        }),
+    )
+}
+
+fn modules() -> Value {
+    table(
+        "The C++ modules of the project",
+        json!({
+            "base_ifcs_dir": string("The directory of the module interfaces"),
+            "interfaces": entries("The module interfaces", "module_interface"),
+            "base_impls_dir": string("The directory of the module implementations"),
+            "implementations": entries("The module implementations", "module_implementation"),
+            "sys_modules": strings("The system headers built as modules"),
+            "header_units": strings("The user headers built as header units"),
+        }),
+    )
+}
+
+fn module_interface() -> Value {
+    entry(
+        "A module interface unit",
+        json!({
+            "file": string("The path of the file, or a glob pattern"),
+            "module_name": string("The module declared on the file"),
+            "partition": reference("module_partition"),
+            "dependencies": strings("The modules imported by the file"),
+            "extra_args": strings("Arguments only applied to this file"),
+            "exclude_from_build": boolean("Excludes the file from the build"),
+        }),
+        &["file"],
+    )
+}
+
+fn module_partition() -> Value {
+    entry(
+        "The module that a partition belongs to",
+        json!({
+            "module": string("The primary module"),
+            "partition_name": string("The name of the partition"),
+            "is_internal_partition": boolean("Whether it's an internal partition"),
+        }),
+        &["module"],
+    )
+}
+
+fn module_implementation() -> Value {
+    entry(
+        "A module implementation unit",
+        json!({
+            "file": string("The path of the file, or a glob pattern"),
+            "dependencies": strings("The modules imported by the file"),
+            "extra_args": strings("Arguments only applied to this file"),
+            "exclude_from_build": boolean("Excludes the file from the build"),
+        }),
+        &["file"],
+    )
+}
+
+fn target() -> Value {
+    table(
+        "A final product of the project",
+        json!({
+            "output_name": string("The name of the product"),
+            "sources": {
+                "description": "The source files, or glob patterns",
+                "type": "array",
+                "items": { "anyOf": [{ "type": "string" }, reference("source_file")] },
+            },
+            "exclude": strings("Glob patterns of the files discarded from the sources"),
+            "extra_args": strings("Deprecated, in favour of `compile_args` and `link_args`. Passed to both the compile and the link command lines"),
+            "compile_args": strings("Arguments for the compile command lines"),
+            "kind": values("The kind of product", TARGET_KINDS),
+            "defines": strings("Preprocessor definitions"),
+            "include_dirs": strings("Include directories"),
+            "system_include_dirs": strings("System include directories"),
+            "warnings": values("The warnings level", WARNINGS_LEVELS),
+            "optimization": values("The optimization level", OPTIMIZATION_LEVELS),
+            "debug_info": boolean("Generates debug information"),
+            "link_libraries": strings("Libraries linked into the product"),
+            "link_dirs": strings("Directories where the libraries are searched"),
+            "link_args": strings("Arguments for the linker command line"),
+            "frameworks": strings("Apple frameworks linked into the product"),
+            "pch": string("The header precompiled for the sources"),
+            "pre_build": strings("Commands executed before building the target"),
+            "post_build": strings("Commands executed after building the target"),
+            "modules": reference("modules"),
+            "dependencies": strings("The library targets linked into this one"),
+        }),
+    )
+}
+
+fn source_file() -> Value {
+    entry(
+        "A source file with its own properties",
+        json!({
+            "file": string("The path of the file, or a glob pattern"),
+            "extra_args": strings("Arguments only applied to this file"),
+            "exclude_from_build": boolean("Excludes the file from the build"),
+        }),
+        &["file"],
+    )
+}
+
+fn generator() -> Value {
+    entry(
+        "A custom command that generates files of the project",
+        json!({
+            "command": string("The command line"),
+            "inputs": strings("The files read by the command, or glob patterns"),
+            "outputs": strings("The files generated by the command"),
+            "target": string("The target whose sources are the generated files"),
+        }),
+        &["command", "outputs"],
+    )
+}
+
+/// A table that can be merged with the same one of other configuration files
+fn table(description: &str, mut properties: Value) -> Value {
+    properties[REPLACE_MARKER] =
+        strings("The arrays replacing the inherited ones instead of being appended to them");
+    json!({
+        "description": description,
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+/// A table declared as an entry of an array, which is always declared as a whole
+fn entry(description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "description": description,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// The entries of the modules arrays, that can be declared just as the path of the file
+fn entries(description: &str, definition: &str) -> Value {
+    json!({
+        "description": description,
+        "type": "array",
+        "items": { "anyOf": [{ "type": "string" }, reference(definition)] },
+    })
+}
+
+fn reference(definition: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{definition}") })
+}
+
+fn string(description: &str) -> Value {
+    json!({ "description": description, "type": "string" })
+}
+
+fn strings(description: &str) -> Value {
+    json!({ "description": description, "type": "array", "items": { "type": "string" } })
+}
+
+fn boolean(description: &str) -> Value {
+    json!({ "description": description, "type": "boolean" })
+}
+
+fn values(description: &str, values: &[&str]) -> Value {
+    json!({ "description": description, "type": "string", "enum": values })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::config_file::build::BuildAttribute;
+    use crate::config_file::compiler::{
+        CompilerAttribute, CppCompiler, LanguageLevel, OptimizationLevel, StdLib, WarningsLevel,
+    };
+    use crate::config_file::generator::GeneratorAttribute;
+    use crate::config_file::modules::{
+        ModuleImplementation, ModuleInterface, ModulePartition, ModulesAttribute,
+    };
+    use crate::config_file::project::ProjectAttribute;
+    use crate::config_file::target::{SourceFileAttribute, TargetAttribute};
+    use crate::config_file::toolchain::ToolchainAttribute;
+    use crate::config_file::workspace::WorkspaceAttribute;
+    use crate::config_file::ZorkConfigFile;
+    use crate::domain::target::TargetKind;
+    use crate::utils::validation::{fields_of, variants_of};
+
+    /// The attributes of the given definition, without the replace marker, sorted
+    fn properties(schema: &Value, definition: &str) -> Vec<String> {
+        let mut properties = schema["definitions"][definition]["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .filter(|key| key.as_str() != REPLACE_MARKER)
+            .cloned()
+            .collect::<Vec<_>>();
+        properties.sort();
+        properties
+    }
+
+    #[test]
+    fn test_schema_matches_the_config_file_structs() {
+        let schema = json_schema();
+        let config_file_fields = fields_of::<ZorkConfigFile>()
+            .iter()
+            .chain(std::iter::once(&CONDITIONAL_SECTIONS))
+            .copied()
+            .collect::<Vec<_>>();
+        let definitions = [
+            ("config_file", config_file_fields.as_slice()),
+            ("project", fields_of::<ProjectAttribute>()),
+            ("compiler", fields_of::<CompilerAttribute>()),
+            ("build", fields_of::<BuildAttribute>()),
+            ("toolchain", fields_of::<ToolchainAttribute>()),
+            ("workspace", fields_of::<WorkspaceAttribute>()),
+            ("modules", fields_of::<ModulesAttribute>()),
+            ("module_interface", fields_of::<ModuleInterface>()),
+            ("module_partition", fields_of::<ModulePartition>()),
+            ("module_implementation", fields_of::<ModuleImplementation>()),
+            ("target", fields_of::<TargetAttribute>()),
+            ("source_file", fields_of::<SourceFileAttribute>()),
+            ("generator", fields_of::<GeneratorAttribute>()),
+        ];
+
+        for (definition, fields) in definitions {
+            let mut fields = fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+            fields.sort();
+            assert_eq!(
+                properties(&schema, definition),
+                fields,
+                "The schema of `{definition}` is out of sync"
+            );
+        }
+    }
+
+    /// Every value of the schema must be accepted, and every variant must be on the schema
+    fn check_values<T: DeserializeOwned>(values: &[&str]) {
+        for value in values {
+            let deserialized = T::deserialize(toml::Value::String(value.to_string()));
+            assert!(deserialized.is_ok(), "{value:?} isn't accepted");
+        }
+        for variant in variants_of::<T>() {
+            assert!(values.contains(variant), "{variant:?} is missing");
+        }
+    }
+
+    #[test]
+    fn test_schema_values_are_accepted() {
+        check_values::<CppCompiler>(CPP_COMPILERS);
+        check_values::<LanguageLevel>(LANGUAGE_LEVELS);
+        check_values::<StdLib>(STD_LIBS);
+        check_values::<WarningsLevel>(WARNINGS_LEVELS);
+        check_values::<OptimizationLevel>(OPTIMIZATION_LEVELS);
+        check_values::<TargetKind>(TARGET_KINDS);
+    }
+
+    #[test]
+    fn test_exported_schema_is_up_to_date() {
+        let exported = include_str!("../../../zork.schema.json");
+        let schema = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+        assert_eq!(
+            exported, schema,
+            "Export the schema again with: zork schema > zork++/zork.schema.json"
+        );
+    }
+}
@@ -1,36 +1,119 @@
 //! Type for holds the Targets build details
 
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
+use crate::config_file::compiler::{OptimizationLevel, WarningsLevel};
+use crate::config_file::modules::ModulesAttribute;
 use crate::domain::target::TargetKind;
 
 /// [`TargetAttribute`] - The type for holding the build details of every
 /// user defined target
 /// * `output_name`- The name with which the final byproduct will be generated
-/// * `sources` - The sources to be included in the compilation of this target
-/// * `extra_args` - Holds extra arguments that the user wants to introduce
+/// * `sources` - The sources to be included in the compilation of this target. Every entry
+///   can be just a path (or a glob pattern), or a table for declaring per-file properties
+///   (see: [`SourceFileAttribute`]). A `**` component of a glob pattern matches any subdirectory
+///   of the code root
+/// * `exclude` - Glob patterns, relative to the code root, of the files that must be discarded
+///   from the ones matched by `sources` (ex: `**/test_*.cpp`)
+/// * `compile_args` - Arguments that are only passed to the compile command lines of the sources of this target
+/// * `extra_args` - Deprecated, in favour of `compile_args` and `link_args`. Passed to both the compile and the link command lines
 /// * `kind` - Determined which type of byproduct will be generated (binary, library...)
+/// * `defines`, `include_dirs`, `system_include_dirs`, `warnings`, `optimization` and `debug_info` -
+///   The same structured compile options available on the `[compiler]` table (see: [`crate::config_file::compiler::CompilerAttribute`]),
+///   but only applied to the sources of this target
+/// * `link_libraries` - The libraries that the final product will be linked against. Just their names
+///   are expected, like `pthread` or `fmt`, since Zork++ will render them according to the selected compiler
+/// * `link_dirs` - Directories (relative to the code root) where the linker will look for the libraries
+/// * `link_args` - Arguments that are only passed to the linker
+/// * `frameworks` - The `Apple` frameworks that the final product will be linked against
+/// * `pre_build` - Commands executed before building the sources of this target. Along with the
+///   variables available for the ones of the `[project]` table, `${target_name}` and `${target_output}`
+///   are expanded on them
+/// * `post_build` - Commands executed once the final product of this target has been generated
+///   (ex: strip or sign the binary)
+/// * `pch` - A header (relative to the code root) that will be precompiled before building the
+///   sources of this target, which will be implicitly included by all of them. With `GCC`, the sources
+///   of a target with a precompiled header can't import modules
+/// * `modules` - The module interfaces and implementations owned by this target, with the same layout
+///   of the `[modules]` table (see: [`ModulesAttribute`]), except for the `sys_modules`. They are only
+///   visible to this target
+/// * `dependencies` - The library targets that must be built before this one, and whose products are
+///   linked into it. The targets of the members of a workspace are referenced as `member:target`
+///
+/// Regardless of where the modules are declared, the linker of a target only receives the module units
+/// that are reachable from the `import` declarations of its sources. Library targets also receive all
+/// the module units that they own, since they are part of their public interface
 ///
 /// ### Tests
 ///
 /// ```rust
-/// use zork::config_file::target::TargetAttribute;
+/// use zork::config_file::compiler::{OptimizationLevel, WarningsLevel};
+/// use zork::config_file::target::{SourceFileAttribute, TargetAttribute, TargetSource};
 /// use zork::domain::target::TargetKind;
 /// const CONFIG_FILE_MOCK: &str = r#"
 ///     #[target.executable]
 ///     output_name = "some_executable"
-///     sources = [ '*.cpp' ]
+///     sources = [
+///         '*.cpp',
+///         { file = 'legacy.cpp', extra_args = ['-w'] },
+///         { file = 'broken.cpp', exclude_from_build = true }
+///     ]
+///     exclude = [ '**/test_*.cpp' ]
 ///     extra_args = ['-Wall']
+///     compile_args = ['-fno-exceptions']
 ///     kind = "Executable"
+///     defines = ['TESTING']
+///     include_dirs = ['test/include']
+///     warnings = 'error'
+///     optimization = 's'
+///     link_libraries = ['pthread', 'fmt']
+///     link_dirs = ['deps/lib']
+///     link_args = ['-static']
+///     frameworks = ['Cocoa']
+///     pch = 'src/pch.h'
+///     post_build = ['strip ${target_output}']
+///     modules = { base_ifcs_dir = 'tests/ifc', interfaces = ['*.cppm'] }
+///     dependencies = ['utils', 'core:corelib']
 /// "#;
 ///
 /// let config: TargetAttribute = toml::from_str(CONFIG_FILE_MOCK)
 ///    .expect("A failure happened parsing the Zork toml file");
 ///
 /// assert_eq!(config.output_name, Some("some_executable"));
-/// assert_eq!(config.sources, vec!["*.cpp"]);
+/// assert_eq!(config.sources[0], TargetSource::Path("*.cpp"));
+/// assert_eq!(
+///     config.sources[1],
+///     TargetSource::Detailed(SourceFileAttribute {
+///         file: "legacy.cpp",
+///         extra_args: Some(vec!["-w"]),
+///         exclude_from_build: None,
+///     })
+/// );
+/// assert_eq!(config.sources[2].file(), "broken.cpp");
+/// assert!(config.sources[2].is_excluded_from_build());
+/// assert_eq!(config.exclude, Some(vec!["**/test_*.cpp"]));
 /// assert_eq!(config.extra_args, Some(vec!["-Wall"]));
+/// assert_eq!(config.compile_args, Some(vec!["-fno-exceptions"]));
 /// assert_eq!(config.kind, Some(TargetKind::Executable));
+/// assert_eq!(config.defines, Some(vec!["TESTING"]));
+/// assert_eq!(config.include_dirs, Some(vec!["test/include"]));
+/// assert_eq!(config.system_include_dirs, None);
+/// assert_eq!(config.warnings, Some(WarningsLevel::ERROR));
+/// assert_eq!(config.optimization, Some(OptimizationLevel::OS));
+/// assert_eq!(config.debug_info, None);
+/// assert_eq!(config.link_libraries, Some(vec!["pthread", "fmt"]));
+/// assert_eq!(config.link_dirs, Some(vec!["deps/lib"]));
+/// assert_eq!(config.link_args, Some(vec!["-static"]));
+/// assert_eq!(config.frameworks, Some(vec!["Cocoa"]));
+/// assert_eq!(config.pch, Some("src/pch.h"));
+/// assert_eq!(config.pre_build, None);
+/// assert_eq!(config.post_build, Some(vec!["strip ${target_output}".into()]));
+/// let modules = config.modules.unwrap();
+/// assert_eq!(modules.base_ifcs_dir, Some("tests/ifc"));
+/// assert_eq!(modules.interfaces.unwrap()[0].file, "*.cppm");
+/// assert_eq!(config.dependencies, Some(vec!["utils", "core:corelib"]));
 /// ```
 /// > Note: TOML table are toml commented (#) to allow us to parse
 /// > the inner attributes as the direct type that they belongs to.
@@ -40,10 +123,78 @@ use crate::domain::target::TargetKind;
 ///
 /// For a test over a real example, please look at the
 /// [`zork::config_file::ZorkConfigFile`] doc-test
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct TargetAttribute<'a> {
     pub output_name: Option<&'a str>,
-    pub sources: Vec<&'a str>,
+    #[serde(borrow)]
+    pub sources: Vec<TargetSource<'a>>,
+    pub exclude: Option<Vec<&'a str>>,
     pub extra_args: Option<Vec<&'a str>>,
+    pub compile_args: Option<Vec<&'a str>>,
     pub kind: Option<TargetKind>,
+    pub defines: Option<Vec<&'a str>>,
+    pub include_dirs: Option<Vec<&'a str>>,
+    pub system_include_dirs: Option<Vec<&'a str>>,
+    pub warnings: Option<WarningsLevel>,
+    pub optimization: Option<OptimizationLevel>,
+    pub debug_info: Option<bool>,
+    pub link_libraries: Option<Vec<&'a str>>,
+    pub link_dirs: Option<Vec<&'a str>>,
+    pub link_args: Option<Vec<&'a str>>,
+    pub frameworks: Option<Vec<&'a str>>,
+    pub pch: Option<&'a str>,
+    #[serde(borrow)]
+    pub pre_build: Option<Vec<Cow<'a, str>>>,
+    #[serde(borrow)]
+    pub post_build: Option<Vec<Cow<'a, str>>>,
+    #[serde(borrow)]
+    pub modules: Option<ModulesAttribute<'a>>,
+    #[serde(borrow)]
+    pub dependencies: Option<Vec<&'a str>>,
+}
+
+/// [`TargetSource`] - Any of the entries of the `sources` attribute of a target.
+/// It can be written as a plain string, holding the path of the file (or a glob pattern),
+/// or as a table, when some per-file property needs to be declared
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TargetSource<'a> {
+    #[serde(borrow)]
+    Path(&'a str),
+    #[serde(borrow)]
+    Detailed(SourceFileAttribute<'a>),
+}
+
+impl<'a> TargetSource<'a> {
+    /// The declared path (or glob pattern) of the entry
+    pub fn file(&self) -> &'a str {
+        match self {
+            TargetSource::Path(file) => file,
+            TargetSource::Detailed(details) => details.file,
+        }
+    }
+
+    pub fn is_excluded_from_build(&self) -> bool {
+        match self {
+            TargetSource::Path(_) => false,
+            TargetSource::Detailed(details) => details.exclude_from_build.unwrap_or_default(),
+        }
+    }
+}
+
+/// [`SourceFileAttribute`] - The per-file properties of a source file of a target
+///
+/// * `file` - The path of the source file (or a glob pattern), relative to the code root
+/// * `extra_args` - Arguments that will be only passed to the compile command line of this file,
+///   after the ones declared for the whole project and for the target
+/// * `exclude_from_build` - When `true`, the file won't be built nor linked, even if it's matched by
+///   another entry of the same target. Useful to remove some files from a glob pattern
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SourceFileAttribute<'a> {
+    #[serde(borrow)]
+    pub file: &'a str,
+    #[serde(borrow)]
+    pub extra_args: Option<Vec<&'a str>>,
+    pub exclude_from_build: Option<bool>,
 }
@@ -0,0 +1,86 @@
+//! file that contains the configuration options available
+//! within Zork++ to describe the toolchain used on the build process,
+//! mainly useful for cross-compilation scenarios
+
+use serde::{Deserialize, Serialize};
+
+/// [`ToolchainAttribute`] - Describes the platform that the built code targets
+/// and the tools involved on the process, other than the compiler's driver
+///
+/// * `target` - The target triple of the generated code (ex: `aarch64-linux-gnu`).
+///   When present, the build products will be placed under a subdirectory of the
+///   output directory named after the triple, so host and cross builds can coexist
+///
+/// * `sysroot` - The root directory where the headers and libraries of the target
+///   platform are located
+///
+/// * `linker` - The linker that the compiler's driver must use (ex: `lld`)
+///
+/// * `archiver` - The tool used to bundle the object files of the static libraries
+///   (ex: `llvm-ar`). If not present, the static libraries are generated through the compiler's
+///   driver, like the rest of the targets
+///
+/// * `target_os` - The operating system where the generated binaries are meant to run,
+///   which decides the extension of the final products. If not present, it will be deduced
+///   from the `target` triple, or the host operating system otherwise
+///
+/// The same attributes can be written on a separated toml file, under a `[toolchain]` table,
+/// which can be selected with the `--toolchain` command line argument
+///
+/// ```rust
+/// use zork::config_file::toolchain::ToolchainAttribute;
+///
+/// const CONFIG_FILE_MOCK: &str = r#"
+///     #[toolchain]
+///     target = 'aarch64-linux-gnu'
+///     sysroot = '/usr/aarch64-linux-gnu'
+///     linker = 'lld'
+///     archiver = 'llvm-ar'
+///     target_os = 'linux'
+///"#;
+///
+/// let config: ToolchainAttribute = toml::from_str(CONFIG_FILE_MOCK)
+///    .expect("A failure happened parsing the Zork toml file");
+///
+/// assert_eq!(config.target, Some("aarch64-linux-gnu"));
+/// assert_eq!(config.sysroot, Some("/usr/aarch64-linux-gnu"));
+/// assert_eq!(config.linker, Some("lld"));
+/// assert_eq!(config.archiver, Some("llvm-ar"));
+/// assert_eq!(config.target_os, Some("linux"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ToolchainAttribute<'a> {
+    #[serde(borrow)]
+    pub target: Option<&'a str>,
+    #[serde(borrow)]
+    pub sysroot: Option<&'a str>,
+    #[serde(borrow)]
+    pub linker: Option<&'a str>,
+    #[serde(borrow)]
+    pub archiver: Option<&'a str>,
+    #[serde(borrow)]
+    pub target_os: Option<&'a str>,
+}
+
+/// The layout of a standalone toolchain file, which just holds a `[toolchain]` table
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ToolchainFile<'a> {
+    #[serde(borrow)]
+    toolchain: ToolchainAttribute<'a>,
+}
+
+/// Parses the contents of a standalone toolchain file
+///
+/// ```rust
+/// use zork::config_file::toolchain::toolchain_from_file;
+///
+/// let toolchain = toolchain_from_file("[toolchain]\ntarget = 'aarch64-linux-gnu'")
+///     .expect("A failure happened parsing the toolchain file");
+/// assert_eq!(toolchain.target, Some("aarch64-linux-gnu"));
+/// assert_eq!(toolchain.sysroot, None);
+/// ```
+pub fn toolchain_from_file(raw: &'_ str) -> Result<ToolchainAttribute<'_>, toml::de::Error> {
+    <ToolchainFile>::deserialize(&mut toml::Deserializer::new(raw)).map(|file| file.toolchain)
+}
@@ -0,0 +1,31 @@
+//! file that contains the configuration options available
+//! within Zork++ to declare a workspace made of several projects
+
+use serde::{Deserialize, Serialize};
+
+/// [`WorkspaceAttribute`] - Declares the configuration file as the root of a workspace, that
+/// orchestrates the build of other projects (the members) with its own compiler configuration,
+/// sharing the output directory and the cache
+///
+/// * `members` - The directories of the member projects, relative to the workspace root, or glob
+///   patterns that matches them. Every member must have a `zork.toml` on its directory
+///
+/// ```rust
+/// use zork::config_file::workspace::WorkspaceAttribute;
+///
+/// const CONFIG_FILE_MOCK: &str = r#"
+///     #[workspace]
+///     members = ['libs/*', 'apps/app']
+///"#;
+///
+/// let config: WorkspaceAttribute = toml::from_str(CONFIG_FILE_MOCK)
+///    .expect("A failure happened parsing the Zork toml file");
+///
+/// assert_eq!(config.members, vec!["libs/*", "apps/app"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceAttribute<'a> {
+    #[serde(borrow)]
+    pub members: Vec<&'a str>,
+}
@@ -8,7 +8,8 @@ use std::{borrow::Borrow, ffi::OsStr, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::project_model::compiler::LanguageLevel;
+use crate::project_model::compiler::{CompileOptions, CppCompiler, LanguageLevel};
+use crate::project_model::target::LinkOptions;
 
 /// Wrapper type for represent and storing a command line argument
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -227,6 +228,26 @@ impl<'a> FromIterator<&'a Argument<'a>> for Arguments<'a> {
     }
 }
 
+/// Translates the structured [`CompileOptions`] declared by the user into the concrete syntax
+/// of the given [`CppCompiler`]
+pub fn compile_options_args<'a>(compiler: CppCompiler, options: &CompileOptions) -> Arguments<'a> {
+    match compiler {
+        CppCompiler::CLANG | CppCompiler::GCC => clang_args::compile_options_args(options),
+        CppCompiler::MSVC | CppCompiler::CLANG_CL => {
+            msvc_args::compile_options_args(compiler, options)
+        }
+    }
+}
+
+/// Translates the [`LinkOptions`] of a target into the concrete syntax of the given [`CppCompiler`].
+/// The returned arguments must be placed after the object files on the linker command line
+pub fn link_options_args<'a>(compiler: CppCompiler, options: &LinkOptions<'a>) -> Arguments<'a> {
+    match compiler {
+        CppCompiler::CLANG | CppCompiler::GCC => clang_args::link_options_args(options),
+        CppCompiler::MSVC | CppCompiler::CLANG_CL => msvc_args::link_options_args(options),
+    }
+}
+
 /// Isolated module to storing custom procedures to easily create and add new command line arguments
 /// or flags specific to Clang, that otherwise, will be bloating the main procedures with a lot
 /// of cognitive complexity
@@ -236,9 +257,10 @@ pub mod clang_args {
     use crate::{
         cache::ZorkCache,
         domain::{
-            commands::command_lines::SourceCommandLine, translation_unit::TranslationUnitStatus,
+            commands::command_lines::SourceCommandLine,
+            translation_unit::{BuildReason, TranslationUnitStatus},
         },
-        project_model::compiler::{CppCompiler, StdLibMode},
+        project_model::compiler::{CppCompiler, OptimizationLevel, StdLibMode, WarningsLevel},
         utils::constants,
     };
 
@@ -306,6 +328,74 @@ pub mod clang_args {
         args
     }
 
+    /// Translates the structured compile options into the GNU style syntax, shared by `Clang`
+    /// and `GCC`
+    pub(crate) fn compile_options_args<'a>(options: &CompileOptions) -> Arguments<'a> {
+        let mut args = Arguments::default();
+
+        options
+            .defines
+            .iter()
+            .for_each(|define| args.push(format!("-D{define}")));
+        options
+            .include_dirs
+            .iter()
+            .for_each(|dir| args.push(format!("-I{}", dir.display())));
+        options
+            .system_include_dirs
+            .iter()
+            .for_each(|dir| args.push(format!("-isystem{}", dir.display())));
+
+        if let Some(warnings) = options.warnings {
+            args.push("-Wall");
+            if warnings.ne(&WarningsLevel::ALL) {
+                args.push("-Wextra");
+            }
+            if warnings.eq(&WarningsLevel::ERROR) {
+                args.push("-Werror");
+            }
+        }
+
+        args.push_opt(options.optimization.map(|level| match level {
+            OptimizationLevel::O0 => "-O0",
+            OptimizationLevel::O1 => "-O1",
+            OptimizationLevel::O2 => "-O2",
+            OptimizationLevel::O3 => "-O3",
+            OptimizationLevel::OS => "-Os",
+        }));
+
+        if options.debug_info.unwrap_or_default() {
+            args.push("-g");
+        }
+
+        args
+    }
+
+    /// Translates the link options of a target into the GNU style syntax, shared by `Clang`
+    /// and `GCC`. The libraries declared with an explicit extension or path are passed verbatim
+    pub(crate) fn link_options_args<'a>(options: &LinkOptions<'a>) -> Arguments<'a> {
+        let mut args = Arguments::default();
+
+        options
+            .link_dirs
+            .iter()
+            .for_each(|dir| args.push(format!("-L{}", dir.display())));
+        options.link_libraries.iter().for_each(|lib| {
+            if Path::new(lib.as_ref()).extension().is_some() || lib.contains(['/', '\\']) {
+                args.push(lib)
+            } else {
+                args.push(format!("-l{lib}"))
+            }
+        });
+        options.frameworks.iter().for_each(|framework| {
+            args.push("-framework");
+            args.push(framework);
+        });
+        args.extend(options.link_args.iter().cloned());
+
+        args
+    }
+
     pub(crate) fn generate_std_cmd<'a>(
         cache: &mut ZorkCache<'a>,
         stdlib_mode: StdLibMode,
@@ -347,13 +437,67 @@ pub mod clang_args {
             args,
             status: TranslationUnitStatus::PendingToBuild,
             byproduct: byproduct.into(),
+            build_reason: Some(BuildReason::New),
         }
     }
 
     #[cfg(test)]
     mod clang_args_tests {
         use crate::domain::commands::arguments::Arguments;
-        use std::{borrow::Cow, path::Path};
+        use crate::project_model::compiler::{CompileOptions, OptimizationLevel, WarningsLevel};
+        use crate::project_model::target::LinkOptions;
+        use std::{
+            borrow::Cow,
+            path::{Path, PathBuf},
+        };
+
+        #[test]
+        fn test_clang_link_options() {
+            let options = LinkOptions {
+                link_libraries: vec![Cow::Borrowed("pthread"), Cow::Borrowed("deps/libfmt.a")],
+                link_dirs: vec![PathBuf::from("deps")],
+                link_args: vec!["-static".into()],
+                frameworks: vec![Cow::Borrowed("Cocoa")],
+            };
+
+            assert_eq!(
+                super::link_options_args(&options),
+                Arguments::from_vec(vec![
+                    "-Ldeps".into(),
+                    "-lpthread".into(),
+                    "deps/libfmt.a".into(),
+                    "-framework".into(),
+                    "Cocoa".into(),
+                    "-static".into(),
+                ])
+            );
+        }
+
+        #[test]
+        fn test_clang_compile_options() {
+            let options = CompileOptions {
+                defines: vec![Cow::Borrowed("ZORK"), Cow::Borrowed("VERSION=2")],
+                include_dirs: vec![PathBuf::from("include")],
+                system_include_dirs: vec![PathBuf::from("deps")],
+                warnings: Some(WarningsLevel::EXTRA),
+                optimization: Some(OptimizationLevel::OS),
+                debug_info: Some(true),
+            };
+
+            assert_eq!(
+                super::compile_options_args(&options),
+                Arguments::from_vec(vec![
+                    "-DZORK".into(),
+                    "-DVERSION=2".into(),
+                    "-Iinclude".into(),
+                    "-isystemdeps".into(),
+                    "-Wall".into(),
+                    "-Wextra".into(),
+                    "-Os".into(),
+                    "-g".into(),
+                ])
+            );
+        }
 
         #[test] // fixed since v0.11.2
         fn test_clang_add_direct_module_ifc_deps() {
@@ -386,14 +530,176 @@ pub mod clang_args {
     }
 }
 
+/// Arguments for the `clang-cl` driver, the `MSVC` compatible frontend of `Clang`.
+///
+/// The `cl` style options are directly understood by the driver, while the modules related
+/// ones, which doesn't have a `cl` counterpart, are forwarded to the `Clang` frontend through
+/// the `/clang:` escape hatch
+pub mod clang_cl_args {
+    use std::path::Path;
+
+    use crate::{project_model::compiler::CppCompiler, utils::constants};
+
+    use super::*;
+
+    pub(crate) fn add_prebuilt_module_path(out_dir: &Path) -> String {
+        format!(
+            "/clang:-fprebuilt-module-path={}",
+            out_dir
+                .join(constants::compilers::CLANG_CL)
+                .join(constants::dir_names::MODULES)
+                .join(constants::dir_names::INTERFACES)
+                .display()
+        )
+    }
+
+    pub(crate) fn add_direct_module_interfaces_dependencies<'a>(
+        dependencies: &[Cow<str>],
+        out_dir: &Path,
+    ) -> Arguments<'a> {
+        let compiler = CppCompiler::CLANG_CL;
+
+        let mut args = Arguments::default();
+        dependencies.iter().for_each(|ifc_dep| {
+            let mut module_file_path = out_dir
+                .join(compiler.as_ref())
+                .join(constants::dir_names::MODULES)
+                .join(constants::dir_names::INTERFACES)
+                .join::<&str>(ifc_dep)
+                .display()
+                .to_string();
+            module_file_path.push('.');
+            module_file_path.push_str(compiler.get_typical_bmi_extension());
+
+            args.push(format!(
+                "/clang:-fmodule-file={}={}",
+                ifc_dep, module_file_path
+            ));
+        });
+
+        args
+    }
+
+    #[cfg(test)]
+    mod clang_cl_args_tests {
+        use crate::domain::commands::arguments::Arguments;
+        use std::{borrow::Cow, path::Path};
+
+        #[test]
+        fn test_clang_cl_add_direct_module_ifc_deps() {
+            let args = super::add_direct_module_interfaces_dependencies(
+                &[Cow::Borrowed("math.numbers")],
+                Path::new("out"),
+            );
+
+            assert_eq!(
+                args,
+                Arguments::from_vec(vec![
+                    "/clang:-fmodule-file=math.numbers=out/clang-cl/modules/interfaces/math.numbers.pcm"
+                        .into()
+                ])
+            );
+        }
+
+        #[test]
+        fn test_clang_cl_prebuilt_module_path() {
+            assert_eq!(
+                super::add_prebuilt_module_path(Path::new("out")),
+                "/clang:-fprebuilt-module-path=out/clang-cl/modules/interfaces"
+            );
+        }
+    }
+}
+
 pub mod msvc_args {
     use crate::cache::ZorkCache;
     use crate::domain::commands::command_lines::SourceCommandLine;
     use crate::domain::translation_unit::TranslationUnit;
-    use crate::project_model::compiler::StdLibMode;
+    use crate::project_model::compiler::{
+        CompileOptions, CppCompiler, OptimizationLevel, StdLibMode, WarningsLevel,
+    };
+    use crate::project_model::target::LinkOptions;
 
     use super::Arguments;
 
+    /// Translates the link options of a target into the `cl` style syntax, shared by `MSVC`
+    /// and `clang-cl`. The library search paths and the link arguments are forwarded to the
+    /// linker through the `/link` option, so they must be the last ones on the command line
+    pub(crate) fn link_options_args<'a>(options: &LinkOptions<'a>) -> Arguments<'a> {
+        let mut args = Arguments::default();
+
+        options.link_libraries.iter().for_each(|lib| {
+            if lib.ends_with(".lib") {
+                args.push(lib)
+            } else {
+                args.push(format!("{lib}.lib"))
+            }
+        });
+        if !options.frameworks.is_empty() {
+            log::warn!("The frameworks are only available for the Apple platforms. Ignoring them");
+        }
+
+        if !options.link_dirs.is_empty() || !options.link_args.is_empty() {
+            args.push("/link");
+            options
+                .link_dirs
+                .iter()
+                .for_each(|dir| args.push(format!("/LIBPATH:{}", dir.display())));
+            args.extend(options.link_args.iter().cloned());
+        }
+
+        args
+    }
+
+    /// Translates the structured compile options into the `cl` style syntax, shared by `MSVC`
+    /// and `clang-cl`
+    pub(crate) fn compile_options_args<'a>(
+        compiler: CppCompiler,
+        options: &CompileOptions,
+    ) -> Arguments<'a> {
+        let mut args = Arguments::default();
+
+        options
+            .defines
+            .iter()
+            .for_each(|define| args.push(format!("/D{define}")));
+        options
+            .include_dirs
+            .iter()
+            .for_each(|dir| args.push(format!("/I{}", dir.display())));
+        // MSVC only silences the warnings of the external headers once their warning level is set
+        if compiler.ne(&CppCompiler::CLANG_CL) && !options.system_include_dirs.is_empty() {
+            args.push("/external:W0");
+        }
+        options.system_include_dirs.iter().for_each(|dir| {
+            args.push(match compiler {
+                CppCompiler::CLANG_CL => format!("/imsvc{}", dir.display()),
+                _ => format!("/external:I{}", dir.display()),
+            })
+        });
+
+        args.push_opt(options.warnings.map(|warnings| match warnings {
+            WarningsLevel::ALL => "/W3",
+            WarningsLevel::EXTRA | WarningsLevel::ERROR => "/W4",
+        }));
+        if options.warnings.eq(&Some(WarningsLevel::ERROR)) {
+            args.push("/WX");
+        }
+
+        // NOTE: there's no more aggressive optimization level than /O2 in MSVC
+        args.push_opt(options.optimization.map(|level| match level {
+            OptimizationLevel::O0 => "/Od",
+            OptimizationLevel::O1 | OptimizationLevel::O2 | OptimizationLevel::O3 => "/O2",
+            OptimizationLevel::OS => "/O1",
+        }));
+
+        if options.debug_info.unwrap_or_default() {
+            args.push("/Zi");
+        }
+
+        args
+    }
+
     pub(crate) fn generate_std_cmd<'a>(
         cache: &ZorkCache<'a>,
         stdlib_mode: StdLibMode,
@@ -430,4 +736,82 @@ pub mod msvc_args {
 
         SourceCommandLine::new(stdlib_sf, arguments, stdlib_obj_path.to_path_buf())
     }
+
+    #[cfg(test)]
+    mod msvc_args_tests {
+        use crate::domain::commands::arguments::Arguments;
+        use crate::project_model::compiler::{
+            CompileOptions, CppCompiler, OptimizationLevel, WarningsLevel,
+        };
+        use crate::project_model::target::LinkOptions;
+        use std::{borrow::Cow, path::PathBuf};
+
+        #[test]
+        fn test_msvc_link_options() {
+            let options = LinkOptions {
+                link_libraries: vec![Cow::Borrowed("fmt"), Cow::Borrowed("user32.lib")],
+                link_dirs: vec![PathBuf::from("deps")],
+                link_args: vec!["/DEBUG".into()],
+                frameworks: vec![],
+            };
+
+            assert_eq!(
+                super::link_options_args(&options),
+                Arguments::from_vec(vec![
+                    "fmt.lib".into(),
+                    "user32.lib".into(),
+                    "/link".into(),
+                    "/LIBPATH:deps".into(),
+                    "/DEBUG".into(),
+                ])
+            );
+
+            let options = LinkOptions {
+                link_libraries: vec![Cow::Borrowed("fmt")],
+                ..Default::default()
+            };
+            assert_eq!(
+                super::link_options_args(&options),
+                Arguments::from_vec(vec!["fmt.lib".into()])
+            );
+        }
+
+        #[test]
+        fn test_msvc_compile_options() {
+            let options = CompileOptions {
+                defines: vec![Cow::Borrowed("ZORK")],
+                include_dirs: vec![PathBuf::from("include")],
+                system_include_dirs: vec![PathBuf::from("deps"), PathBuf::from("vendor")],
+                warnings: Some(WarningsLevel::ERROR),
+                optimization: Some(OptimizationLevel::O0),
+                debug_info: Some(true),
+            };
+
+            let expected = |system_includes: &[&'static str]| {
+                let mut args = vec!["/DZORK".into(), "/Iinclude".into()];
+                args.extend(system_includes.iter().map(|&arg| arg.into()));
+                args.extend(["/W4".into(), "/WX".into(), "/Od".into(), "/Zi".into()]);
+                Arguments::from_vec(args)
+            };
+
+            // The warning level of the external headers is set once, for all of them
+            assert_eq!(
+                super::compile_options_args(CppCompiler::MSVC, &options),
+                expected(&["/external:W0", "/external:Ideps", "/external:Ivendor"])
+            );
+            assert_eq!(
+                super::compile_options_args(CppCompiler::CLANG_CL, &options),
+                expected(&["/imsvcdeps", "/imsvcvendor"])
+            );
+
+            // And not at all when there's no one
+            let options = CompileOptions {
+                system_include_dirs: vec![],
+                ..options
+            };
+            assert!(!super::compile_options_args(CppCompiler::MSVC, &options)
+                .iter()
+                .any(|arg| arg.value().starts_with("/external")));
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::domain::commands::arguments::{Argument, Arguments};
 use crate::domain::flyweight_data::FlyweightData;
 use crate::domain::target::{Target, TargetIdentifier};
-use crate::domain::translation_unit::{TranslationUnit, TranslationUnitStatus};
+use crate::domain::translation_unit::{BuildReason, TranslationUnit, TranslationUnitStatus};
 use crate::project_model::compiler::CppCompiler;
 use crate::utils::constants::error_messages;
 use crate::utils::fs;
@@ -18,6 +18,8 @@ pub struct Commands<'a> {
     pub flyweight_data: Option<FlyweightData<'a>>,
     pub modules: ModulesCommands<'a>,
     pub targets: IndexMap<TargetIdentifier<'a>, Target<'a>>,
+    #[serde(default)]
+    pub generators: Vec<SourceCommandLine<'a>>,
 }
 
 impl<'a> Commands<'a> {
@@ -51,6 +53,8 @@ pub struct ModulesCommands<'a> {
     pub cpp_stdlib: Option<SourceCommandLine<'a>>,
     pub c_compat_stdlib: Option<SourceCommandLine<'a>>,
     pub system_modules: Vec<SourceCommandLine<'a>>,
+    #[serde(default)]
+    pub header_units: Vec<SourceCommandLine<'a>>,
     pub interfaces: Vec<SourceCommandLine<'a>>,
     pub implementations: Vec<SourceCommandLine<'a>>,
 }
@@ -87,6 +91,9 @@ impl<'a> ModulesCommands<'a> {
 ///
 /// *byproduct*: A [`PathBuf`] like [`Argument`] which hold the physical address on the filesystem
 ///     where the compiled object file will be dumped after building it
+///
+/// *build_reason*: A [`BuildReason`] of why the command is pending to build on the current iteration,
+///     if it's known
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct SourceCommandLine<'a> {
     pub directory: PathBuf,
@@ -94,6 +101,8 @@ pub struct SourceCommandLine<'a> {
     pub args: Arguments<'a>,
     pub status: TranslationUnitStatus,
     pub byproduct: Argument<'a>,
+    #[serde(skip)]
+    pub build_reason: Option<BuildReason>,
 }
 
 impl<'a> SourceCommandLine<'a> {
@@ -108,9 +117,16 @@ impl<'a> SourceCommandLine<'a> {
             args,
             status: TranslationUnitStatus::PendingToBuild,
             byproduct: byproduct.into(),
+            build_reason: Some(BuildReason::New),
         }
     }
 
+    /// Marks the command as pending to build on the current iteration, for the given reason
+    pub fn mark_pending(&mut self, reason: BuildReason) {
+        self.status = TranslationUnitStatus::PendingToBuild;
+        self.build_reason = Some(reason);
+    }
+
     pub fn path(&self) -> PathBuf {
         self.directory.join(Path::new(&self.filename))
     }
@@ -124,7 +140,10 @@ impl<'a> SourceCommandLine<'a> {
 pub struct LinkerCommandLine<'a> {
     pub target: Argument<'a>,
     pub args: Arguments<'a>,
-    pub extra_args: Arguments<'a>,
+    #[serde(default)]
+    pub extra_args: Arguments<'a>, // the deprecated `extra_args` of the target
+    #[serde(default)]
+    pub link_options: Arguments<'a>, // must be placed after the object files
     pub execution_result: TranslationUnitStatus,
 }
 
@@ -134,7 +153,7 @@ impl<'a> LinkerCommandLine<'a> {
             CppCompiler::CLANG | CppCompiler::GCC => {
                 vec![Argument::from("-o"), self.target.clone()]
             }
-            CppCompiler::MSVC => vec![self.target.clone()],
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => vec![self.target.clone()],
         }
     }
 }
@@ -154,6 +173,7 @@ impl<'a> Commands<'a> {
             .iter_mut()
             .chain(self.modules.c_compat_stdlib.as_mut_slice().iter_mut())
             .chain(self.modules.system_modules.as_mut_slice().iter_mut())
+            .chain(self.modules.header_units.as_mut_slice().iter_mut())
             .chain(self.modules.interfaces.as_mut_slice().iter_mut())
             .chain(self.modules.implementations.as_mut_slice().iter_mut())
     }
@@ -3,10 +3,13 @@ use std::path::Path;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::commands::arguments;
 use super::commands::arguments::clang_args;
+use super::commands::arguments::clang_cl_args;
 use super::commands::arguments::Argument;
 use super::commands::arguments::Arguments;
 use crate::cache::CompilersMetadata;
+use crate::compiler::helpers;
 use crate::{
     cache::EnvVars,
     project_model::{compiler::CppCompiler, ZorkModel},
@@ -21,7 +24,15 @@ pub struct FlyweightData<'a> {
     pub shared_args: Arguments<'a>,
     pub std_references: Arguments<'a>, // the correct format of explicitly add the std modular libs
     // to the compiler
+    #[serde(default)]
+    pub header_units_refs: Arguments<'a>, // the references to the user headers built as header units
     pub compile_but_dont_link: [Argument<'a>; 1],
+    #[serde(default)]
+    pub compile_options: Arguments<'a>, // the user declared structured compile options
+    #[serde(default)]
+    pub compile_args: Arguments<'a>, // the user declared compile only arguments
+    #[serde(default)]
+    pub linker_args: Arguments<'a>, // the args that only makes sense when the linker is invoked
     pub env_vars: EnvVars,
 }
 
@@ -31,6 +42,9 @@ impl<'a> FlyweightData<'a> {
         general_args.push(program_data.compiler.language_level_arg());
         general_args.extend_from_slice(&program_data.compiler.extra_args);
 
+        let (toolchain_args, linker_args) = generate_toolchain_args(program_data);
+        general_args.extend(toolchain_args);
+
         let (shared_args, std_references, env_vars) = match program_data.compiler.cpp_compiler {
             CppCompiler::CLANG => {
                 let shared_args = generate_clang_flyweight_args(program_data, compilers_metadata);
@@ -48,6 +62,14 @@ impl<'a> FlyweightData<'a> {
                     &compilers_metadata.msvc.env_vars,
                 )
             }
+            CppCompiler::CLANG_CL => {
+                let shared_args = generate_clang_cl_flyweight_args(program_data);
+                (
+                    shared_args.0,
+                    shared_args.1,
+                    &compilers_metadata.clang_cl.env_vars,
+                )
+            }
             CppCompiler::GCC => {
                 let shared_args = generate_gcc_flyweight_args();
                 (
@@ -61,14 +83,26 @@ impl<'a> FlyweightData<'a> {
         let compile_but_dont_link: [Argument; 1] =
             [Argument::from(match program_data.compiler.cpp_compiler {
                 CppCompiler::CLANG | CppCompiler::GCC => "-c",
-                CppCompiler::MSVC => "/c",
+                CppCompiler::MSVC | CppCompiler::CLANG_CL => "/c",
             })];
 
         Self {
             general_args,
             shared_args,
             std_references,
+            header_units_refs: helpers::header_units_references(
+                program_data.compiler.cpp_compiler,
+                program_data.build.output_dir.as_ref(),
+                &program_data.modules.header_units,
+                &program_data.modules.sys_modules,
+            ),
             compile_but_dont_link,
+            compile_options: arguments::compile_options_args(
+                program_data.compiler.cpp_compiler,
+                &program_data.compiler.options,
+            ),
+            compile_args: Arguments::from_vec(program_data.compiler.compile_args.clone()),
+            linker_args,
             env_vars: env_vars.clone(),
         }
     }
@@ -103,6 +137,49 @@ fn generate_msvc_flyweight_args<'a>(
 
 type SharedArgsStdRefsTuple<'a> = (Arguments<'a>, Arguments<'a>);
 
+/// Translates the user declared toolchain into the arguments that tell the compiler's driver
+/// which platform is targeted, returning them along with the ones that must be only sent
+/// when the linker is invoked
+fn generate_toolchain_args<'a>(program_data: &'a ZorkModel<'_>) -> (Arguments<'a>, Arguments<'a>) {
+    let compiler = program_data.compiler.cpp_compiler;
+    let toolchain = &program_data.compiler.toolchain;
+
+    let mut toolchain_args = Arguments::default();
+    let mut linker_args = Arguments::default();
+
+    if let Some(triple) = &toolchain.target {
+        match compiler {
+            CppCompiler::CLANG | CppCompiler::CLANG_CL => {
+                toolchain_args.push(format!("--target={triple}"))
+            }
+            _ => log::warn!(
+                "{compiler} doesn't accept a target triple. Set the `driver_path` to the cross compiler instead"
+            ),
+        }
+    }
+
+    if let Some(sysroot) = &toolchain.sysroot {
+        match compiler {
+            CppCompiler::CLANG | CppCompiler::GCC => {
+                toolchain_args.push(format!("--sysroot={}", sysroot.display()))
+            }
+            CppCompiler::CLANG_CL => {
+                toolchain_args.push(format!("/clang:--sysroot={}", sysroot.display()))
+            }
+            CppCompiler::MSVC => log::warn!("{compiler} doesn't support a custom sysroot"),
+        }
+    }
+
+    if let Some(linker) = &toolchain.linker {
+        match compiler {
+            CppCompiler::MSVC => log::warn!("{compiler} doesn't support a custom linker"),
+            _ => linker_args.push(format!("-fuse-ld={linker}")),
+        }
+    }
+
+    (toolchain_args, linker_args)
+}
+
 fn generate_clang_flyweight_args<'a>(
     program_data: &'a ZorkModel<'_>,
     compilers_metadata: &CompilersMetadata<'_>,
@@ -134,8 +211,85 @@ fn generate_clang_flyweight_args<'a>(
     (compiler_flyweight_args, std_references)
 }
 
+/// `clang-cl` works against the `MSVC` STL, so there's no modular standard library references
+/// to provide, since `import std;` isn't yet available for this driver
+fn generate_clang_cl_flyweight_args<'a>(
+    program_data: &ZorkModel<'_>,
+) -> SharedArgsStdRefsTuple<'a> {
+    let out_dir: &Path = program_data.build.output_dir.as_ref();
+    let mut compiler_flyweight_args = Arguments::default();
+
+    compiler_flyweight_args.push("/EHsc"); // exception_handling_model
+    compiler_flyweight_args.push("/nologo");
+    compiler_flyweight_args.push(clang_cl_args::add_prebuilt_module_path(out_dir));
+
+    (compiler_flyweight_args, Arguments::default())
+}
+
 fn generate_gcc_flyweight_args<'a>() -> SharedArgsStdRefsTuple<'a> {
     let mut compiler_flyweight_args = Arguments::default();
     compiler_flyweight_args.push("-fmodules-ts");
     (compiler_flyweight_args, Arguments::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::path::Path;
+
+    use crate::project_model::compiler::{CompilerModel, ToolchainModel};
+
+    use super::*;
+
+    fn toolchain_args(cpp_compiler: CppCompiler) -> (Vec<String>, Vec<String>) {
+        let model = ZorkModel {
+            compiler: CompilerModel {
+                cpp_compiler,
+                toolchain: ToolchainModel {
+                    target: Some(Cow::Borrowed("aarch64-linux-gnu")),
+                    sysroot: Some(Cow::Borrowed(Path::new("/usr/aarch64-linux-gnu"))),
+                    linker: Some(Cow::Borrowed("lld")),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (toolchain_args, linker_args) = generate_toolchain_args(&model);
+        let to_strings = |args: Arguments| args.iter().map(|arg| arg.to_string()).collect();
+        (to_strings(toolchain_args), to_strings(linker_args))
+    }
+
+    #[test]
+    fn test_toolchain_args_per_compiler() {
+        let (args, linker_args) = toolchain_args(CppCompiler::CLANG);
+        assert_eq!(
+            args,
+            [
+                "--target=aarch64-linux-gnu",
+                "--sysroot=/usr/aarch64-linux-gnu"
+            ]
+        );
+        assert_eq!(linker_args, ["-fuse-ld=lld"]);
+
+        // GCC is a cross compiler by itself, so it doesn't accept the triple
+        let (args, linker_args) = toolchain_args(CppCompiler::GCC);
+        assert_eq!(args, ["--sysroot=/usr/aarch64-linux-gnu"]);
+        assert_eq!(linker_args, ["-fuse-ld=lld"]);
+
+        let (args, linker_args) = toolchain_args(CppCompiler::CLANG_CL);
+        assert_eq!(
+            args,
+            [
+                "--target=aarch64-linux-gnu",
+                "/clang:--sysroot=/usr/aarch64-linux-gnu"
+            ]
+        );
+        assert_eq!(linker_args, ["-fuse-ld=lld"]);
+
+        let (args, linker_args) = toolchain_args(CppCompiler::MSVC);
+        assert!(args.is_empty());
+        assert!(linker_args.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod commands;
 pub mod flyweight_data;
+pub mod reachability;
 pub mod target;
 pub mod translation_unit;
@@ -0,0 +1,393 @@
+//! Resolution of the user's module units that every target needs, following the `import`
+//! declarations from the sources of the target through the declared module units
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use color_eyre::{eyre::Context, Result};
+use indexmap::IndexMap;
+
+use crate::domain::target::{TargetIdentifier, TargetKind};
+use crate::domain::translation_unit::TranslationUnit;
+use crate::project_model::target::TargetModel;
+use crate::project_model::ZorkModel;
+use crate::utils::scanner::{self, ModuleUnitDeclaration};
+
+/// The paths of the module interfaces and implementations reachable from every enabled target,
+/// along with the module interfaces and header units imported by every translation unit
+#[derive(Debug, Default)]
+pub struct ModulesReachability<'a> {
+    reachable: IndexMap<&'a TargetIdentifier<'a>, HashSet<PathBuf>>,
+    imported_units: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl<'a> ModulesReachability<'a> {
+    /// Computes the module units that are reachable from the targets enabled for the
+    /// current program iteration
+    pub fn new(model: &'a ZorkModel<'a>) -> Result<Self> {
+        let graph = ModulesGraph::new(model)?;
+        let enabled_targets = model
+            .targets
+            .iter()
+            .filter(|(_, target)| target.enabled_for_current_program_iteration);
+
+        let mut sources: HashMap<PathBuf, ModuleUnitDeclaration> = HashMap::new();
+        for (identifier, target) in enabled_targets.clone() {
+            for source in target.sources.as_slice() {
+                if let Entry::Vacant(entry) = sources.entry(source.path()) {
+                    let declaration =
+                        scanner::scan_module_unit(entry.key()).with_context(|| {
+                            format!(
+                                "Unable to resolve the modules imported by the target: {:?}",
+                                identifier.name()
+                            )
+                        })?;
+                    entry.insert(declaration);
+                }
+            }
+        }
+
+        let reachable = enabled_targets
+            .map(|(identifier, target)| {
+                (
+                    identifier,
+                    graph.reachable_from(identifier, target, &sources),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            reachable,
+            imported_units: graph.imported_units(model, &sources),
+        })
+    }
+
+    /// Whenever the module unit must be sent to the linker (or archiver) of the given target
+    pub fn is_reachable_from(&self, target: &TargetIdentifier, module_unit: &Path) -> bool {
+        self.reachable
+            .get(target)
+            .is_some_and(|reachable| reachable.contains(module_unit))
+    }
+
+    /// The module interfaces, system modules and header units imported by the given translation
+    /// unit, which must be built before it
+    pub fn imported_by(&self, translation_unit: &Path) -> impl Iterator<Item = &PathBuf> {
+        self.imported_units
+            .get(translation_unit)
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// A module unit with the module names that it provides to its importers, and the ones that it imports
+struct ModuleNode<'a> {
+    path: PathBuf,
+    owner: Option<&'a str>,
+    is_interface: bool,
+    provides: Vec<String>,
+    imports: Vec<String>,
+    header_imports: Vec<String>,
+}
+
+struct ModulesGraph<'a>(Vec<ModuleNode<'a>>);
+
+impl<'a> ModulesGraph<'a> {
+    fn new(model: &'a ZorkModel<'a>) -> Result<Self> {
+        let modules = &model.modules;
+        let mut nodes =
+            Vec::with_capacity(modules.interfaces.len() + modules.implementations.len());
+
+        for interface in &modules.interfaces {
+            let path = interface.path();
+            let declaration = scanner::scan_module_unit(&path)?;
+            let provides = declared_module_name(&declaration)
+                .unwrap_or_else(|| interface.provided_module_name());
+
+            nodes.push(ModuleNode {
+                imports: imports_of(&declaration, &interface.dependencies),
+                header_imports: declaration.header_imports,
+                provides: vec![provides],
+                owner: interface.owner.as_deref(),
+                is_interface: true,
+                path,
+            });
+        }
+
+        for implementation in &modules.implementations {
+            let path = implementation.path();
+            let declaration = scanner::scan_module_unit(&path)?;
+            // An implementation unit is needed whenever the module that it implements is reachable
+            let provides = declared_module_name(&declaration).map_or_else(
+                || {
+                    implementation
+                        .dependencies
+                        .iter()
+                        .map(|dep| dep.to_string())
+                        .collect()
+                },
+                |module| vec![module],
+            );
+
+            nodes.push(ModuleNode {
+                imports: imports_of(&declaration, &implementation.dependencies),
+                header_imports: declaration.header_imports,
+                provides,
+                owner: implementation.owner.as_deref(),
+                is_interface: false,
+                path,
+            });
+        }
+
+        Ok(Self(nodes))
+    }
+
+    fn reachable_from(
+        &self,
+        identifier: &TargetIdentifier,
+        target: &TargetModel,
+        sources: &HashMap<PathBuf, ModuleUnitDeclaration>,
+    ) -> HashSet<PathBuf> {
+        let visible = |node: &&ModuleNode| node.owner.is_none_or(|o| o == identifier.name());
+        let is_library = matches!(target.kind, TargetKind::StaticLib | TargetKind::DyLib);
+
+        let mut reachable_modules: HashSet<String> = target
+            .sources
+            .as_slice()
+            .iter()
+            .filter_map(|source| sources.get(&source.path()))
+            .flat_map(|declaration| declaration.imports.iter().cloned())
+            .collect();
+
+        let mut reachable_units: HashSet<PathBuf> = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for node in self.0.iter().filter(visible) {
+                let owned_by_library = is_library && node.owner.is_some();
+                let is_reachable = owned_by_library
+                    || node
+                        .provides
+                        .iter()
+                        .any(|module| reachable_modules.contains(module));
+
+                if is_reachable && reachable_units.insert(node.path.clone()) {
+                    reachable_modules.extend(node.imports.iter().cloned());
+                    changed = true;
+                }
+            }
+        }
+
+        reachable_units
+    }
+
+    /// Resolves the module interfaces, system modules and header units imported by every module
+    /// unit and by every one of the given sources. The implementation units implicitly import the
+    /// interface of the module that they implement
+    fn imported_units(
+        &self,
+        model: &ZorkModel,
+        sources: &HashMap<PathBuf, ModuleUnitDeclaration>,
+    ) -> HashMap<PathBuf, HashSet<PathBuf>> {
+        let modules = &model.modules;
+        // Every header unit along with the path that its importers refer to
+        let header_units = modules
+            .sys_modules
+            .iter()
+            .map(|sys_module| (sys_module.path(), PathBuf::from(&*sys_module.file_stem)))
+            .chain(
+                modules
+                    .header_units
+                    .iter()
+                    .map(|header| (header.path(), header.path())),
+            )
+            .collect::<Vec<(PathBuf, PathBuf)>>();
+
+        let resolve = |unit: &Path, imports: &[&String], header_imports: &[String]| {
+            let interfaces = self
+                .0
+                .iter()
+                .filter(|node| node.is_interface && node.path.ne(unit))
+                .filter(|node| node.provides.iter().any(|module| imports.contains(&module)))
+                .map(|node| node.path.clone());
+            let headers = header_units
+                .iter()
+                .filter(|(_, imported_as)| {
+                    header_imports
+                        .iter()
+                        .any(|import| imported_as.ends_with(import))
+                })
+                .map(|(path, _)| path.clone());
+
+            interfaces.chain(headers).collect::<HashSet<PathBuf>>()
+        };
+
+        let module_units = self.0.iter().map(|node| {
+            let implemented = node.provides.iter().filter(|_| !node.is_interface);
+            let imports = node.imports.iter().chain(implemented).collect::<Vec<_>>();
+            let imported = resolve(&node.path, &imports, &node.header_imports);
+            (node.path.clone(), imported)
+        });
+        let sources = sources.iter().map(|(path, declaration)| {
+            let imports = declaration.imports.iter().collect::<Vec<_>>();
+            let imported = resolve(path, &imports, &declaration.header_imports);
+            (path.clone(), imported)
+        });
+
+        module_units.chain(sources).collect()
+    }
+}
+
+/// The fully qualified name of the module (or partition) declared on the unit, if any
+fn declared_module_name(declaration: &ModuleUnitDeclaration) -> Option<String> {
+    declaration
+        .module_name
+        .as_ref()
+        .map(|module| match &declaration.partition_name {
+            Some(partition) => format!("{module}:{partition}"),
+            None => module.clone(),
+        })
+}
+
+fn imports_of(
+    declaration: &ModuleUnitDeclaration,
+    declared_deps: &[std::borrow::Cow<str>],
+) -> Vec<String> {
+    declaration
+        .imports
+        .iter()
+        .cloned()
+        .chain(declared_deps.iter().map(|dep| dep.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod reachability_tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::input::CliArgs;
+    use crate::config_file::{self, ZorkConfigFile};
+    use crate::utils::reader::build_model;
+
+    #[test]
+    fn test_modules_reachable_from_targets() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        let files = [
+            ("main.cpp", "import math;\nint main() {}\n"),
+            ("tests.cpp", "import testing;\nint main() {}\n"),
+            (
+                "math.cppm",
+                "export module math;\nexport import :numbers;\n",
+            ),
+            ("math-numbers.cppm", "export module math:numbers;\n"),
+            ("math.cpp", "module math;\n"),
+            ("broken.cppm", "export module broken;\n"),
+            ("testing.cppm", "export module testing;\nimport math;\n"),
+        ];
+        for (file, contents) in files {
+            std::fs::write(root.join(file), contents)?;
+        }
+
+        let config: ZorkConfigFile = config_file::zork_cfg_from_file(
+            r#"
+            [project]
+            name = 'reachability'
+
+            [compiler]
+            cpp_compiler = 'clang'
+            cpp_standard = '23'
+
+            [targets.executable]
+            sources = ['main.cpp']
+
+            [targets.tests]
+            sources = ['tests.cpp']
+            modules = { interfaces = ['testing.cppm'] }
+
+            [modules]
+            interfaces = ['math-numbers.cppm', 'math.cppm', 'broken.cppm']
+            implementations = ['math.cpp']
+            "#,
+        )?;
+        let cli_args = CliArgs::parse_from(["", "build"]);
+        let model = build_model(config, &cli_args, root)?;
+        let reachability = ModulesReachability::new(&model)?;
+
+        let executable = TargetIdentifier::from("executable");
+        let tests = TargetIdentifier::from("tests");
+        let math_units = ["math.cppm", "math-numbers.cppm", "math.cpp"];
+
+        for unit in math_units {
+            assert!(reachability.is_reachable_from(&executable, &root.join(unit)));
+            assert!(reachability.is_reachable_from(&tests, &root.join(unit)));
+        }
+        assert!(reachability.is_reachable_from(&tests, &root.join("testing.cppm")));
+        assert!(!reachability.is_reachable_from(&executable, &root.join("testing.cppm")));
+
+        assert!(!reachability.is_reachable_from(&executable, &root.join("broken.cppm")));
+        assert!(!reachability.is_reachable_from(&tests, &root.join("broken.cppm")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_units_imported_by_translation_units() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        let files = [
+            (
+                "main.cpp",
+                "import math;\nimport <iostream>;\nint main() {}\n",
+            ),
+            ("math.cppm", "export module math;\nimport \"utils.hpp\";\n"),
+            ("math.cpp", "module math;\n"),
+            ("utils.hpp", "#pragma once\n"),
+        ];
+        for (file, contents) in files {
+            std::fs::write(root.join(file), contents)?;
+        }
+
+        let config: ZorkConfigFile = config_file::zork_cfg_from_file(
+            r#"
+            [project]
+            name = 'imports'
+
+            [compiler]
+            cpp_compiler = 'clang'
+            cpp_standard = '23'
+
+            [targets.executable]
+            sources = ['main.cpp']
+
+            [modules]
+            interfaces = ['math.cppm']
+            implementations = ['math.cpp']
+            sys_modules = ['iostream']
+            header_units = ['utils.hpp']
+            "#,
+        )?;
+        let cli_args = CliArgs::parse_from(["", "build"]);
+        let model = build_model(config, &cli_args, root)?;
+        let reachability = ModulesReachability::new(&model)?;
+
+        let imported_by = |unit: &str| {
+            let mut imported = reachability
+                .imported_by(&root.join(unit))
+                .cloned()
+                .collect::<Vec<_>>();
+            imported.sort();
+            imported
+        };
+        assert_eq!(
+            imported_by("main.cpp"),
+            [root.join("math.cppm"), model.modules.sys_modules[0].path()]
+        );
+        assert_eq!(imported_by("math.cppm"), [root.join("utils.hpp")]);
+        // The implementation units implicitly import the interface of their module
+        assert_eq!(imported_by("math.cpp"), [root.join("math.cppm")]);
+
+        Ok(())
+    }
+}
@@ -5,12 +5,15 @@ use crate::domain::commands::command_lines::{LinkerCommandLine, SourceCommandLin
 use crate::project_model::sourceset::SourceSet;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::path::PathBuf;
 
 /// The final product that will be made after the building process
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct Target<'a> {
     pub sources: Vec<SourceCommandLine<'a>>,
     pub linker: LinkerCommandLine<'a>,
+    #[serde(default)]
+    pub pch: Option<SourceCommandLine<'a>>,
     pub kind: TargetKind,
     pub enabled_for_current_program_iteration: bool,
 }
@@ -24,6 +27,7 @@ impl<'a> Target<'a> {
         Self {
             sources: Vec::default(),
             linker: LinkerCommandLine::default(),
+            pch: None,
             kind,
             enabled_for_current_program_iteration: true,
         }
@@ -47,6 +51,18 @@ impl<'a> TargetIdentifier<'a> {
     pub fn name(&'a self) -> &'a str {
         self.0.as_ref()
     }
+
+    /// The workspace member that declares the target, if any
+    pub fn member(&self) -> Option<&str> {
+        self.0.split_once(':').map(|(member, _)| member)
+    }
+
+    /// The path, relative to the directory of the products of a compiler, where the final product
+    /// of the target is placed. The ones of the workspace members are nested under a directory
+    /// named after their member
+    pub fn as_path(&self) -> PathBuf {
+        self.0.split(':').collect()
+    }
 }
 
 /// The different types of final products
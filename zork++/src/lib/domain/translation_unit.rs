@@ -28,6 +28,7 @@ pub trait TranslationUnit<'a>: AsTranslationUnit<'a> + Any<Inv<'a>> + Display +
     ///     path: PathBuf::from("/usr/include"),
     ///     file_stem: Cow::from("std"),
     ///     extension: Cow::from("h"),
+    ///     extra_args: vec![],
     /// };
     ///
     /// assert_eq!(source_file.path(), PathBuf::from("/usr/include/std.h"));
@@ -36,6 +37,7 @@ pub trait TranslationUnit<'a>: AsTranslationUnit<'a> + Any<Inv<'a>> + Display +
     ///     path: PathBuf::from("/usr/include"),
     ///     file_stem: Cow::from("std.compat"),
     ///     extension: Cow::from("h"),
+    ///     extra_args: vec![],
     /// };
     ///
     /// assert_eq!(source_file_compat.path(), PathBuf::from("/usr/include/std.compat.h"));
@@ -53,9 +55,15 @@ pub trait TranslationUnit<'a>: AsTranslationUnit<'a> + Any<Inv<'a>> + Display +
     /// Outputs the declared extension for `self`
     fn extension(&self) -> &Cow<'_, str>;
 
-    /// Outputs the file stem concatenated with the extension for a given tu
+    /// Outputs the file stem concatenated with the extension for a given tu, or just the file
+    /// stem for the ones without extension, like the system headers
     fn filename(&self) -> String {
-        format!("{}.{}", self.file_stem(), self.extension())
+        let extension = self.extension();
+        if extension.is_empty() {
+            self.file_stem().to_string()
+        } else {
+            format!("{}.{}", self.file_stem(), extension)
+        }
     }
 }
 
@@ -97,8 +105,10 @@ pub enum TranslationUnitKind<'a> {
     ModuleInterface,
     ModuleImplementation,
     SourceFile(&'a TargetIdentifier<'a>),
+    PrecompiledHeader(&'a TargetIdentifier<'a>),
     ModularStdLib(StdLibMode),
     SystemHeader,
+    HeaderUnit,
 }
 
 /// The different states of a translation unit in the whole lifecycle of
@@ -119,6 +129,58 @@ pub enum TranslationUnitStatus {
     ToDelete,
     /// The execution failed, returning a [`color_eyre::Result`] with the Err variant
     Error,
+    /// The command wasn't executed, because something that it depends on failed to build
+    Skipped,
+}
+
+impl TranslationUnitStatus {
+    /// Whether the command couldn't be executed, or its execution was unsuccessful
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            TranslationUnitStatus::Failed | TranslationUnitStatus::Error
+        )
+    }
+
+    /// Whether the byproducts of the command can't be used by the ones that depend on them,
+    /// because it failed, or it was skipped
+    pub fn is_unusable(&self) -> bool {
+        self.is_failure() || self.eq(&TranslationUnitStatus::Skipped)
+    }
+}
+
+/// Why a command is executed on the current iteration. It's only tracked for the current
+/// iteration, so it isn't stored on the cache
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BuildReason {
+    /// The command wasn't generated on any previous iteration
+    New,
+    /// The translation unit, or any of the files that it depends on, was modified since the last iteration
+    Modified,
+    /// The arguments of the command changed since the last iteration
+    ArgsChanged,
+    /// Something that the command depends on is rebuilt on the current iteration
+    DependencyRebuilt,
+    /// Any of the declared outputs of the command is missing
+    MissingOutputs,
+    /// The command failed, or it wasn't reached, on the last iteration
+    NotBuilt,
+    /// The command is executed on every iteration
+    Always,
+}
+
+impl Display for BuildReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildReason::New => write!(f, "new"),
+            BuildReason::Modified => write!(f, "modified"),
+            BuildReason::ArgsChanged => write!(f, "args changed"),
+            BuildReason::DependencyRebuilt => write!(f, "dependency rebuilt"),
+            BuildReason::MissingOutputs => write!(f, "missing outputs"),
+            BuildReason::NotBuilt => write!(f, "not built yet"),
+            BuildReason::Always => write!(f, "always executed"),
+        }
+    }
 }
 
 impl From<color_eyre::Result<ExitStatus, Report>> for TranslationUnitStatus {
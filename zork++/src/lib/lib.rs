@@ -16,33 +16,54 @@ pub mod utils;
 /// data sent to stdout/stderr
 pub mod worker {
     use crate::config_file;
+    use crate::config_file::toolchain::ToolchainAttribute;
     use crate::config_file::ZorkConfigFile;
-    use crate::domain::target::Target;
+    use crate::domain::reachability::ModulesReachability;
+    use crate::domain::target::{Target, TargetKind};
     use crate::project_model;
+    use std::borrow::Cow;
     use std::path::PathBuf;
     use std::{fs, path::Path, time::Instant};
 
     use crate::utils::constants::{dir_names, error_messages, ZORK};
     use crate::{
-        cache::{self, ZorkCache},
+        cache::{self, EnvVars, ZorkCache},
         cli::{
             input::{CliArgs, Command},
             output::executors,
+            output::hooks::{self, HookStage},
         },
         compiler::generate_commands_arguments,
         project_model::{compiler::CppCompiler, ZorkModel},
         utils::{
             self,
-            reader::{find_config_files, ConfigFile},
+            expansion::{self, ExpandedValues},
+            inheritance,
+            reader::{
+                discard_inherited_config_files, discard_workspace_members, find_config_files,
+                find_workspace_members, load_config_files, ConfigFile, WorkspaceMember,
+            },
             template::create_templated_project,
+            validation::{self, ConfigFileUnit, SourceFiles},
         },
     };
-    use color_eyre::eyre::ContextCompat;
+    use chrono::{DateTime, Utc};
+    use color_eyre::eyre::{eyre, ContextCompat};
     use color_eyre::{eyre::Context, Report, Result};
 
     /// The main work of the project. Runs the tasks
     /// inputted in the CLI
     pub fn run_zork(cli_args: &CliArgs) -> std::result::Result<(), Report> {
+        // The schema doesn't depend on any project, so it's just written to stdout
+        if cli_args.command.eq(&Command::Schema) {
+            let schema = serde_json::to_string_pretty(&config_file::schema::json_schema())
+                .with_context(|| {
+                    "Unable to serialize the JSON Schema of the configuration files"
+                })?;
+            println!("{schema}");
+            return Ok(());
+        }
+
         let abs_project_root = determine_absolute_path_of_the_project_root(cli_args)?;
 
         // If this run is just for create a new C++ project with the given Zork++ projects creation
@@ -51,55 +72,256 @@ pub mod worker {
             return Ok(());
         };
 
-        let config_files: Vec<ConfigFile> =
-            find_config_files(&abs_project_root, &cli_args.match_files)?;
+        let config_files: Vec<ConfigFile> = if cli_args.config_files.is_empty() {
+            find_config_files(&abs_project_root, &cli_args.match_files)
+                .and_then(discard_workspace_members)
+                .and_then(discard_inherited_config_files)?
+        } else {
+            load_config_files(&abs_project_root, &cli_args.config_files)?
+        };
 
+        let toolchain_file = load_toolchain_file(&abs_project_root, cli_args)?;
+        let toolchain = toolchain_file
+            .as_ref()
+            .map(|toolchain_file| config_file::toolchain::toolchain_from_file(&toolchain_file.raw))
+            .transpose()
+            .with_context(|| error_messages::PARSE_TOOLCHAIN_FILE)?;
+
+        // With the keep going flag, the failure of a configuration file doesn't stop the
+        // remaining ones, and all the failures are reported together at the end. A check always
+        // reports the problems of every configuration file
+        let is_check = cli_args.command.eq(&Command::Check);
+        let keep_going = cli_args.keep_going || is_check;
+        let job = if is_check { "check" } else { "build" };
+        let total_config_files = config_files.len();
+        let mut failures = Vec::new();
         for config_file in config_files {
-            let cfg_path = &config_file.path;
-            log::debug!(
-                "Launching a Zork++ work event for the configuration file: {:?}",
-                cfg_path,
+            let cfg_path = config_file.path.clone();
+            let result = run_config_file(
+                cli_args,
+                config_file,
+                toolchain_file.as_ref(),
+                toolchain.as_ref(),
+                &abs_project_root,
             );
-            let raw_file = fs::read_to_string(cfg_path)
-                .with_context(|| format!("{}: {:?}", error_messages::READ_CFG_FILE, cfg_path))?;
 
-            let config: ZorkConfigFile<'_> = config_file::zork_cfg_from_file(raw_file.as_str())
-                .with_context(|| error_messages::PARSE_CFG_FILE)?;
+            if let Err(e) = result {
+                if !keep_going {
+                    return Err(e);
+                }
+                log::error!("The {job} failed for: {cfg_path:?}, continuing with the next ones");
+                failures.push((cfg_path, e));
+            }
+        }
 
-            create_output_directory(&config, &abs_project_root)?; // NOTE: review if we must
-                                                                  // rebuilt the cache and model if the
-                                                                  // output dir changes from
-                                                                  // previous
+        if failures.is_empty() {
+            return Ok(());
+        }
 
-            let mut cache: ZorkCache<'_> = cache::load(&config, cli_args, &abs_project_root)?;
+        let summary = failures
+            .iter()
+            .map(|(cfg_path, e)| format!("\n{cfg_path:?}: {e:#}"))
+            .collect::<String>();
+        Err(eyre!(
+            "The {job} failed for {} of {} configuration files:{summary}",
+            failures.len(),
+            total_config_files
+        ))
+    }
 
-            let program_data = {
-                // The purpose of this scope is to let the reader to see clearly
-                // that the model will only be mutated within the scope of this
-                // block, and after it, it will be read-only data
-                let mut program_data: ZorkModel<'_> = load_zork_model(
-                    &mut cache,
-                    &config_file,
-                    config,
-                    cli_args,
-                    &abs_project_root,
-                )?;
-                map_model_targets_to_cache(&mut program_data, &mut cache, cli_args)?;
+    /// Performs the work for one of the configuration files of the project
+    fn run_config_file(
+        cli_args: &CliArgs,
+        mut config_file: ConfigFile,
+        toolchain_file: Option<&ToolchainFile>,
+        toolchain: Option<&ToolchainAttribute<'_>>,
+        abs_project_root: &Path,
+    ) -> Result<()> {
+        let cfg_path = &config_file.path;
+        log::debug!(
+            "Launching a Zork++ work event for the configuration file: {:?}",
+            cfg_path,
+        );
+        // The configuration files that this one inherits from are merged into it, and their
+        // changes are tracked as well
+        let resolved_file = inheritance::read_config_file(cfg_path)?;
+        if let Some(last_time_modified) = resolved_file.last_time_modified() {
+            config_file.last_time_modified = config_file.last_time_modified.max(last_time_modified);
+        }
+        // The unknown attributes are reported before any attempt of deserializing them
+        let sources = SourceFiles::new(cfg_path, &resolved_file);
+        validation::report(validation::check_unknown_keys(&resolved_file.raw, &sources))?;
 
-                program_data
-            };
+        let mut config: ZorkConfigFile<'_> =
+            config_file::zork_cfg_from_file(resolved_file.raw.as_str())
+                .with_context(|| error_messages::PARSE_CFG_FILE)?;
 
-            cache
-                .process_compiler_metadata(&program_data)
-                .with_context(|| error_messages::FAILURE_LOADING_COMPILER_METADATA)?;
+        // The toolchain file selected from the command line takes precedence over the
+        // one declared on the configuration file, and its changes must be tracked as well
+        if let Some(toolchain_file) = toolchain_file {
+            config.toolchain = toolchain.cloned();
+            config_file.last_time_modified = config_file
+                .last_time_modified
+                .max(toolchain_file.last_time_modified);
+        }
 
-            // Perform main work
-            perform_main_work(cli_args, &program_data, &mut cache, cfg_path)?; // NOTE: study if we
-                                                                               // must provide a flag to continue working with other cfgs (if present) if the current
-                                                                               // fails or abort without continuing (current behaviour)
+        // The values of some variables depends on the configuration file itself
+        let variables = utils::reader::config_variables(&config, cli_args, abs_project_root)?;
+        let mut expanded_values = ExpandedValues::default();
+        let config = expansion::expand_config_file(config, &variables, &mut expanded_values)
+            .with_context(|| format!("{}: {:?}", error_messages::PARSE_CFG_FILE, cfg_path))?;
+        let mut config_env_vars = EnvVars::new();
+        expansion::read_environment(&resolved_file.raw, &mut config_env_vars);
+
+        // The members of a workspace are built along with it, so their changes are tracked too
+        let members_files = match &config.workspace {
+            Some(workspace) => {
+                find_workspace_members(cfg_path.parent().unwrap_or(abs_project_root), workspace)?
+            }
+            None => Vec::with_capacity(0),
+        };
+        let members_resolved_files = members_files
+            .iter()
+            .map(|member| inheritance::read_config_file(&member.config_file.path))
+            .collect::<Result<Vec<_>>>()?;
+        let members_sources = members_files
+            .iter()
+            .zip(members_resolved_files.iter())
+            .map(|(member, resolved_file)| {
+                SourceFiles::new(&member.config_file.path, resolved_file)
+            })
+            .collect::<Vec<_>>();
+        validation::report(
+            members_resolved_files
+                .iter()
+                .zip(members_sources.iter())
+                .flat_map(|(resolved_file, sources)| {
+                    validation::check_unknown_keys(&resolved_file.raw, sources)
+                })
+                .collect(),
+        )?;
+        // The variables of every member are the ones of its own configuration file
+        let mut members_expanded_values = members_files
+            .iter()
+            .map(|_| ExpandedValues::default())
+            .collect::<Vec<_>>();
+        let members = members_files
+            .iter()
+            .zip(members_resolved_files.iter())
+            .zip(members_expanded_values.iter_mut())
+            .map(|((member, resolved_file), expanded_values)| {
+                let err_msg = || {
+                    format!(
+                        "{}: {:?}",
+                        error_messages::PARSE_CFG_FILE,
+                        member.config_file.path
+                    )
+                };
+                let config =
+                    config_file::zork_cfg_from_file(&resolved_file.raw).with_context(err_msg)?;
+                let variables = utils::reader::config_variables(&config, cli_args, &member.root)?;
+                expansion::read_environment(&resolved_file.raw, &mut config_env_vars);
+
+                Ok(WorkspaceMember {
+                    name: member.name.clone(),
+                    root: member.root.clone(),
+                    config: expansion::expand_config_file(config, &variables, expanded_values)
+                        .with_context(err_msg)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Every problem found on the declarations of the workspace is reported at once, before
+        // building anything
+        let units = std::iter::once(ConfigFileUnit {
+            config: &config,
+            code_root: abs_project_root.join(config.project.code_root.unwrap_or_default()),
+            sources: &sources,
+        })
+        .chain(
+            members
+                .iter()
+                .zip(members_sources.iter())
+                .map(|(member, sources)| ConfigFileUnit {
+                    config: &member.config,
+                    code_root: member
+                        .root
+                        .join(member.config.project.code_root.unwrap_or_default()),
+                    sources,
+                }),
+        )
+        .collect::<Vec<_>>();
+        validation::report(validation::check_config_files(&units))?;
+
+        for (member, resolved_file) in members_files.iter().zip(&members_resolved_files) {
+            config_file.last_time_modified = config_file
+                .last_time_modified
+                .max(member.config_file.last_time_modified)
+                .max(resolved_file.last_time_modified().unwrap_or_default());
         }
 
-        Ok(())
+        create_output_directory(&config, &members, abs_project_root)?; // NOTE: review if we must
+                                                                       // rebuilt the cache and model if the
+                                                                       // output dir changes from
+                                                                       // previous
+
+        let mut cache: ZorkCache<'_> = cache::load(&config, cli_args, abs_project_root)?;
+
+        let program_data = {
+            // The purpose of this scope is to let the reader to see clearly
+            // that the model will only be mutated within the scope of this
+            // block, and after it, it will be read-only data
+            let mut program_data: ZorkModel<'_> = load_zork_model(
+                &mut cache,
+                &config_file,
+                config,
+                members,
+                config_env_vars,
+                cli_args,
+                abs_project_root,
+            )?;
+            map_model_targets_to_cache(&mut program_data, &mut cache, cli_args)?;
+
+            program_data
+        };
+
+        cache
+            .process_compiler_metadata(&program_data)
+            .with_context(|| error_messages::FAILURE_LOADING_COMPILER_METADATA)?;
+
+        // Perform main work
+        perform_main_work(cli_args, &program_data, &mut cache, cfg_path)
+    }
+
+    /// The contents of the toolchain file selected through the command line arguments
+    struct ToolchainFile {
+        raw: String,
+        last_time_modified: DateTime<Utc>,
+    }
+
+    /// Reads the toolchain file passed in with the `--toolchain` [`CliArgs`] argument, if any.
+    /// A relative path is relative to the root of the project
+    fn load_toolchain_file(
+        abs_project_root: &Path,
+        cli_args: &CliArgs,
+    ) -> Result<Option<ToolchainFile>> {
+        cli_args
+            .toolchain
+            .as_deref()
+            .map(|path| {
+                let path = abs_project_root.join(path);
+                let err_msg = || format!("{}: {:?}", error_messages::READ_TOOLCHAIN_FILE, path);
+                let raw = fs::read_to_string(&path).with_context(err_msg)?;
+                let last_time_modified = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .with_context(err_msg)?;
+
+                Ok(ToolchainFile {
+                    raw,
+                    last_time_modified: DateTime::<Utc>::from(last_time_modified),
+                })
+            })
+            .transpose()
     }
 
     /// Inspects the [`CliArgs`] main passed argument, and if it's [`Command::New`] just creates a
@@ -137,6 +359,29 @@ pub mod worker {
             generate_commands_ts.elapsed().as_millis()
         );
 
+        // A check doesn't run anything, nor saves the generated commands, so the next build
+        // starts from the same state
+        if cli_args.command.eq(&Command::Check) {
+            check_modular_stdlibs(cache)?;
+            log::info!(
+                "No problems found for: {:?}, with {} command lines ready to be executed",
+                cfg_path,
+                cache.get_all_commands_iter().count()
+            );
+            return Ok(());
+        }
+
+        // A dry run doesn't save the generated commands either, since none of them is executed
+        if cli_args.dry_run {
+            // NOTE: the sources created by the generators can't be scanned until they're executed
+            let reachability = ModulesReachability::new(program_data)?;
+            return executors::print_dry_run(
+                program_data,
+                &cache.generated_commands,
+                &reachability,
+            );
+        }
+
         let work_result = do_main_work_based_on_cli_input(cli_args, program_data, cache)
             .with_context(|| {
                 format!(
@@ -152,6 +397,26 @@ pub mod worker {
         work_result.with_context(|| format!("Failed to complete the job for: {:?}", cfg_path))
     }
 
+    /// The sources of the modular standard library are located through the metadata of the
+    /// compiler, so they can only be checked once it's gathered
+    fn check_modular_stdlibs(cache: &ZorkCache<'_>) -> Result<()> {
+        let modules = &cache.generated_commands.modules;
+        for stdlib in modules
+            .cpp_stdlib
+            .iter()
+            .chain(modules.c_compat_stdlib.iter())
+        {
+            let path = stdlib.path();
+            if !path.exists() {
+                return Err(eyre!(
+                    "The source of the modular standard library {path:?} doesn't exist"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     fn do_main_work_based_on_cli_input(
         cli_args: &CliArgs,
         program_data: &ZorkModel<'_>,
@@ -166,10 +431,27 @@ pub mod worker {
             .as_ref()
             .with_context(|| error_messages::FAILURE_LOADING_FLYWEIGHT_DATA)?;
 
+        let generators_generated_commands = &mut cache.generated_commands.generators;
+        let keep_going = cli_args.keep_going;
+
+        hooks::run_project_hooks(program_data, HookStage::PreBuild, &flyweight_data.env_vars)?;
+
+        // The generated sources must exist before looking for the modules imported by them
+        executors::run_generators(
+            program_data,
+            generators_generated_commands,
+            &flyweight_data.env_vars,
+            keep_going,
+        )?;
+
+        let reachability = ModulesReachability::new(program_data)?;
+
         executors::run_modules_generated_commands(
             program_data,
             flyweight_data,
             modules_generated_commands,
+            &reachability,
+            keep_going,
         )?;
 
         let target_executed_commands = executors::run_targets_generated_commands(
@@ -177,18 +459,37 @@ pub mod worker {
             flyweight_data,
             targets_generated_commands,
             modules_generated_commands,
-        );
+            &reachability,
+            keep_going,
+        )
+        .and_then(|_| {
+            // When the build keeps going after a failure, it's only reported at the end
+            if keep_going {
+                executors::check_build_failures(
+                    generators_generated_commands,
+                    modules_generated_commands,
+                    targets_generated_commands,
+                )
+            } else {
+                Ok(())
+            }
+        })
+        .and_then(|_| {
+            hooks::run_project_hooks(program_data, HookStage::PostBuild, &flyweight_data.env_vars)
+        });
 
         match cli_args.command {
             Command::Build => target_executed_commands,
             Command::Run | Command::Test => match target_executed_commands {
                 Ok(_) => {
                     for (target_identifier, target_data) in targets_generated_commands.iter() {
-                        if target_data.enabled_for_current_program_iteration {
+                        if target_data.enabled_for_current_program_iteration
+                            && target_data.kind.eq(&TargetKind::Executable)
+                        {
                             executors::autorun_generated_binary(
-                                &program_data.compiler.cpp_compiler,
+                                &program_data.compiler,
                                 &program_data.build.output_dir,
-                                target_identifier.name(),
+                                &target_identifier.as_path(),
                             )?
                         }
                     }
@@ -219,27 +520,65 @@ pub mod worker {
     /// Helper function to load the data of a concrete [`ZorkConfigFile`] into a [`ZorkModel`],
     /// which is the ultimate data structure that holds the read only information about the user
     /// input in a more concise way that the config file struct.
+    ///
+    /// The cached model is reused unless the configuration files, the build profile or the
+    /// values of the environment variables read by them changed since the last run
     fn load_zork_model<'a>(
         cache: &mut ZorkCache<'a>,
         meta_config_file: &ConfigFile,
         zork_config_file: ZorkConfigFile<'a>,
+        members: Vec<WorkspaceMember<'a>>,
+        config_env_vars: EnvVars,
         cli_args: &'a CliArgs,
         abs_project_root: &Path,
     ) -> Result<ZorkModel<'a>> {
-        if meta_config_file.last_time_modified > cache.metadata.last_program_execution {
+        let declared_launcher = zork_config_file.compiler.launcher;
+        let expanded_values_changed = cache.metadata.profile.ne(&cli_args.profile)
+            || cache.metadata.config_env_vars.ne(&config_env_vars);
+        cache.metadata.expanded_values_changed = expanded_values_changed;
+        cache.metadata.profile.clone_from(&cli_args.profile);
+        cache.metadata.config_env_vars = config_env_vars;
+
+        let mut project_model = if meta_config_file.last_time_modified
+            > cache.metadata.last_program_execution
+            || expanded_values_changed
+        {
             cache.metadata.cfg_modified = true;
-            let project_model =
-                utils::reader::build_model(zork_config_file, cli_args, abs_project_root)?;
+            let project_model = utils::reader::build_workspace_model(
+                zork_config_file,
+                members,
+                cli_args,
+                abs_project_root,
+            )?;
 
             // Check for the changes made by the user on the cfg
             check_for_deletions_in_cfg(&project_model, cache)
                 .with_context(|| error_messages::CHECK_FOR_DELETIONS)?;
 
-            Ok(project_model)
+            project_model
         } else {
             log::debug!("Loading the ZorkModel from the cache");
-            project_model::load(cache)
-        }
+            project_model::load(cache)?
+        };
+
+        apply_launcher(&mut project_model, cli_args, declared_launcher);
+        Ok(project_model)
+    }
+
+    /// Sets the compiler launcher of the current invocation, where the one selected on the command
+    /// line overrides the one declared on the configuration file. Since it may change between
+    /// invocations without any change on the configuration files, it's applied after loading the
+    /// model, because the one loaded from the cache holds the launcher of the last run
+    fn apply_launcher<'a>(
+        program_data: &mut ZorkModel<'a>,
+        cli_args: &'a CliArgs,
+        declared_launcher: Option<&'a str>,
+    ) {
+        program_data.compiler.launcher = cli_args
+            .launcher
+            .as_deref()
+            .or(declared_launcher)
+            .map(Cow::Borrowed);
     }
 
     /// Little helper to check if the user remove files from the [`ZorkConfigFile`] and therefore,
@@ -283,6 +622,7 @@ pub mod worker {
                 cli_args,
             )?;
         }
+        helpers::enable_dependencies_of_enabled_targets(program_data, cache);
 
         log::info!(
             "Target enabled for this iteration of Zork++: {:?}",
@@ -298,6 +638,13 @@ pub mod worker {
         // was tracked already)
         helpers::delete_from_cache_removed_targets_from_cfg_file(program_data, cache);
 
+        // 4th - The targets are processed in the order of the model, where every target is placed
+        // after the ones that it depends on
+        cache.generated_commands.targets.sort_by(|id1, _, id2, _| {
+            let position = |id| program_data.targets.get_index_of(id);
+            position(id1).cmp(&position(id2))
+        });
+
         Ok(())
     }
 
@@ -317,15 +664,14 @@ pub mod worker {
     /// - a /cache folder, where lives the metadata cached by Zork++
     ///     in order to track different aspects of the program (last time
     ///     modified files, last process build time...)
-    fn create_output_directory(config: &ZorkConfigFile, project_root: &Path) -> Result<()> {
+    fn create_output_directory(
+        config: &ZorkConfigFile,
+        members: &[WorkspaceMember],
+        project_root: &Path,
+    ) -> Result<()> {
         let compiler: CppCompiler = config.compiler.cpp_compiler.into();
         let compiler_name = compiler.as_ref();
-        let binding = config
-            .build
-            .as_ref()
-            .and_then(|build_attr| build_attr.output_dir)
-            .unwrap_or("out");
-        let out_dir = Path::new(project_root).join(binding);
+        let out_dir = utils::reader::resolve_output_dir(config, project_root);
 
         // Recursively create the directories below and all of its parent components if they are missing
         let modules_path = out_dir.join(compiler_name).join(dir_names::MODULES);
@@ -338,8 +684,43 @@ pub mod worker {
 
         utils::fs::create_directory(&modules_path.join(dir_names::INTERFACES))?;
         utils::fs::create_directory(&modules_path.join(dir_names::IMPLEMENTATIONS))?;
+        utils::fs::create_directory(&modules_path.join(dir_names::HEADER_UNITS))?;
         utils::fs::create_directory(&modules_path.join(dir_names::STD))?;
 
+        // The products of the workspace members are nested under a directory named after them
+        for member in members {
+            utils::fs::create_directory(&out_dir.join(compiler_name).join(&member.name))?;
+            utils::fs::create_directory(
+                &out_dir
+                    .join(compiler_name)
+                    .join(dir_names::OBJECT_FILES)
+                    .join(&member.name),
+            )?;
+        }
+
+        let members_targets = members.iter().flat_map(|member| {
+            member
+                .config
+                .targets
+                .iter()
+                .map(|(name, target)| (Path::new(&member.name).join(name), target))
+        });
+        for target_path in config
+            .targets
+            .iter()
+            .map(|(name, target)| (PathBuf::from(name), target))
+            .chain(members_targets)
+            .filter(|(_, target)| target.pch.is_some())
+            .map(|(path, _)| path)
+        {
+            utils::fs::create_directory(
+                &out_dir
+                    .join(compiler_name)
+                    .join(dir_names::PCH)
+                    .join(target_path),
+            )?;
+        }
+
         utils::fs::create_directory(&zork_cache_path)?;
         utils::fs::create_directory(&zork_intrinsics_path)?;
 
@@ -424,6 +805,31 @@ pub mod worker {
             Ok(())
         }
 
+        /// The targets that an enabled target depends on must be built as well. Since every
+        /// target is placed after its dependencies, they are visited in reverse order
+        pub(crate) fn enable_dependencies_of_enabled_targets<'a>(
+            program_data: &mut ZorkModel<'a>,
+            cache: &mut ZorkCache<'a>,
+        ) {
+            for idx in (0..program_data.targets.len()).rev() {
+                let target_data = &program_data.targets[idx];
+                if !target_data.enabled_for_current_program_iteration {
+                    continue;
+                }
+
+                for dependency in target_data.dependencies.clone() {
+                    if let Some(dependency_data) = program_data.targets.get_mut(&dependency) {
+                        dependency_data.enabled_for_current_program_iteration = true;
+                    }
+                    if let Some(cached_dependency) =
+                        cache.generated_commands.targets.get_mut(&dependency)
+                    {
+                        cached_dependency.enabled_for_current_program_iteration = true;
+                    }
+                }
+            }
+        }
+
         pub(crate) fn delete_from_cache_removed_targets_from_cfg_file(
             program_data: &ZorkModel,
             cache: &mut ZorkCache,
@@ -438,23 +844,487 @@ pub mod worker {
     #[cfg(test)]
     mod tests {
         use std::borrow::Cow;
+        use std::fs;
         use std::path::Path;
 
-        use crate::cache::{self, ZorkCache};
+        use crate::cache::{self, EnvVars, ZorkCache};
         use crate::cli::input::CliArgs;
+        use crate::cli::output::executors;
+        use crate::compiler;
+        use crate::domain::reachability::ModulesReachability;
         use crate::domain::target::TargetIdentifier;
+        use crate::domain::translation_unit::{BuildReason, TranslationUnitStatus};
+        use crate::project_model;
         use crate::project_model::compiler::CppCompiler;
         use crate::project_model::ZorkModel;
         use crate::utils;
+        use crate::utils::reader::ConfigFile;
         use crate::utils::template::resources::CONFIG_FILE;
+        use chrono::{DateTime, Utc};
         use clap::Parser;
         use color_eyre::Result;
-        use tempfile::tempdir;
+        use tempfile::{tempdir, TempDir};
 
         use crate::config_file::{self, ZorkConfigFile};
         use crate::utils::constants::{dir_names, ZORK};
 
-        use super::{helpers, map_model_targets_to_cache};
+        use super::{
+            apply_launcher, helpers, load_toolchain_file, load_zork_model,
+            map_model_targets_to_cache,
+        };
+
+        /// A project written on a temporary directory, whose model is built as in the invocations
+        /// with the given command line arguments
+        struct TestProject {
+            root: TempDir,
+            cli_args: CliArgs,
+        }
+
+        impl TestProject {
+            fn new(files: &[(&str, &str)], args: &[&str]) -> Result<Self> {
+                let root = tempdir()?;
+                for (file, content) in files {
+                    let path = root.path().join(file);
+                    fs::create_dir_all(path.parent().unwrap_or(root.path()))?;
+                    fs::write(path, content)?;
+                }
+                let cli_args = CliArgs::parse_from(std::iter::once("").chain(args.iter().copied()));
+
+                Ok(Self { root, cli_args })
+            }
+
+            /// Builds the model of the project declared by the given configuration, and maps its
+            /// targets into the cache, so the commands of the project are ready to be generated
+            fn model<'a>(
+                &'a self,
+                config: &'a str,
+                cache: &mut ZorkCache<'a>,
+            ) -> Result<ZorkModel<'a>> {
+                let zcf: ZorkConfigFile = config_file::zork_cfg_from_file(config)?;
+                let declared_launcher = zcf.compiler.launcher;
+                let mut model = utils::reader::build_model(zcf, &self.cli_args, self.root.path())?;
+
+                apply_launcher(&mut model, &self.cli_args, declared_launcher);
+                map_model_targets_to_cache(&mut model, cache, &self.cli_args)?;
+                Ok(model)
+            }
+
+            /// Generates the commands of the project declared by the given configuration, and
+            /// returns the lines printed by a dry run of them
+            fn dry_run<'a>(&'a self, config: &'a str) -> Result<Vec<String>> {
+                let mut cache = ZorkCache::default();
+                let model = self.model(config, &mut cache)?;
+                compiler::generate_commands_arguments(&model, &mut cache)?;
+                let reachability = ModulesReachability::new(&model)?;
+
+                executors::dry_run_commands(&model, &cache.generated_commands, &reachability)
+            }
+        }
+
+        /// The line of the dry run whose command line ends with the given input file
+        fn line_for<'l>(lines: &'l [String], input: &str) -> &'l str {
+            lines
+                .iter()
+                .find(|line| line.ends_with(input) || line.contains(&format!("{input} ")))
+                .unwrap_or_else(|| panic!("No command for {input:?} in {lines:#?}"))
+        }
+
+        const LAUNCHER_CONFIG: &str = r#"
+            [project]
+            name = "launched"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            launcher = "ccache"
+
+            [targets.executable]
+            sources = ["main.cpp"]
+        "#;
+
+        #[test]
+        fn test_launcher_isnt_persisted_with_the_cached_model() -> Result<()> {
+            let temp = tempdir()?;
+            let project_model_file_path = temp.path().join("pm.json");
+            let cache_for = |last_program_execution| {
+                let mut cache = ZorkCache::default();
+                cache.metadata.project_model_file_path = project_model_file_path.clone();
+                cache.metadata.last_program_execution = last_program_execution;
+                cache.metadata.profile = String::from("debug");
+                cache
+            };
+            let config_file = |last_time_modified| ConfigFile {
+                path: temp.path().join("zork.toml"),
+                last_time_modified,
+            };
+            let cfg = || config_file::zork_cfg_from_file(LAUNCHER_CONFIG);
+
+            // The model is built on the first run, with the launcher selected on the command line
+            let first_run = Utc::now();
+            let cli_args = CliArgs::parse_from(["", "--launcher", "sccache", "build"]);
+            let mut cache = cache_for(DateTime::default());
+            let model = load_zork_model(
+                &mut cache,
+                &config_file(first_run),
+                cfg()?,
+                Vec::new(),
+                EnvVars::new(),
+                &cli_args,
+                temp.path(),
+            )?;
+            assert_eq!(model.compiler.launcher.as_deref(), Some("sccache"));
+            project_model::save(&model, &cache)?;
+
+            // And the next runs load it from the cache, since the configuration file didn't change
+            let runs = [
+                (vec!["", "build"], Some("ccache")),
+                (vec!["", "--launcher", "distcc", "build"], Some("distcc")),
+            ];
+            for (args, expected_launcher) in runs {
+                let cli_args = CliArgs::parse_from(args);
+                let mut cache = cache_for(Utc::now());
+                let model = load_zork_model(
+                    &mut cache,
+                    &config_file(first_run),
+                    cfg()?,
+                    Vec::new(),
+                    EnvVars::new(),
+                    &cli_args,
+                    temp.path(),
+                )?;
+                assert!(!cache.metadata.cfg_modified);
+                assert_eq!(model.compiler.launcher.as_deref(), expected_launcher);
+            }
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_dry_run_prints_the_reason_of_every_command() -> Result<()> {
+            let project =
+                TestProject::new(&[("main.cpp", "int main() {}\n")], &["--dry-run", "build"])?;
+            let lines = project.dry_run(LAUNCHER_CONFIG)?;
+
+            // The sources are built through the launcher for the first time, and the linker runs
+            // because of them
+            assert_eq!(lines.len(), 2, "{lines:#?}");
+            assert!(line_for(&lines, "main.cpp").starts_with("[new] ccache g++ "));
+            assert!(lines[1].starts_with("[dependency rebuilt] g++ "));
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_unreachable_modules_are_built_but_not_linked() -> Result<()> {
+            let project = TestProject::new(
+                &[
+                    ("main.cpp", "import math;\nint main() {}\n"),
+                    ("math.cppm", "export module math;\n"),
+                    ("unused.cppm", "export module unused;\n"),
+                ],
+                &["--dry-run", "build"],
+            )?;
+            let lines = project.dry_run(
+                r#"
+                [project]
+                name = "unreachable"
+
+                [compiler]
+                cpp_compiler = "gcc"
+                cpp_standard = "20"
+
+                [targets.executable]
+                sources = ["main.cpp"]
+
+                [modules]
+                interfaces = [{ file = "math.cppm" }, { file = "unused.cppm" }]
+                "#,
+            )?;
+
+            assert!(line_for(&lines, "math.cppm").starts_with("[new] g++ "));
+            assert!(line_for(&lines, "unused.cppm").starts_with("[new] g++ "));
+            let link = lines.last().unwrap();
+            assert!(link.contains("math.o"), "{link}");
+            assert!(!link.contains("unused.o"), "{link}");
+
+            Ok(())
+        }
+
+        fn per_file_args_config(legacy_args: &str) -> String {
+            format!(
+                r#"
+                [project]
+                name = "per_file"
+
+                [compiler]
+                cpp_compiler = "gcc"
+                cpp_standard = "20"
+
+                [targets.executable]
+                sources = [
+                    "*.cpp",
+                    {{ file = "legacy.cpp", extra_args = [{legacy_args}] }},
+                    {{ file = "broken.cpp", exclude_from_build = true }},
+                ]
+                compile_args = ["-DTARGET"]
+
+                [modules]
+                interfaces = [{{ file = "math.cppm", extra_args = ["-DINTERFACE"] }}]
+                "#
+            )
+        }
+        const PER_FILE_ARGS_FILES: &[(&str, &str)] = &[
+            ("main.cpp", "import math;\nint main() {}\n"),
+            ("legacy.cpp", "void legacy() {}\n"),
+            ("broken.cpp", "void broken() {\n"),
+            ("math.cppm", "export module math;\n"),
+        ];
+
+        #[test]
+        fn test_per_file_args_are_only_sent_to_their_file() -> Result<()> {
+            let project = TestProject::new(PER_FILE_ARGS_FILES, &["build"])?;
+            let config = per_file_args_config("'-w'");
+            let mut cache = ZorkCache::default();
+            let model = project.model(&config, &mut cache)?;
+            compiler::generate_commands_arguments(&model, &mut cache)?;
+
+            let commands = &cache.generated_commands;
+            let sources = &commands.targets[&TargetIdentifier::from("executable")].sources;
+            let args_of = |filename: &str| {
+                sources
+                    .iter()
+                    .chain(commands.modules.interfaces.iter())
+                    .find(|scl| scl.filename.eq(filename))
+                    .map(|scl| scl.args.iter().map(|arg| arg.value().as_ref()).collect())
+                    .unwrap_or_else(|| panic!("No command for {filename}"))
+            };
+
+            // Appended after the input file, so they override the ones of the project and target
+            let legacy: Vec<&str> = args_of("legacy.cpp");
+            assert!(legacy.ends_with(&["-w"]));
+            let main: Vec<&str> = args_of("main.cpp");
+            assert!(!main.contains(&"-w"));
+            let interface: Vec<&str> = args_of("math.cppm");
+            assert!(interface.ends_with(&["-DINTERFACE"]));
+            let with_interface_args = sources
+                .iter()
+                .chain(commands.modules.interfaces.iter())
+                .filter(|scl| scl.args.iter().any(|arg| arg.value().eq("-DINTERFACE")));
+            assert_eq!(with_interface_args.count(), 1);
+
+            // The excluded ones are neither built nor linked
+            assert!(sources.iter().all(|scl| !scl.filename.contains("broken")));
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_per_file_args_changes_invalidate_their_cached_command() -> Result<()> {
+            let project = TestProject::new(PER_FILE_ARGS_FILES, &["build"])?;
+            let (config, changed_config) =
+                (per_file_args_config("'-w'"), per_file_args_config("'-O3'"));
+            let mut cache = ZorkCache::default();
+
+            // The first run builds everything
+            let model = project.model(&config, &mut cache)?;
+            compiler::generate_commands_arguments(&model, &mut cache)?;
+            let target = TargetIdentifier::from("executable");
+            let sources = |cache: &ZorkCache| {
+                cache.generated_commands.targets[&target]
+                    .sources
+                    .iter()
+                    .map(|scl| (scl.filename.clone(), scl.status))
+                    .collect::<Vec<_>>()
+            };
+            assert!(sources(&cache)
+                .iter()
+                .all(|(_, status)| status.eq(&TranslationUnitStatus::PendingToBuild)));
+            cache
+                .generated_commands
+                .targets
+                .values_mut()
+                .flat_map(|target| target.sources.iter_mut())
+                .chain(cache.generated_commands.modules.interfaces.iter_mut())
+                .for_each(|scl| scl.status = TranslationUnitStatus::Success);
+
+            // And the next one, after changing the per-file args of a source, only rebuilds it
+            cache.metadata.last_program_execution = Utc::now();
+            cache.metadata.cfg_modified = true;
+            let changed_model = project.model(&changed_config, &mut cache)?;
+            compiler::generate_commands_arguments(&changed_model, &mut cache)?;
+
+            for (filename, status) in sources(&cache) {
+                if filename.eq("legacy.cpp") {
+                    assert_eq!(status, TranslationUnitStatus::PendingToBuild);
+                } else {
+                    assert_eq!(status, TranslationUnitStatus::Cached, "{filename}");
+                }
+            }
+            let legacy = cache.generated_commands.targets[&target]
+                .sources
+                .iter()
+                .find(|scl| scl.filename.eq("legacy.cpp"))
+                .unwrap();
+            assert!(legacy.args.iter().any(|arg| arg.value().eq("-O3")));
+            assert!(!legacy.args.iter().any(|arg| arg.value().eq("-w")));
+            assert_eq!(
+                cache.generated_commands.modules.interfaces[0].status,
+                TranslationUnitStatus::Cached
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_msvc_sys_modules_as_header_units() -> Result<()> {
+            let config = r#"
+                [project]
+                name = "sys_modules"
+
+                [compiler]
+                cpp_compiler = "msvc"
+                cpp_standard = "latest"
+
+                [targets.executable]
+                sources = ["main.cpp"]
+
+                [modules]
+                sys_modules = ["iostream"]
+            "#;
+            let files = [("main.cpp", "import <iostream>;\nint main() {}\n")];
+            let project = TestProject::new(&files, &["build"])?;
+            let mut cache = ZorkCache::default();
+            let model = project.model(config, &mut cache)?;
+            compiler::generate_commands_arguments(&model, &mut cache)?;
+
+            let commands = &cache.generated_commands;
+            let ifc = project
+                .root
+                .path()
+                .join("out/msvc/modules/header_units/iostream.ifc");
+            let sys_module = commands.modules.system_modules[0].args.to_string();
+            assert_eq!(
+                sys_module,
+                format!(
+                    "/exportHeader /headerName:angle iostream /ifcOutput {} /Fo{}",
+                    ifc.display(),
+                    ifc.with_extension("obj").display()
+                )
+            );
+
+            // Its importers reference the generated ifc
+            let flyweight_data = commands.flyweight_data.as_ref().unwrap();
+            assert_eq!(
+                flyweight_data.header_units_refs.to_string(),
+                format!("/headerUnit:angle iostream={}", ifc.display())
+            );
+
+            Ok(())
+        }
+
+        const CLANG_CL_CONFIG: &str = r#"
+            [project]
+            name = "clang_cl_proj"
+
+            [compiler]
+            cpp_compiler = "clang-cl"
+            cpp_standard = "20"
+            compile_args = ["-Wall"]
+
+            [targets.executable]
+            output_name = "app"
+            sources = ["main.cpp"]
+            pch = "pch.hpp"
+
+            [modules]
+            interfaces = [{ file = "math.cppm" }]
+            implementations = [{ file = "math.cpp", dependencies = ["math"] }]
+            sys_modules = ["iostream"]
+            header_units = ["utils.hpp"]
+        "#;
+        const CLANG_CL_FILES: &[(&str, &str)] = &[
+            (
+                "main.cpp",
+                "import math;\nint main() { return add(1, 2); }\n",
+            ),
+            ("pch.hpp", "#pragma once\n"),
+            ("utils.hpp", "#pragma once\n"),
+            (
+                "math.cppm",
+                "export module math;\nexport int add(int a, int b);\n",
+            ),
+            (
+                "math.cpp",
+                "module math;\nint add(int a, int b) { return a + b; }\n",
+            ),
+        ];
+
+        #[test]
+        fn test_clang_cl_modules_commands() -> Result<()> {
+            let lines = TestProject::new(CLANG_CL_FILES, &["--dry-run", "build"])?
+                .dry_run(CLANG_CL_CONFIG)?;
+
+            let interface = line_for(&lines, "math.cppm");
+            assert!(interface.starts_with("[new] clang-cl /std:c++20"));
+            assert!(interface.contains(" /c "));
+            assert!(interface.contains(" /TP "));
+            assert!(interface.contains("/clang:-fmodule-output="));
+            assert!(interface.contains("modules/interfaces/math.pcm"));
+            assert!(interface.contains("modules/interfaces/math.obj"));
+
+            let implementation = line_for(&lines, "math.cpp");
+            assert!(implementation.starts_with("[new] clang-cl"));
+            assert!(implementation.contains("/clang:-fmodule-file=math="));
+            assert!(implementation.contains("obj_files/math.obj"));
+
+            // The interface is built before the implementation that depends on it
+            let position = |line: &str| lines.iter().position(|l| l == line);
+            assert!(position(interface) < position(implementation));
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_clang_cl_sources_and_link_commands() -> Result<()> {
+            let lines = TestProject::new(CLANG_CL_FILES, &["--dry-run", "build"])?
+                .dry_run(CLANG_CL_CONFIG)?;
+
+            let source = line_for(&lines, "main.cpp");
+            assert!(source.starts_with("[new] clang-cl /std:c++20 /EHsc /nologo"));
+            assert!(source.contains(" /c -Wall "));
+            assert!(source.contains("obj_files/main.obj"));
+
+            let link = lines.last().unwrap();
+            assert!(link.starts_with("[dependency rebuilt] clang-cl"));
+            assert!(link.contains("/Fe"));
+            assert!(!link.contains(" /c "));
+            for obj in [
+                "modules/interfaces/math.obj",
+                "obj_files/math.obj",
+                "obj_files/main.obj",
+            ] {
+                assert!(link.contains(obj), "{obj} isn't linked: {link}");
+            }
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_clang_cl_unsupported_features_are_skipped() -> Result<()> {
+            // The system modules, header units and precompiled headers aren't yet supported
+            // for clang-cl, so they must be skipped instead of reaching their generators
+            let lines = TestProject::new(CLANG_CL_FILES, &["--dry-run", "build"])?
+                .dry_run(CLANG_CL_CONFIG)?;
+
+            assert_eq!(lines.len(), 4);
+            assert!(lines.iter().all(|line| !line.contains("iostream")
+                && !line.contains("utils.hpp")
+                && !line.contains("pch.hpp")
+                && !line.contains("/Yc")
+                && !line.contains("/Yu")));
+
+            Ok(())
+        }
 
         #[test]
         fn test_creation_directories() -> Result<()> {
@@ -475,7 +1345,7 @@ pub mod worker {
             let modules_path = compiler_folder_dir.join("modules");
 
             // This should create and out/ directory at the root of the tmp path
-            super::create_output_directory(&zcf, temp_path)?;
+            super::create_output_directory(&zcf, &[], temp_path)?;
 
             assert!(out_dir.exists());
 
@@ -601,5 +1471,167 @@ pub mod worker {
 
             Ok(())
         }
+
+        #[test]
+        fn test_dry_run_lists_the_hooks_in_execution_order() -> Result<()> {
+            let config = r#"
+                [project]
+                name = "hooks"
+                pre_build = ["echo project_pre ${project_name}"]
+                post_build = ["echo project_post"]
+
+                [compiler]
+                cpp_compiler = "gcc"
+                cpp_standard = "20"
+
+                [targets.executable]
+                sources = ["main.cpp"]
+                pre_build = ["echo target_pre ${target_name}"]
+                post_build = ["echo target_post"]
+            "#;
+            let project = TestProject::new(&[("main.cpp", "int main() {}\n")], &["build"])?;
+            let lines = project.dry_run(config)?;
+
+            // The hooks of the target wrap its sources and its final product, while the ones
+            // of the project wrap the whole build
+            let expected = [
+                "[always executed] echo project_pre hooks",
+                "[always executed] echo target_pre executable",
+                "main.cpp",
+                "out/gcc/executable ",
+                "[always executed] echo target_post",
+                "[always executed] echo project_post",
+            ];
+            assert_eq!(lines.len(), expected.len(), "{lines:#?}");
+            for (line, input) in lines.iter().zip(expected) {
+                assert!(line.contains(input), "{input} not found on: {line}");
+            }
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_sys_modules_and_header_units_are_cached() -> Result<()> {
+            let config = r#"
+                [project]
+                name = "sys_modules"
+
+                [compiler]
+                cpp_compiler = "gcc"
+                cpp_standard = "20"
+
+                [targets.executable]
+                sources = ["main.cpp"]
+
+                [modules]
+                sys_modules = ["iostream"]
+                header_units = ["utils.hpp"]
+            "#;
+            let files = [
+                ("main.cpp", "import <iostream>;\nint main() {}\n"),
+                ("utils.hpp", "#pragma once\n"),
+            ];
+            let project = TestProject::new(&files, &["build"])?;
+            let mut cache = ZorkCache::default();
+            let header_units = |cache: &ZorkCache| {
+                let modules = &cache.generated_commands.modules;
+                modules
+                    .system_modules
+                    .iter()
+                    .chain(modules.header_units.iter())
+                    .map(|scl| (scl.filename.clone(), scl.status, scl.build_reason))
+                    .collect::<Vec<_>>()
+            };
+
+            // The first run builds them
+            let model = project.model(config, &mut cache)?;
+            compiler::generate_commands_arguments(&model, &mut cache)?;
+            let pending = TranslationUnitStatus::PendingToBuild;
+            assert_eq!(
+                header_units(&cache),
+                [
+                    (String::from("iostream"), pending, Some(BuildReason::New)),
+                    (String::from("utils.hpp"), pending, Some(BuildReason::New))
+                ]
+            );
+            let modules = &mut cache.generated_commands.modules;
+            modules
+                .system_modules
+                .iter_mut()
+                .chain(modules.header_units.iter_mut())
+                .for_each(|scl| scl.status = TranslationUnitStatus::Success);
+
+            // And the next one reuses them, without tracking them twice
+            cache.metadata.last_program_execution = Utc::now();
+            let next_model = project.model(config, &mut cache)?;
+            compiler::generate_commands_arguments(&next_model, &mut cache)?;
+            let cached = TranslationUnitStatus::Cached;
+            assert_eq!(
+                header_units(&cache),
+                [
+                    (String::from("iostream"), cached, None),
+                    (String::from("utils.hpp"), cached, None)
+                ]
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_static_libs_are_only_archived_with_a_declared_archiver() -> Result<()> {
+            let config = |toolchain: &str| {
+                format!(
+                    r#"
+                    [project]
+                    name = "libs"
+
+                    [compiler]
+                    cpp_compiler = "gcc"
+                    cpp_standard = "20"
+                    {toolchain}
+
+                    [targets.utils]
+                    sources = ["utils.cpp"]
+                    kind = "StaticLib"
+                    "#
+                )
+            };
+            let project = TestProject::new(&[("utils.cpp", "void utils() {}\n")], &["build"])?;
+
+            // Without an archiver, the static libraries are generated through the driver
+            let lines = project.dry_run(&config(""))?;
+            let output = project.root.path().join("out/gcc/utils.a");
+            let link = lines.last().unwrap();
+            assert!(link.starts_with("[dependency rebuilt] g++ "), "{link}");
+            assert!(
+                link.contains(&format!(" -o {} ", output.display())),
+                "{link}"
+            );
+
+            // And the archiver bundles them when the toolchain declares one
+            let with_archiver = config("[toolchain]\narchiver = \"llvm-ar\"");
+            let lines = project.dry_run(&with_archiver)?;
+            assert!(lines.last().unwrap().starts_with(&format!(
+                "[dependency rebuilt] llvm-ar rcs {}",
+                output.display()
+            )));
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_toolchain_file_is_relative_to_the_project_root() -> Result<()> {
+            let temp = tempdir()?;
+            let toolchain = "[toolchain]\ntarget = \"aarch64-linux-gnu\"\n";
+            fs::write(temp.path().join("cross.toml"), toolchain)?;
+            let cli_args = CliArgs::parse_from(["", "--toolchain", "cross.toml", "build"]);
+
+            let loaded = load_toolchain_file(temp.path(), &cli_args)?;
+            assert_eq!(loaded.map(|file| file.raw).as_deref(), Some(toolchain));
+            // Not against the working directory
+            assert!(load_toolchain_file(Path::new("."), &cli_args).is_err());
+
+            Ok(())
+        }
     }
 }
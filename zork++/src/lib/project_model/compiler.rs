@@ -1,5 +1,8 @@
 use core::fmt;
-use std::{borrow::Cow, path::Path};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
 
 use crate::domain::commands::arguments::Argument;
 use serde::{Deserialize, Serialize};
@@ -13,14 +16,19 @@ pub struct CompilerModel<'a> {
     pub cpp_standard: LanguageLevel,
     pub std_lib: Option<StdLib>,
     pub std_lib_installed_dir: Option<Cow<'a, Path>>,
-    pub extra_args: Vec<Argument<'a>>,
+    pub compile_args: Vec<Argument<'a>>,
+    pub link_args: Vec<Argument<'a>>,
+    pub extra_args: Vec<Argument<'a>>, // deprecated, passed to the compile and the link command lines
+    pub launcher: Option<Cow<'a, str>>,
+    pub toolchain: ToolchainModel<'a>,
+    pub options: CompileOptions<'a>,
 }
 
 impl<'a> CompilerModel<'a> {
     pub fn language_level(&self) -> Cow<'static, str> {
         match self.cpp_compiler {
             CppCompiler::CLANG | CppCompiler::GCC => format!("-std=c++{}", self.cpp_standard),
-            CppCompiler::MSVC => format!("/std:c++{}", self.cpp_standard),
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => format!("/std:c++{}", self.cpp_standard),
         }
         .into()
     }
@@ -34,6 +42,80 @@ impl<'a> CompilerModel<'a> {
             .as_ref()
             .map(|lib| Argument::from(format!("-stdlib={lib}")))
     }
+
+    /// Returns the invokable name of the tool that bundles the object files of the static
+    /// libraries, when the toolchain declares one. Otherwise, the static libraries are generated
+    /// through the driver of the compiler
+    pub fn archiver(&self) -> Option<&str> {
+        self.toolchain.archiver.as_deref()
+    }
+
+    pub fn static_lib_extension(&self) -> &str {
+        match self.cpp_compiler {
+            CppCompiler::CLANG | CppCompiler::GCC => "a",
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => "lib",
+        }
+    }
+}
+
+/// The structured compile options declared by the user, which are translated into the
+/// concrete syntax of every compiler (see: [`crate::domain::commands::arguments::compile_options_args`])
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone)]
+pub struct CompileOptions<'a> {
+    pub defines: Vec<Cow<'a, str>>,
+    pub include_dirs: Vec<PathBuf>,
+    pub system_include_dirs: Vec<PathBuf>,
+    pub warnings: Option<WarningsLevel>,
+    pub optimization: Option<OptimizationLevel>,
+    pub debug_info: Option<bool>,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum WarningsLevel {
+    ALL,
+    EXTRA,
+    ERROR,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum OptimizationLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+    OS,
+}
+
+/// Holds the details about the platform targeted by the build process, and the tools
+/// used other than the compiler's driver
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ToolchainModel<'a> {
+    pub target: Option<Cow<'a, str>>,
+    pub sysroot: Option<Cow<'a, Path>>,
+    pub linker: Option<Cow<'a, str>>,
+    pub archiver: Option<Cow<'a, str>>,
+    pub target_os: Option<Cow<'a, str>>,
+}
+
+impl<'a> ToolchainModel<'a> {
+    /// Whenever the generated binaries are meant to run on *Windows*. If the user didn't
+    /// specify the target operating system, it's deduced from the target triple, and the
+    /// host operating system is assumed if there's no target triple neither
+    pub fn targets_windows(&self) -> bool {
+        match (&self.target_os, &self.target) {
+            (Some(target_os), _) => target_os.eq_ignore_ascii_case("windows"),
+            (None, Some(triple)) => triple.contains("windows") || triple.contains("mingw"),
+            (None, None) => cfg!(target_os = "windows"),
+        }
+    }
+
+    pub fn binary_extension(&self) -> &str {
+        if self.targets_windows() {
+            "exe"
+        } else {
+            ""
+        }
+    }
 }
 
 impl<'a> ExtraArgs<'a> for CompilerModel<'a> {
@@ -48,6 +130,8 @@ pub enum CppCompiler {
     CLANG,
     MSVC,
     GCC,
+    #[allow(non_camel_case_types)]
+    CLANG_CL,
 }
 
 impl fmt::Display for CppCompiler {
@@ -62,6 +146,7 @@ impl AsRef<str> for CppCompiler {
             CppCompiler::CLANG => "clang",
             CppCompiler::MSVC => "msvc",
             CppCompiler::GCC => "gcc",
+            CppCompiler::CLANG_CL => "clang-cl",
         }
     }
 }
@@ -77,20 +162,21 @@ impl CppCompiler {
                 CppCompiler::CLANG => "clang++",
                 CppCompiler::MSVC => "cl",
                 CppCompiler::GCC => "g++",
+                CppCompiler::CLANG_CL => "clang-cl",
             })
         }
     }
 
     pub fn default_module_extension<'a>(&self) -> Cow<'a, str> {
         Cow::Borrowed(match *self {
-            CppCompiler::CLANG => "cppm",
+            CppCompiler::CLANG | CppCompiler::CLANG_CL => "cppm",
             CppCompiler::MSVC => "ixx",
             CppCompiler::GCC => "cc",
         })
     }
     pub fn get_default_module_extension<'a>(&self) -> Cow<'a, str> {
         Cow::Borrowed(match *self {
-            CppCompiler::CLANG => "cppm",
+            CppCompiler::CLANG | CppCompiler::CLANG_CL => "cppm",
             CppCompiler::MSVC => "ixx",
             CppCompiler::GCC => "cc",
         })
@@ -98,7 +184,7 @@ impl CppCompiler {
 
     pub fn typical_bmi_extension(&self) -> Cow<'_, str> {
         Cow::Borrowed(match *self {
-            CppCompiler::CLANG => "pcm",
+            CppCompiler::CLANG | CppCompiler::CLANG_CL => "pcm",
             CppCompiler::MSVC => "ifc",
             CppCompiler::GCC => "o",
         })
@@ -106,7 +192,7 @@ impl CppCompiler {
 
     pub fn get_typical_bmi_extension(&self) -> &str {
         match *self {
-            CppCompiler::CLANG => "pcm",
+            CppCompiler::CLANG | CppCompiler::CLANG_CL => "pcm",
             CppCompiler::MSVC => "ifc",
             CppCompiler::GCC => "o",
         }
@@ -116,7 +202,7 @@ impl CppCompiler {
     pub fn obj_file_extension(&self) -> Cow<'_, str> {
         Cow::Borrowed(match *self {
             CppCompiler::CLANG | CppCompiler::GCC => "o",
-            CppCompiler::MSVC => "obj",
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => "obj",
         })
     }
 
@@ -124,7 +210,7 @@ impl CppCompiler {
     pub fn get_obj_file_extension(&self) -> &str {
         match *self {
             CppCompiler::CLANG | CppCompiler::GCC => "o",
-            CppCompiler::MSVC => "obj",
+            CppCompiler::MSVC | CppCompiler::CLANG_CL => "obj",
         }
     }
 }
@@ -208,3 +294,63 @@ impl fmt::Display for StdLibMode {
         write!(f, "{}", self.printable_info())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toolchain<'a>(target: Option<&'a str>, target_os: Option<&'a str>) -> ToolchainModel<'a> {
+        ToolchainModel {
+            target: target.map(Cow::Borrowed),
+            target_os: target_os.map(Cow::Borrowed),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_binary_extension_of_cross_targets() {
+        let windows_triples = ["x86_64-pc-windows-msvc", "x86_64-w64-mingw32"];
+        for triple in windows_triples {
+            let toolchain = toolchain(Some(triple), None);
+            assert!(toolchain.targets_windows());
+            assert_eq!(toolchain.binary_extension(), "exe");
+        }
+
+        let linux = toolchain(Some("aarch64-linux-gnu"), None);
+        assert!(!linux.targets_windows());
+        assert_eq!(linux.binary_extension(), "");
+
+        // The declared target operating system takes precedence over the triple
+        let overridden = toolchain(Some("aarch64-linux-gnu"), Some("Windows"));
+        assert!(overridden.targets_windows());
+        let overridden = toolchain(Some("x86_64-pc-windows-msvc"), Some("linux"));
+        assert_eq!(overridden.binary_extension(), "");
+
+        // And the host is assumed when there's nothing declared
+        let host = toolchain(None, None);
+        assert_eq!(host.targets_windows(), cfg!(target_os = "windows"));
+    }
+
+    #[test]
+    fn test_archiver_selection() {
+        let expected = [
+            (CppCompiler::CLANG, "a"),
+            (CppCompiler::GCC, "a"),
+            (CppCompiler::MSVC, "lib"),
+            (CppCompiler::CLANG_CL, "lib"),
+        ];
+
+        for (cpp_compiler, extension) in expected {
+            let mut compiler = CompilerModel {
+                cpp_compiler,
+                ..Default::default()
+            };
+            // There's no archiver unless the toolchain declares one
+            assert_eq!(compiler.archiver(), None);
+            assert_eq!(compiler.static_lib_extension(), extension);
+
+            compiler.toolchain.archiver = Some(Cow::Borrowed("llvm-ar"));
+            assert_eq!(compiler.archiver(), Some("llvm-ar"));
+        }
+    }
+}
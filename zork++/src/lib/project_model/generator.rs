@@ -0,0 +1,16 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::target::TargetIdentifier;
+
+/// A rule that generates files of the project, with its command already expanded and
+/// its inputs and outputs resolved against the code root
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone)]
+pub struct GeneratorModel<'a> {
+    pub command: Cow<'a, str>,
+    pub inputs: Vec<PathBuf>,
+    pub outputs: Vec<PathBuf>,
+    pub target: Option<TargetIdentifier<'a>>,
+}
@@ -1,5 +1,6 @@
 pub mod build;
 pub mod compiler;
+pub mod generator;
 pub mod modules;
 pub mod project;
 pub mod sourceset;
@@ -17,8 +18,8 @@ use crate::{cache::ZorkCache, domain::target::TargetIdentifier};
 use crate::utils;
 
 use self::{
-    build::BuildModel, compiler::CompilerModel, modules::ModulesModel, project::ProjectModel,
-    target::TargetModel,
+    build::BuildModel, compiler::CompilerModel, generator::GeneratorModel, modules::ModulesModel,
+    project::ProjectModel, target::TargetModel,
 };
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -28,6 +29,7 @@ pub struct ZorkModel<'a> {
     pub build: BuildModel,
     pub modules: ModulesModel<'a>,
     pub targets: IndexMap<TargetIdentifier<'a>, TargetModel<'a>>,
+    pub generators: Vec<GeneratorModel<'a>>,
 }
 
 /// Loads the mapped [`ZorkModel`] for a concrete [`ZorkConfigFile`] from the [`ZorkCache`]
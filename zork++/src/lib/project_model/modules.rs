@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use transient::Transient;
 
 use crate::config_file::modules::ModulePartition;
+use crate::domain::commands::arguments::Argument;
 use crate::domain::translation_unit::TranslationUnit;
 use crate::impl_translation_unit_for;
 
@@ -16,6 +17,7 @@ pub struct ModulesModel<'a> {
     pub base_impls_dir: Cow<'a, Path>,
     pub implementations: Vec<ModuleImplementationModel<'a>>,
     pub sys_modules: Vec<SystemModule<'a>>,
+    pub header_units: Vec<HeaderUnitModel<'a>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Transient, Serialize, Deserialize, Default)]
@@ -26,10 +28,26 @@ pub struct ModuleInterfaceModel<'a> {
     pub module_name: Cow<'a, str>,
     pub partition: Option<ModulePartitionModel<'a>>,
     pub dependencies: Vec<Cow<'a, str>>,
+    pub extra_args: Vec<Argument<'a>>,
+    pub owner: Option<Cow<'a, str>>, // the target that owns the module unit, if any
 }
 
 impl_translation_unit_for!(ModuleInterfaceModel<'a>);
 
+impl<'a> ModuleInterfaceModel<'a> {
+    /// The name that the importers of this interface must use, being the fully qualified
+    /// `module:partition` one for the partitions
+    pub fn provided_module_name(&self) -> String {
+        match &self.partition {
+            Some(partition) if !partition.partition_name.is_empty() => {
+                format!("{}:{}", partition.module, partition.partition_name)
+            }
+            Some(partition) => format!("{}:{}", partition.module, self.file_stem),
+            None => self.module_name.to_string(),
+        }
+    }
+}
+
 impl<'a> fmt::Display for ModuleInterfaceModel<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -66,6 +84,8 @@ pub struct ModuleImplementationModel<'a> {
     pub file_stem: Cow<'a, str>,
     pub extension: Cow<'a, str>,
     pub dependencies: Vec<Cow<'a, str>>,
+    pub extra_args: Vec<Argument<'a>>,
+    pub owner: Option<Cow<'a, str>>, // the target that owns the module unit, if any
 }
 
 impl_translation_unit_for!(ModuleImplementationModel<'a>);
@@ -93,3 +113,20 @@ impl<'a> fmt::Display for SystemModule<'a> {
         write!(f, "{:?}", self.path())
     }
 }
+
+/// Holds the fs information about a header of the user's project that will be built as
+/// a header unit, so the other translation units can import it (`import "foo.h";`)
+#[derive(Debug, PartialEq, Eq, Transient, Serialize, Deserialize, Default)]
+pub struct HeaderUnitModel<'a> {
+    pub path: PathBuf,
+    pub file_stem: Cow<'a, str>,
+    pub extension: Cow<'a, str>,
+}
+
+impl_translation_unit_for!(HeaderUnitModel<'a>);
+
+impl<'a> fmt::Display for HeaderUnitModel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.path())
+    }
+}
@@ -8,4 +8,25 @@ pub struct ProjectModel<'a> {
     pub authors: Vec<Cow<'a, str>>,
     pub compilation_db: bool,
     pub code_root: Option<Cow<'a, str>>,
+    pub hooks: BuildHooks<'a>,
+}
+
+/// The custom commands that the user declared to be executed around the build process
+/// of the whole project or of a concrete target
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone)]
+pub struct BuildHooks<'a> {
+    pub pre_build: Vec<Cow<'a, str>>,
+    pub post_build: Vec<Cow<'a, str>>,
+}
+
+impl<'a> BuildHooks<'a> {
+    pub fn new(
+        pre_build: Option<Vec<Cow<'a, str>>>,
+        post_build: Option<Vec<Cow<'a, str>>>,
+    ) -> Self {
+        Self {
+            pre_build: pre_build.unwrap_or_default(),
+            post_build: post_build.unwrap_or_default(),
+        }
+    }
 }
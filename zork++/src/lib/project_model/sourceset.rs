@@ -1,6 +1,6 @@
 use core::fmt;
 use std::borrow::Cow;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::domain::commands::arguments::Argument;
 use color_eyre::{eyre::Context, Result};
@@ -15,6 +15,8 @@ pub struct SourceFile<'a> {
     pub path: PathBuf,
     pub file_stem: Cow<'a, str>,
     pub extension: Cow<'a, str>,
+    #[serde(default)]
+    pub extra_args: Vec<Argument<'a>>,
 }
 
 impl_translation_unit_for!(SourceFile<'a>);
@@ -49,11 +51,63 @@ impl Source {
 pub struct GlobPattern(pub PathBuf);
 
 impl GlobPattern {
+    /// Returns the files matched by the pattern, sorted, so the resultant command lines
+    /// are always generated in the same order. A `**` component matches the current
+    /// directory and any of its subdirectories
     #[inline(always)]
     fn resolve(&self) -> Result<Vec<PathBuf>> {
-        glob::glob(self.0.to_str().unwrap_or_default())?
-            .map(|path| path.with_context(|| ""))
-            .collect()
+        let mut paths = glob::glob(self.0.to_str().unwrap_or_default())
+            .with_context(|| format!("Invalid glob pattern: {:?}", self.0))?
+            .map(|path| {
+                path.with_context(|| format!("Unable to read a path matched by {:?}", self.0))
+            })
+            .collect::<Result<Vec<PathBuf>>>()?;
+
+        if paths.is_empty() {
+            log::warn!("The glob pattern {:?} doesn't match any file", self.0);
+        }
+
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+/// A set of glob patterns, relative to the code root, whose matched files must be discarded
+/// from the ones declared as sources
+#[derive(Debug, Default)]
+pub struct ExclusionPatterns(Vec<glob::Pattern>);
+
+impl ExclusionPatterns {
+    pub fn new(code_root: &Path, patterns: &[&str]) -> Result<Self> {
+        let escaped_root = glob::Pattern::escape(code_root.to_str().unwrap_or_default());
+
+        patterns
+            .iter()
+            .map(|pattern| {
+                let pattern_path = Path::new(pattern);
+                let full_pattern = if pattern_path.is_absolute() {
+                    pattern_path.to_path_buf()
+                } else {
+                    Path::new(&escaped_root).join(pattern_path)
+                };
+
+                glob::Pattern::new(full_pattern.to_str().unwrap_or_default())
+                    .with_context(|| format!("Invalid exclusion pattern: {pattern:?}"))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    /// Whenever the given path is matched by any of the exclusion patterns
+    pub fn excludes(&self, path: &Path) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+
+        self.0
+            .iter()
+            .any(|pattern| pattern.matches_path_with(path, options))
     }
 }
 
@@ -69,6 +123,10 @@ impl<'a> SourceSet<'a> {
         self.0.as_slice()
     }
 
+    pub fn push(&mut self, source: SourceFile<'a>) {
+        self.0.push(source)
+    }
+
     pub fn as_args_to(&self, dst: &mut Vec<Argument>) -> Result<()> {
         let args = self.0.iter().map(|sf| sf.path()).map(Argument::from);
 
@@ -77,3 +135,50 @@ impl<'a> SourceSet<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod sourceset_tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_resolution_and_exclusions() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src").join("nested"))?;
+        for file in [
+            "main.cpp",
+            "test_main.cpp",
+            "src/b.cpp",
+            "src/nested/test_a.cpp",
+        ] {
+            std::fs::write(root.join(file), "")?;
+        }
+
+        let resolved = Source::Glob(GlobPattern(root.join("**").join("*.cpp"))).paths()?;
+        assert_eq!(
+            resolved,
+            vec![
+                root.join("main.cpp"),
+                root.join("src").join("b.cpp"),
+                root.join("src").join("nested").join("test_a.cpp"),
+                root.join("test_main.cpp"),
+            ]
+        );
+
+        let exclusions = ExclusionPatterns::new(root, &["**/test_*.cpp"])?;
+        let remaining: Vec<&PathBuf> = resolved
+            .iter()
+            .filter(|path| !exclusions.excludes(path))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![&root.join("main.cpp"), &root.join("src").join("b.cpp")]
+        );
+
+        let non_recursive = ExclusionPatterns::new(root, &["*.cpp"])?;
+        assert!(non_recursive.excludes(&root.join("main.cpp")));
+        assert!(!non_recursive.excludes(&root.join("src").join("b.cpp")));
+
+        Ok(())
+    }
+}
@@ -1,14 +1,33 @@
 use crate::domain::commands::arguments::Argument;
-use crate::domain::target::TargetKind;
-use crate::project_model::sourceset::SourceSet;
+use crate::domain::target::{TargetIdentifier, TargetKind};
+use crate::project_model::compiler::CompileOptions;
+use crate::project_model::project::BuildHooks;
+use crate::project_model::sourceset::{SourceFile, SourceSet};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::path::PathBuf;
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone)]
 pub struct TargetModel<'a> {
     pub output_name: Cow<'a, str>,
     pub sources: SourceSet<'a>,
-    pub extra_args: Vec<Argument<'a>>,
+    pub compile_args: Vec<Argument<'a>>,
+    pub extra_args: Vec<Argument<'a>>, // deprecated, passed to the compile and the link command lines
     pub kind: TargetKind,
+    pub options: CompileOptions<'a>,
+    pub link_options: LinkOptions<'a>,
+    pub pch: Option<SourceFile<'a>>,
+    pub hooks: BuildHooks<'a>,
+    pub dependencies: Vec<TargetIdentifier<'a>>,
     pub enabled_for_current_program_iteration: bool,
 }
+
+/// The details about how the final product of a target must be linked, which are translated into
+/// the concrete syntax of every compiler (see: [`crate::domain::commands::arguments::link_options_args`])
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default, Clone)]
+pub struct LinkOptions<'a> {
+    pub link_libraries: Vec<Cow<'a, str>>,
+    pub link_dirs: Vec<PathBuf>,
+    pub link_args: Vec<Argument<'a>>,
+    pub frameworks: Vec<Cow<'a, str>>,
+}
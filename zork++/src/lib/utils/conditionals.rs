@@ -0,0 +1,295 @@
+//! Resolution of the conditional sections of the configuration files, which are only applied
+//! when the host operating system or the selected compiler matches their condition:
+//! - the `[compiler.<compiler>]` tables are merged into the `[compiler]` table, when `<compiler>`
+//!   is the selected one
+//! - the `[target.'cfg(<predicate>)'.<table>]` tables are merged into the root of the
+//!   configuration file, when the predicate holds
+//!
+//! The predicates follow the syntax of the `Rust` ones, with the `os`, `family`, `arch` and
+//! `compiler` keys, the `unix` and `windows` shorthands, and the `all`, `any` and `not` combinators.
+//! The conditional sections are merged with the same rules used for the configuration files
+//! inheritance (see [`crate::utils::inheritance`])
+
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use toml::value::Table;
+use toml::Value;
+
+use crate::config_file::compiler::CppCompiler;
+
+use super::inheritance;
+
+/// The table of the configuration file holding the conditional sections by predicate
+pub const CONDITIONAL_SECTIONS: &str = "target";
+
+/// Merges the conditional sections of the given configuration file whose conditions holds,
+/// and removes all of them. Returns whether the configuration file had any conditional section
+pub fn resolve_conditionals(config: &mut Table) -> Result<bool> {
+    // The conditions are evaluated against the compiler selected before merging any section
+    let compiler = config
+        .get("compiler")
+        .and_then(|compiler| compiler.get("cpp_compiler"))
+        .and_then(as_compiler);
+    let mut found = false;
+
+    if let Some(Value::Table(sections)) = config.remove(CONDITIONAL_SECTIONS) {
+        found = true;
+        for (condition, section) in sections {
+            let predicate = Predicate::parse(&condition)
+                .with_context(|| format!("Invalid condition on [target.'{condition}']"))?;
+            let section = match section {
+                Value::Table(section) => section,
+                _ => return Err(eyre!("[target.'{condition}'] must be a table")),
+            };
+
+            if predicate.eval(compiler)? {
+                inheritance::merge(config, section);
+            }
+        }
+    }
+
+    if let Some(Value::Table(compiler_table)) = config.get_mut("compiler") {
+        let by_compiler = compiler_table
+            .iter()
+            .filter(|(key, value)| value.is_table() && key_as_compiler(key).is_some())
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for key in by_compiler {
+            found = true;
+            if let Some(Value::Table(section)) = compiler_table.remove(&key) {
+                if key_as_compiler(&key) == compiler {
+                    inheritance::merge(compiler_table, section);
+                }
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+fn as_compiler(value: &Value) -> Option<CppCompiler> {
+    value.clone().try_into().ok()
+}
+
+/// The compiler named by the given key, when it's the one of a `[compiler.<compiler>]` table
+pub(crate) fn key_as_compiler(key: &str) -> Option<CppCompiler> {
+    as_compiler(&Value::String(key.to_string()))
+}
+
+/// A condition of a conditional section of the configuration file
+#[derive(Debug, PartialEq)]
+enum Predicate {
+    /// A `key = "value"` pair, or one of the shorthands without value
+    Cfg(String, Option<String>),
+    All(Vec<Predicate>),
+    Any(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    /// Parses the condition of a section, declared as `cfg(<predicate>)`
+    fn parse(condition: &str) -> Result<Self> {
+        let mut tokens = tokenize(condition)?.into_iter().peekable();
+        if tokens.next() != Some(Token::Ident(String::from("cfg")))
+            || tokens.next() != Some(Token::OpenParen)
+        {
+            return Err(eyre!("Expected a condition like: cfg(os = \"linux\")"));
+        }
+        let predicate = Self::parse_predicate(&mut tokens)?;
+        if tokens.next() != Some(Token::CloseParen) || tokens.next().is_some() {
+            return Err(eyre!("Expected a single predicate inside of cfg(...)"));
+        }
+
+        Ok(predicate)
+    }
+
+    fn parse_predicate(
+        tokens: &mut std::iter::Peekable<std::vec::IntoIter<Token>>,
+    ) -> Result<Self> {
+        let ident = match tokens.next() {
+            Some(Token::Ident(ident)) => ident,
+            other => return Err(eyre!("Expected an identifier, found: {other:?}")),
+        };
+
+        match tokens.peek() {
+            Some(Token::Equals) => {
+                tokens.next();
+                match tokens.next() {
+                    Some(Token::Str(value)) => Ok(Predicate::Cfg(ident, Some(value))),
+                    other => Err(eyre!(
+                        "Expected a quoted value for {ident:?}, found: {other:?}"
+                    )),
+                }
+            }
+            Some(Token::OpenParen) => {
+                tokens.next();
+                let mut predicates = Vec::new();
+                while tokens.peek() != Some(&Token::CloseParen) {
+                    predicates.push(Self::parse_predicate(tokens)?);
+                    if tokens.peek() == Some(&Token::Comma) {
+                        tokens.next();
+                    } else {
+                        break;
+                    }
+                }
+                if tokens.next() != Some(Token::CloseParen) {
+                    return Err(eyre!("Unclosed parenthesis on {ident}(...)"));
+                }
+
+                match ident.as_str() {
+                    "all" => Ok(Predicate::All(predicates)),
+                    "any" => Ok(Predicate::Any(predicates)),
+                    "not" if predicates.len() == 1 => {
+                        Ok(Predicate::Not(Box::new(predicates.remove(0))))
+                    }
+                    "not" => Err(eyre!("not(...) expects exactly one predicate")),
+                    _ => Err(eyre!("Unknown combinator: {ident:?}")),
+                }
+            }
+            _ => Ok(Predicate::Cfg(ident, None)),
+        }
+    }
+
+    fn eval(&self, compiler: Option<CppCompiler>) -> Result<bool> {
+        match self {
+            Predicate::Cfg(key, None) => match key.as_str() {
+                "unix" | "windows" => Ok(std::env::consts::FAMILY.eq(key)),
+                _ => Err(eyre!("Unknown condition: {key:?}")),
+            },
+            Predicate::Cfg(key, Some(value)) => match key.as_str() {
+                "os" => Ok(std::env::consts::OS.eq(value)),
+                "family" => Ok(std::env::consts::FAMILY.eq(value)),
+                "arch" => Ok(std::env::consts::ARCH.eq(value)),
+                "compiler" => {
+                    let expected = key_as_compiler(value)
+                        .ok_or_else(|| eyre!("Unknown compiler on condition: {value:?}"))?;
+                    Ok(compiler == Some(expected))
+                }
+                _ => Err(eyre!("Unknown condition: {key:?}")),
+            },
+            Predicate::All(predicates) => {
+                for predicate in predicates {
+                    if !predicate.eval(compiler)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Predicate::Any(predicates) => {
+                for predicate in predicates {
+                    if predicate.eval(compiler)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Predicate::Not(predicate) => predicate.eval(compiler).map(|holds| !holds),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    OpenParen,
+    CloseParen,
+    Comma,
+    Equals,
+}
+
+fn tokenize(condition: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = condition.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => tokens.push(Token::OpenParen),
+            ')' => tokens.push(Token::CloseParen),
+            ',' => tokens.push(Token::Comma),
+            '=' => tokens.push(Token::Equals),
+            '"' | '\'' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some(other) => value.push(other),
+                        None => return Err(eyre!("Unterminated string on: {condition}")),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_whitespace() => {}
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' || next == '-' {
+                        ident.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            other => return Err(eyre!("Unexpected character {other:?} on: {condition}")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_eval_predicates() -> Result<()> {
+        let host_os = std::env::consts::OS;
+
+        let predicate = Predicate::parse(&format!("cfg(os = \"{host_os}\")"))?;
+        assert!(predicate.eval(None)?);
+
+        let predicate = Predicate::parse(&format!(
+            "cfg(all(not(os = \"{host_os}\"), any(compiler = 'gcc', unix)))"
+        ))?;
+        assert!(!predicate.eval(Some(CppCompiler::GCC))?);
+
+        let predicate = Predicate::parse("cfg(any(compiler = 'clang', compiler = 'gcc'))")?;
+        assert!(predicate.eval(Some(CppCompiler::GCC))?);
+        assert!(!predicate.eval(Some(CppCompiler::MSVC))?);
+
+        assert!(Predicate::parse("os = 'linux'").is_err());
+        assert!(Predicate::parse("cfg(os = 'linux'").is_err());
+        assert!(Predicate::parse("cfg(platform = 'linux')")?
+            .eval(None)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_conditional_sections() -> Result<()> {
+        let host_os = std::env::consts::OS;
+        let mut config: Table = toml::from_str(&format!(
+            "[compiler]\ncpp_compiler = 'gcc'\ncompile_args = ['-Wall']\n\
+            [compiler.gcc]\ncompile_args = ['-fconcepts-diagnostics-depth=2']\n\
+            [compiler.clang]\ncompile_args = ['-Wno-unused-command-line-argument']\n\
+            [target.'cfg(os = \"{host_os}\")'.targets.app]\nsources = ['host.cpp']\n\
+            [target.'cfg(not(os = \"{host_os}\"))'.targets.app]\nsources = ['other.cpp']\n\
+            [targets.app]\nsources = ['main.cpp']"
+        ))?;
+
+        assert!(resolve_conditionals(&mut config)?);
+
+        let expected: Table = toml::from_str(
+            "[compiler]\ncpp_compiler = 'gcc'\n\
+            compile_args = ['-Wall', '-fconcepts-diagnostics-depth=2']\n\
+            [targets.app]\nsources = ['main.cpp', 'host.cpp']",
+        )?;
+        assert_eq!(config, expected);
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ pub const ZORK: &str = "zork";
 
 pub mod compilers {
     pub const CLANG: &str = "clang";
+    pub const CLANG_CL: &str = "clang-cl";
 }
 
 /// The names of the `Zork++`specific directories, not their paths
@@ -15,7 +16,9 @@ pub mod dir_names {
     pub const INTRINSICS: &str = "intrinsics";
     pub const INTERFACES: &str = "interfaces";
     pub const IMPLEMENTATIONS: &str = "implementations";
+    pub const HEADER_UNITS: &str = "header_units";
     pub const OBJECT_FILES: &str = "obj_files";
+    pub const PCH: &str = "pch";
 }
 
 pub mod env_vars {
@@ -30,6 +33,8 @@ pub mod debug_messages {
 pub mod error_messages {
     pub const READ_CFG_FILE: &str = "Could not read the configuration file";
     pub const PARSE_CFG_FILE: &str = "Could not parse the configuration file";
+    pub const READ_TOOLCHAIN_FILE: &str = "Could not read the toolchain file";
+    pub const PARSE_TOOLCHAIN_FILE: &str = "Could not parse the toolchain file";
     pub const REMOVE_FILE: &str = "Unable to remove file from fs";
     pub const FAILURE_GENERATING_COMMANDS: &str =
         "Failed to generated the commands for the project";
@@ -79,6 +84,12 @@ pub mod error_messages {
         "An error happened while retrieving the target information";
     pub const FAILURE_SYSTEM_MODULES: &str =
         "An error happened while generating the commands for the declared system headers as modules";
+    pub const FAILURE_HEADER_UNITS: &str =
+        "An error happened while generating the commands for the declared header units";
+    pub const FAILURE_GENERATORS: &str =
+        "An error happened while generating the commands of the generators";
+    pub const FAILURE_PRECOMPILED_HEADER: &str =
+        "An error happened while generating the command for the precompiled header of the target";
     pub const WRONG_DOWNCAST_FOR: &str = "An error happened while resolving the original type of";
     pub const FILTERING_COMPILE_BUT_DONT_LINK: &str = "Unlikely error happened while removing the compile but don't link flag from the flyweight data. This is a BUG, so please, open an issue on upsteam";
 
@@ -119,12 +130,6 @@ pub const CONFIG_FILE_NAME: &str = "zork";
 pub const CONFIG_FILE_EXT: &str = "toml";
 pub const CACHE_FILE_EXT: &str = "json";
 
-pub const BINARY_EXTENSION: &str = if cfg!(target_os = "windows") {
-    "exe"
-} else {
-    ""
-};
-
 pub const ZORK_CACHE_FILENAME: &str = "cache.json";
 pub const COMPILATION_DATABASE: &str = "compile_commands.json";
 
@@ -0,0 +1,436 @@
+//! Expansion of the `${variable}` placeholders that the user can write on the string values
+//! of the configuration file, and splitting of the custom commands declared on it into
+//! the program and its arguments
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use color_eyre::eyre::{eyre, Context, ContextCompat};
+use color_eyre::Result;
+use indexmap::IndexMap;
+use regex::Regex;
+
+use crate::cache::EnvVars;
+use crate::config_file::{
+    build::BuildAttribute,
+    compiler::CompilerAttribute,
+    generator::GeneratorAttribute,
+    modules::{ModuleImplementation, ModuleInterface, ModulePartition, ModulesAttribute},
+    project::ProjectAttribute,
+    target::{SourceFileAttribute, TargetAttribute, TargetSource},
+    toolchain::ToolchainAttribute,
+    workspace::WorkspaceAttribute,
+    ZorkConfigFile,
+};
+
+/// The prefix of the variables that are replaced with the value of an environment variable,
+/// like `${env:LLVM_ROOT}`
+pub const ENV_VARIABLE_PREFIX: &str = "env:";
+
+static VARIABLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\{((?:env:)?\w+)\}").unwrap());
+static ENV_VARIABLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\{env:(\w+)\}").unwrap());
+
+/// Replaces the `${variable}` occurrences of the given command with their values. The
+/// `${env:NAME}` ones are replaced with the value of the `NAME` environment variable
+pub fn expand_variables(command: &str, variables: &[(&str, Cow<str>)]) -> Result<String> {
+    let mut expanded = String::with_capacity(command.len());
+    let mut last_match = 0;
+
+    for captures in VARIABLE.captures_iter(command) {
+        let whole = captures.get(0).unwrap();
+        let name = &captures[1];
+        let value = match name.strip_prefix(ENV_VARIABLE_PREFIX) {
+            Some(env_var) => Cow::Owned(std::env::var(env_var).with_context(|| {
+                format!("The environment variable of ${{{name}}} is not defined")
+            })?),
+            None => variables
+                .iter()
+                .find(|(var, _)| (*var).eq(name))
+                .map(|(_, value)| Cow::Borrowed(value.as_ref()))
+                .with_context(|| format!("Unknown variable: ${{{name}}}"))?,
+        };
+
+        expanded.push_str(&command[last_match..whole.start()]);
+        expanded.push_str(&value);
+        last_match = whole.end();
+    }
+    expanded.push_str(&command[last_match..]);
+
+    Ok(expanded)
+}
+
+/// The values of a configuration file that had any variable expanded, which the expanded
+/// configuration file borrows them from
+#[derive(Debug, Default)]
+pub struct ExpandedValues(Vec<String>);
+
+/// Expands the variables of every string value of the given configuration file. The values
+/// without any variable are kept borrowed from the contents of the file, and the expanded ones
+/// are borrowed from the given storage
+pub fn expand_config_file<'a>(
+    mut config: ZorkConfigFile<'a>,
+    variables: &[(&str, Cow<str>)],
+    storage: &'a mut ExpandedValues,
+) -> Result<ZorkConfigFile<'a>> {
+    config.expand(
+        "",
+        &mut Expander {
+            variables,
+            pass: Pass::Expand(&mut storage.0),
+        },
+    )?;
+
+    let storage: &'a ExpandedValues = storage;
+    config.expand(
+        "",
+        &mut Expander {
+            variables,
+            pass: Pass::Replace(storage.0.iter()),
+        },
+    )?;
+
+    Ok(config)
+}
+
+/// Collects the current values of the environment variables read by the given configuration
+/// file, so their changes can be tracked between runs. The undefined ones are reported once the
+/// configuration file is expanded
+pub fn read_environment(raw: &str, env_vars: &mut EnvVars) {
+    for captures in ENV_VARIABLE.captures_iter(raw) {
+        let name = &captures[1];
+        if let Ok(value) = std::env::var(name) {
+            env_vars.insert(name.to_string(), value);
+        }
+    }
+}
+
+struct Expander<'a, 'v> {
+    variables: &'v [(&'v str, Cow<'v, str>)],
+    pass: Pass<'a, 'v>,
+}
+
+/// The values of a configuration file are visited twice, since the ones borrowed from its contents
+/// can't be replaced until all the expanded values are stored
+enum Pass<'a, 'v> {
+    /// Stores the expanded values of the ones that hold any variable
+    Expand(&'v mut Vec<String>),
+    /// Replaces the values that hold any variable with their stored expansion, in the same order
+    Replace(std::slice::Iter<'a, String>),
+}
+
+trait Expand<'a> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()>;
+}
+
+impl<'a> Expand<'a> for &'a str {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        if !self.contains("${") {
+            return Ok(());
+        }
+
+        match &mut expander.pass {
+            Pass::Expand(expanded) => expanded.push(
+                expand_variables(self, expander.variables)
+                    .with_context(|| format!("Invalid value of `{key}`: {self:?}"))?,
+            ),
+            Pass::Replace(expanded) => {
+                if let Some(expanded) = expanded.next() {
+                    *self = expanded;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Expand<'a> for Cow<'a, str> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        // The owned values are expanded in place, so they are only visited once
+        if matches!(expander.pass, Pass::Expand(_)) && self.contains("${") {
+            *self = Cow::Owned(
+                expand_variables(self, expander.variables)
+                    .with_context(|| format!("Invalid value of `{key}`: {self:?}"))?,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T: Expand<'a>> Expand<'a> for Option<T> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        match self {
+            Some(value) => value.expand(key, expander),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, T: Expand<'a>> Expand<'a> for Vec<T> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        self.iter_mut()
+            .try_for_each(|value| value.expand(key, expander))
+    }
+}
+
+/// Implements [`Expand`] for a table of the configuration file, by expanding the given fields
+macro_rules! expand_fields {
+    ($table:ident { $($field:ident),+ $(,)? }) => {
+        impl<'a> Expand<'a> for $table<'a> {
+            fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+                $(
+                    self.$field
+                        .expand(&nested_key(key, stringify!($field)), expander)?;
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
+expand_fields!(ZorkConfigFile {
+    project,
+    compiler,
+    build,
+    modules,
+    toolchain,
+    targets,
+    generators,
+    workspace,
+});
+expand_fields!(ProjectAttribute {
+    name,
+    authors,
+    code_root,
+    pre_build,
+    post_build,
+});
+expand_fields!(CompilerAttribute {
+    driver_path,
+    std_lib_installed_dir,
+    extra_args,
+    compile_args,
+    link_args,
+    system_headers_path,
+    launcher,
+    defines,
+    include_dirs,
+    system_include_dirs,
+});
+expand_fields!(BuildAttribute { output_dir });
+expand_fields!(ModulesAttribute {
+    base_ifcs_dir,
+    interfaces,
+    base_impls_dir,
+    implementations,
+    sys_modules,
+    header_units,
+});
+expand_fields!(ModuleInterface {
+    file,
+    module_name,
+    partition,
+    dependencies,
+    extra_args,
+});
+expand_fields!(ModulePartition {
+    module,
+    partition_name
+});
+expand_fields!(ModuleImplementation {
+    file,
+    dependencies,
+    extra_args
+});
+expand_fields!(ToolchainAttribute {
+    target,
+    sysroot,
+    linker,
+    archiver,
+    target_os,
+});
+expand_fields!(TargetAttribute {
+    output_name,
+    sources,
+    exclude,
+    extra_args,
+    compile_args,
+    defines,
+    include_dirs,
+    system_include_dirs,
+    link_libraries,
+    link_dirs,
+    link_args,
+    frameworks,
+    pch,
+    pre_build,
+    post_build,
+    modules,
+    dependencies,
+});
+expand_fields!(SourceFileAttribute { file, extra_args });
+expand_fields!(GeneratorAttribute {
+    command,
+    inputs,
+    outputs,
+    target
+});
+expand_fields!(WorkspaceAttribute { members });
+
+impl<'a> Expand<'a> for IndexMap<&'a str, TargetAttribute<'a>> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        self.iter_mut()
+            .try_for_each(|(name, target)| target.expand(&nested_key(key, name), expander))
+    }
+}
+
+impl<'a> Expand<'a> for TargetSource<'a> {
+    fn expand(&mut self, key: &str, expander: &mut Expander<'a, '_>) -> Result<()> {
+        match self {
+            TargetSource::Path(file) => file.expand(key, expander),
+            TargetSource::Detailed(source) => source.expand(key, expander),
+        }
+    }
+}
+
+fn nested_key(key: &str, name: &str) -> String {
+    if key.is_empty() {
+        name.to_string()
+    } else {
+        format!("{key}.{name}")
+    }
+}
+
+/// Splits a command into its words, separated by whitespaces, unless they are quoted (with
+/// single or double quotes). Inside double quotes, a quote can be escaped with a backslash
+pub fn split_command_line(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some(closing) if closing == c => break,
+                        Some('\\') if c == '"' && chars.peek() == Some(&'"') => {
+                            word.push(chars.next().unwrap())
+                        }
+                        Some(other) => word.push(other),
+                        None => return Err(eyre!("Unterminated quote on: {command:?}")),
+                    }
+                }
+            }
+            c if c.is_whitespace() => words.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(current);
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod expansion_tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_variables() -> Result<()> {
+        let variables = [
+            ("out_dir", Cow::Borrowed("/project/out")),
+            ("target_name", Cow::Borrowed("app")),
+        ];
+
+        assert_eq!(
+            expand_variables("flatc -o ${out_dir}/gen/${target_name} a.fbs", &variables)?,
+            "flatc -o /project/out/gen/app a.fbs"
+        );
+        assert_eq!(expand_variables("echo $HOME", &variables)?, "echo $HOME");
+        assert!(expand_variables("strip ${target_output}", &variables).is_err());
+
+        std::env::set_var("ZORK_EXPANSION_TEST_ROOT", "/opt/llvm");
+        assert_eq!(
+            expand_variables("${env:ZORK_EXPANSION_TEST_ROOT}/bin/clang++", &variables)?,
+            "/opt/llvm/bin/clang++"
+        );
+        assert!(expand_variables("${env:ZORK_EXPANSION_UNDEFINED}", &variables).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_config_file() -> Result<()> {
+        let variables = [("profile", Cow::Borrowed("release"))];
+        let expand = |tables: &str| -> Result<(String, usize)> {
+            let raw = format!(
+                "[project]\nname = 'app'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n{tables}"
+            );
+            let mut storage = ExpandedValues::default();
+            let config: ZorkConfigFile = toml::from_str(&raw)?;
+            let config = format!(
+                "{:?}",
+                expand_config_file(config, &variables, &mut storage)?
+            );
+            Ok((config, storage.0.len()))
+        };
+
+        let (config, expanded) = expand(
+            "[build]\noutput_dir = 'out/${profile}'\n\
+            [targets.app]\nsources = ['main.cpp', { file = '${profile}.cpp' }]\n\
+            pre_build = ['echo ${profile}']",
+        )?;
+        assert!(config.contains(r#"output_dir: Some("out/release")"#));
+        assert!(config.contains(r#"file: "release.cpp""#));
+        assert!(config.contains(r#""main.cpp""#));
+        assert!(config.contains(r#""echo release""#));
+        // The owned values are expanded in place
+        assert_eq!(expanded, 2);
+
+        let err = expand("driver_path = '${toolchain_root}/clang++'").unwrap_err();
+        assert!(format!("{err:#}").contains("compiler.driver_path"));
+
+        assert_eq!(expand("")?.1, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_environment() {
+        std::env::set_var("ZORK_EXPANSION_TEST_CC", "gcc");
+        let raw = "[compiler]\ndriver_path = '${env:ZORK_EXPANSION_TEST_CC}'\n\
+            [build]\noutput_dir = '${env:ZORK_EXPANSION_UNDEFINED}/${profile}'";
+
+        let mut env_vars = EnvVars::new();
+        read_environment(raw, &mut env_vars);
+        assert_eq!(
+            env_vars,
+            EnvVars::from([("ZORK_EXPANSION_TEST_CC".to_string(), "gcc".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_split_command_line() -> Result<()> {
+        assert_eq!(
+            split_command_line(r#"python  gen.py --name "my app" 'a b'c "say \"hi\"""#)?,
+            vec![
+                "python",
+                "gen.py",
+                "--name",
+                "my app",
+                "a bc",
+                r#"say "hi""#
+            ]
+        );
+        assert_eq!(
+            split_command_line(r"C:\tools\sign.exe out\app.exe")?,
+            vec![r"C:\tools\sign.exe", r"out\app.exe"]
+        );
+        assert!(split_command_line("echo 'unterminated").is_err());
+        assert!(split_command_line("   ")?.is_empty());
+
+        Ok(())
+    }
+}
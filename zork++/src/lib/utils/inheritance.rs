@@ -0,0 +1,273 @@
+//! Resolution of the configuration files that inherits from other ones, through their `extends`
+//! and `include` attributes.
+//!
+//! The inherited configuration files are merged from the first to the last one, and the
+//! configuration file that inherits from them is merged in the last place, so every layer only
+//! has to state its differences:
+//! - tables are merged key by key, and the values of the last layers overrides the previous ones
+//! - arrays are appended to the inherited ones, unless their key is listed in the
+//!   [`REPLACE_MARKER`] attribute of the same table, where they replace the inherited ones
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, Context};
+use color_eyre::Result;
+use toml::value::Table;
+use toml::Value;
+
+use super::conditionals;
+use super::constants::error_messages;
+use super::reader::ConfigFile;
+
+/// The attribute of a configuration file holding the only configuration file that it extends
+pub const EXTENDS: &str = "extends";
+/// The attribute of a configuration file holding the configuration files that it includes
+pub const INCLUDE: &str = "include";
+/// The attribute of any table holding the keys of the arrays that replaces the inherited ones,
+/// instead of being appended to them
+pub const REPLACE_MARKER: &str = "replace";
+
+/// The contents of a configuration file, with the ones of the configuration files that it
+/// inherits from already merged in
+#[derive(Debug)]
+pub struct ResolvedConfigFile {
+    pub raw: String,
+    /// The configuration files merged into the resolved one, in any level of the inheritance
+    pub inherited: Vec<ConfigFile>,
+}
+
+impl ResolvedConfigFile {
+    /// The last time that any of the configuration files merged into this one was modified
+    pub fn last_time_modified(&self) -> Option<DateTime<Utc>> {
+        self.inherited
+            .iter()
+            .map(|cfg| cfg.last_time_modified)
+            .max()
+    }
+}
+
+/// Reads the configuration file at the given path, merging the configuration files that it
+/// inherits from, if any, and then its conditional sections (see [`super::conditionals`])
+pub fn read_config_file(path: &Path) -> Result<ResolvedConfigFile> {
+    let raw = read_to_string(path)?;
+    let table = parse(path, &raw)?;
+
+    let mut inherited = Vec::new();
+    let mut merged = if inherited_paths(path, &table)?.is_empty() {
+        table
+    } else {
+        resolve(path, &raw, &mut Vec::new(), &mut inherited)?
+    };
+    let has_conditionals = conditionals::resolve_conditionals(&mut merged)
+        .with_context(|| format!("{}: {path:?}", error_messages::PARSE_CFG_FILE))?;
+
+    if inherited.is_empty() && !has_conditionals {
+        return Ok(ResolvedConfigFile { raw, inherited });
+    }
+
+    // Serialized as a value, so the keys are emitted before the tables that follow them
+    let raw = toml::to_string_pretty(&Value::Table(merged))
+        .with_context(|| format!("{}: {path:?}", error_messages::PARSE_CFG_FILE))?;
+
+    Ok(ResolvedConfigFile { raw, inherited })
+}
+
+/// Returns the paths of the configuration files that the one at the given path inherits
+/// from directly, through its `extends` and `include` attributes
+pub fn inherited_config_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let raw = read_to_string(path)?;
+    inherited_paths(path, &parse(path, &raw)?)
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("{}: {path:?}", error_messages::READ_CFG_FILE))
+}
+
+fn parse(path: &Path, raw: &str) -> Result<Table> {
+    toml::from_str(raw).with_context(|| format!("{}: {path:?}", error_messages::PARSE_CFG_FILE))
+}
+
+/// The inherited configuration files are declared relative to the configuration file that
+/// inherits from them
+fn inherited_paths(path: &Path, table: &Table) -> Result<Vec<PathBuf>> {
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let invalid = |attribute: &str| {
+        eyre!("The {attribute:?} attribute of the configuration file {path:?} must hold paths")
+    };
+
+    let mut inherited = Vec::new();
+    if let Some(extends) = table.get(EXTENDS) {
+        let extends = extends.as_str().ok_or_else(|| invalid(EXTENDS))?;
+        inherited.push(base_dir.join(extends));
+    }
+    if let Some(include) = table.get(INCLUDE) {
+        let include = include.as_array().ok_or_else(|| invalid(INCLUDE))?;
+        for file in include {
+            let file = file.as_str().ok_or_else(|| invalid(INCLUDE))?;
+            inherited.push(base_dir.join(file));
+        }
+    }
+
+    Ok(inherited)
+}
+
+fn resolve(
+    path: &Path,
+    raw: &str,
+    stack: &mut Vec<PathBuf>,
+    inherited: &mut Vec<ConfigFile>,
+) -> Result<Table> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("{}: {path:?}", error_messages::READ_CFG_FILE))?;
+    if stack.contains(&canonical) {
+        return Err(eyre!(
+            "Found a cycle on the inheritance of the configuration file: {path:?}"
+        ));
+    }
+    stack.push(canonical);
+
+    let table = parse(path, raw)?;
+    let mut merged = Table::new();
+    for parent in inherited_paths(path, &table)? {
+        let parent_raw = read_to_string(&parent)
+            .with_context(|| format!("Could not inherit from it on: {path:?}"))?;
+        let last_time_modified = std::fs::metadata(&parent)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("{}: {parent:?}", error_messages::READ_CFG_FILE))?;
+
+        let mut parent_table = resolve(&parent, &parent_raw, stack, inherited)?;
+        // The inheritance of every layer has already been resolved, so it isn't inherited
+        parent_table.remove(EXTENDS);
+        parent_table.remove(INCLUDE);
+        merge(&mut merged, parent_table);
+
+        inherited.push(ConfigFile {
+            path: parent,
+            last_time_modified: DateTime::<Utc>::from(last_time_modified),
+        });
+    }
+    merge(&mut merged, table);
+
+    stack.pop();
+    Ok(merged)
+}
+
+/// Merges the overlay table into the base one, consuming the [`REPLACE_MARKER`] attributes
+/// found on the overlay
+pub(crate) fn merge(base: &mut Table, mut overlay: Table) {
+    let replaced = match overlay.remove(REPLACE_MARKER) {
+        Some(Value::Array(keys)) => keys
+            .into_iter()
+            .filter_map(|key| key.as_str().map(String::from))
+            .collect(),
+        Some(other) => {
+            // Not a marker, so it's left to be reported by the deserialization
+            overlay.insert(REPLACE_MARKER.to_string(), other);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    for (key, value) in overlay {
+        let replace = replaced.contains(&key);
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(table)) => merge(base_table, table),
+            (Some(Value::Array(base_array)), Value::Array(array)) if !replace => {
+                base_array.extend(array)
+            }
+            (_, Value::Table(table)) => {
+                let mut new_table = Table::new();
+                merge(&mut new_table, table);
+                base.insert(key, Value::Table(new_table));
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_tables_and_arrays() -> Result<()> {
+        let mut base: Table = toml::from_str(
+            "[compiler]\ncpp_compiler = 'clang'\nextra_args = ['-Wall']\n\
+            [targets.app]\nsources = ['main.cpp']\nextra_args = ['-g']",
+        )?;
+        let overlay: Table = toml::from_str(
+            "[compiler]\ncpp_compiler = 'gcc'\nextra_args = ['-Wextra']\n\
+            [targets.app]\nreplace = ['sources']\nsources = ['linux.cpp']\nextra_args = ['-O2']\n\
+            [targets.tests]\nreplace = ['sources']\nsources = ['tests.cpp']",
+        )?;
+
+        merge(&mut base, overlay);
+
+        let expected: Table = toml::from_str(
+            "[compiler]\ncpp_compiler = 'gcc'\nextra_args = ['-Wall', '-Wextra']\n\
+            [targets.app]\nsources = ['linux.cpp']\nextra_args = ['-g', '-O2']\n\
+            [targets.tests]\nsources = ['tests.cpp']",
+        )?;
+        assert_eq!(base, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_config_file_with_extends_and_include() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::create_dir(root.join("common"))?;
+        std::fs::write(
+            root.join("common/zork_base.toml"),
+            "[project]\nname = 'app'\n[compiler]\ncpp_compiler = 'clang'\ncpp_standard = '20'\n\
+            extra_args = ['-Wall']\n[targets.app]\nsources = ['main.cpp']",
+        )?;
+        std::fs::write(
+            root.join("common/msvc_paths.toml"),
+            "[compiler]\nstd_lib_installed_dir = 'C:\\VS\\modules'",
+        )?;
+        std::fs::write(
+            root.join("zork_windows.toml"),
+            "extends = 'common/zork_base.toml'\ninclude = ['common/msvc_paths.toml']\n\
+            [compiler]\ncpp_compiler = 'msvc'\nextra_args = ['/EHsc']",
+        )?;
+
+        let resolved = read_config_file(&root.join("zork_windows.toml"))?;
+        assert_eq!(resolved.inherited.len(), 2);
+
+        let config = crate::config_file::zork_cfg_from_file(&resolved.raw)?;
+        assert_eq!(config.project.name, "app");
+        assert_eq!(
+            config.compiler.cpp_compiler,
+            crate::config_file::compiler::CppCompiler::MSVC
+        );
+        assert_eq!(
+            config.compiler.std_lib_installed_dir,
+            Some("C:\\VS\\modules")
+        );
+        assert_eq!(config.compiler.extra_args, Some(vec!["-Wall", "/EHsc"]));
+        assert_eq!(config.extends, Some("common/zork_base.toml"));
+        assert!(config.targets.contains_key("app"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inheritance_cycle() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::write(root.join("zork_a.toml"), "extends = 'zork_b.toml'")?;
+        std::fs::write(root.join("zork_b.toml"), "extends = 'zork_a.toml'")?;
+
+        let err = read_config_file(&root.join("zork_a.toml")).unwrap_err();
+        assert!(format!("{err:#}").contains("cycle"));
+
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
+pub mod conditionals;
 pub mod constants;
+pub mod expansion;
 pub mod fs;
+pub mod inheritance;
 pub mod logger;
 pub mod reader;
+pub mod scanner;
 pub mod template;
+pub mod validation;
@@ -1,39 +1,52 @@
 use crate::cli::input::CliArgs;
 
-use crate::config_file::target::TargetAttribute;
+use crate::config_file;
+use crate::config_file::target::{TargetAttribute, TargetSource};
 use crate::domain::commands::arguments::Argument;
-use crate::domain::target::TargetIdentifier;
+use crate::domain::target::{TargetIdentifier, TargetKind};
+use crate::domain::translation_unit::TranslationUnit;
 use crate::project_model::modules::SystemModule;
 use crate::project_model::sourceset::SourceFile;
-use crate::project_model::target::TargetModel;
+use crate::project_model::target::{LinkOptions, TargetModel};
 use crate::{
     config_file::{
-        build::BuildAttribute,
-        compiler::CompilerAttribute,
+        compiler::{CompilerAttribute, OptimizationLevel, WarningsLevel},
+        generator::GeneratorAttribute,
         modules::{ModuleImplementation, ModuleInterface, ModulesAttribute},
         project::ProjectAttribute,
+        toolchain::ToolchainAttribute,
+        workspace::WorkspaceAttribute,
         ZorkConfigFile,
     },
     project_model::{
         build::BuildModel,
-        compiler::CompilerModel,
+        compiler::{CompileOptions, CompilerModel, CppCompiler, ToolchainModel},
+        generator::GeneratorModel,
         modules::{
-            ModuleImplementationModel, ModuleInterfaceModel, ModulePartitionModel, ModulesModel,
+            HeaderUnitModel, ModuleImplementationModel, ModuleInterfaceModel, ModulePartitionModel,
+            ModulesModel,
         },
-        project::ProjectModel,
-        sourceset::{GlobPattern, Source, SourceSet},
+        project::{BuildHooks, ProjectModel},
+        sourceset::{ExclusionPatterns, GlobPattern, Source, SourceSet},
         ZorkModel,
     },
     utils,
 };
 use chrono::{DateTime, Utc};
-use color_eyre::{eyre::eyre, Result};
+use color_eyre::{
+    eyre::{eyre, Context, ContextCompat},
+    Result,
+};
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use super::constants::dir_names;
+use super::constants::{dir_names, error_messages, CONFIG_FILE_EXT, CONFIG_FILE_NAME};
+use super::{expansion, inheritance, scanner};
+
+/// The extensions of the generated files that are compiled as sources of their target
+const GENERATED_SOURCES_EXTENSIONS: [&str; 4] = ["cpp", "cc", "cxx", "c++"];
 
 /// Details about a found configuration file on the project
 ///
@@ -53,56 +66,234 @@ pub struct ConfigFile {
 /// parameter to indicate where the configuration files lives in
 /// the client's project. Defaults to `.`
 ///
+/// *filename_match* - Only the configuration files whose name contains
+/// this value are taken, if present
+///
 /// This function fails if there's no configuration file
-/// (or isn't present in any directory of the project)
+/// (or isn't present in any directory of the project), or if none of
+/// them matches the *filename_match*, listing the found ones
 pub fn find_config_files(
-    base_path: &Path, // TODO:(Workspaces) create the cfg arg to specifically receive where's located the
-    // user's Zork config files if they're not in the root of the project
-    // nor matches the tree structure from user's root cmd arg value
+    base_path: &Path,
     filename_match: &Option<String>,
 ) -> Result<Vec<ConfigFile>> {
     log::debug!("Searching for Zork++ configuration files...");
-    let mut files = vec![];
+    let mut candidates = vec![];
 
     for e in WalkDir::new(base_path)
-        .max_depth(2) // TODO:(Workspaces) so, max_depth should be zero when the cfg arg is ready
+        .max_depth(2)
         .into_iter()
         .filter_map(|e| e.ok())
     {
         let filename = e.file_name().to_str().unwrap();
-        let file_match = filename_match
-            .as_ref()
-            .map(|fm| fm.as_str())
-            .unwrap_or(filename);
         if e.metadata().unwrap().is_file()
             && filename.starts_with("zork")
             && filename.ends_with(".toml")
-            && filename.contains(file_match)
         {
-            files.push(ConfigFile {
+            candidates.push(ConfigFile {
                 path: e.path().to_path_buf(),
                 last_time_modified: DateTime::<Utc>::from(e.metadata()?.modified()?),
             })
         }
     }
 
+    if candidates.is_empty() {
+        return Err(eyre!("No configuration files found for the project"));
+    }
+
+    let Some(file_match) = filename_match else {
+        return Ok(candidates);
+    };
+
+    let (files, discarded): (Vec<ConfigFile>, Vec<ConfigFile>) =
+        candidates.into_iter().partition(|cfg| {
+            cfg.path
+                .file_name()
+                .and_then(|filename| filename.to_str())
+                .is_some_and(|filename| filename.contains(file_match.as_str()))
+        });
+
     if files.is_empty() {
-        Err(eyre!("No configuration files found for the project"))
+        Err(eyre!(
+            "None of the configuration files found for the project matches {file_match:?}. The found ones are: {:?}",
+            discarded.iter().map(|cfg| &cfg.path).collect::<Vec<_>>()
+        ))
     } else {
         Ok(files)
     }
 }
 
+/// Loads the details of the configuration files passed in explicitly with the `--config`
+/// [`CliArgs`] argument, which skips the discovery of the configuration files of the project.
+/// The relative paths are relative to the root of the project
+pub fn load_config_files(project_root: &Path, paths: &[String]) -> Result<Vec<ConfigFile>> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = project_root.join(path);
+            let metadata = path
+                .metadata()
+                .with_context(|| format!("{}: {:?}", error_messages::READ_CFG_FILE, path))?;
+            if !metadata.is_file() {
+                return Err(eyre!("The configuration file {path:?} isn't a file"));
+            }
+
+            Ok(ConfigFile {
+                last_time_modified: DateTime::<Utc>::from(metadata.modified()?),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// The configuration file of a member of a workspace, found on the directory of the member
+#[derive(Debug)]
+pub struct MemberConfigFile {
+    pub name: String,
+    pub root: PathBuf,
+    pub config_file: ConfigFile,
+}
+
+/// A member of a workspace, with its configuration file already parsed
+#[derive(Debug)]
+pub struct WorkspaceMember<'a> {
+    pub name: String,
+    pub root: PathBuf,
+    pub config: ZorkConfigFile<'a>,
+}
+
+/// Resolves the members declared on the `[workspace]` table of the configuration file placed on
+/// *workspace_root*, which must have a `zork.toml` on their directories. The name of a member is
+/// the name of its directory, so it must be unique among all the members of the workspace
+pub fn find_workspace_members(
+    workspace_root: &Path,
+    workspace: &WorkspaceAttribute,
+) -> Result<Vec<MemberConfigFile>> {
+    let mut members: Vec<MemberConfigFile> = Vec::with_capacity(workspace.members.len());
+
+    for declared in &workspace.members {
+        let member_path = get_file_path(workspace_root, None, declared);
+        let member_dirs: Vec<PathBuf> = if declared.contains('*') {
+            Source::Glob(GlobPattern(member_path))
+                .paths()?
+                .into_iter()
+                .filter(|path| path.is_dir())
+                .collect()
+        } else {
+            vec![member_path]
+        };
+
+        for root in member_dirs {
+            let cfg_path = root.join(format!("{CONFIG_FILE_NAME}.{CONFIG_FILE_EXT}"));
+            if !cfg_path.is_file() {
+                return Err(eyre!(
+                    "The workspace member {root:?} doesn't have a {CONFIG_FILE_NAME}.{CONFIG_FILE_EXT} file"
+                ));
+            }
+            if members.iter().any(|member| member.root.eq(&root)) {
+                continue;
+            }
+
+            let name = root
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| format!("Invalid workspace member: {root:?}"))?
+                .to_string();
+            if members.iter().any(|member| member.name.eq(&name)) {
+                return Err(eyre!(
+                    "There's more than one workspace member named {name:?}. Their directories must have different names"
+                ));
+            }
+
+            let last_time_modified = DateTime::<Utc>::from(cfg_path.metadata()?.modified()?);
+            members.push(MemberConfigFile {
+                name,
+                root,
+                config_file: ConfigFile {
+                    path: cfg_path,
+                    last_time_modified,
+                },
+            });
+        }
+    }
+
+    Ok(members)
+}
+
+/// Discards the configuration files that belongs to a member of a workspace declared on any
+/// other of the found configuration files, since the members are built as part of their workspace
+pub fn discard_workspace_members(config_files: Vec<ConfigFile>) -> Result<Vec<ConfigFile>> {
+    let mut members_cfgs = Vec::new();
+
+    for config_file in &config_files {
+        let resolved_file = inheritance::read_config_file(&config_file.path)?;
+        let workspace = config_file::workspace_from_file(&resolved_file.raw)
+            .with_context(|| error_messages::PARSE_CFG_FILE)?;
+
+        if let Some(workspace) = workspace {
+            let workspace_root = config_file.path.parent().unwrap_or(Path::new("."));
+            for member in find_workspace_members(workspace_root, &workspace)? {
+                members_cfgs.push(member.config_file.path.canonicalize()?);
+            }
+        }
+    }
+
+    Ok(config_files
+        .into_iter()
+        .filter(|cfg| {
+            cfg.path
+                .canonicalize()
+                .ok()
+                .is_none_or(|path| !members_cfgs.contains(&path))
+        })
+        .collect())
+}
+
+/// Discards the configuration files that other ones of the found configuration files inherits
+/// from, through their `extends` or `include` attributes, since they're just the shared layers
+/// of the configuration files that are built
+pub fn discard_inherited_config_files(config_files: Vec<ConfigFile>) -> Result<Vec<ConfigFile>> {
+    let mut inherited_cfgs = Vec::new();
+
+    for config_file in &config_files {
+        for inherited in inheritance::inherited_config_files(&config_file.path)? {
+            if let Ok(path) = inherited.canonicalize() {
+                inherited_cfgs.push(path);
+            }
+        }
+    }
+
+    Ok(config_files
+        .into_iter()
+        .filter(|cfg| {
+            cfg.path
+                .canonicalize()
+                .ok()
+                .is_none_or(|path| !inherited_cfgs.contains(&path))
+        })
+        .collect())
+}
+
 pub fn build_model<'a>(
     config: ZorkConfigFile<'a>,
     cli_args: &'a CliArgs,
     absolute_project_root: &Path,
+) -> Result<ZorkModel<'a>> {
+    build_workspace_model(config, Vec::new(), cli_args, absolute_project_root)
+}
+
+/// Builds the [`ZorkModel`] of a workspace. The modules, targets and generators of every member
+/// are assembled relative to the code root of the member, and added to the ones of the root
+/// configuration file, which provides the rest of the configuration for the whole workspace
+pub fn build_workspace_model<'a>(
+    config: ZorkConfigFile<'a>,
+    members: Vec<WorkspaceMember<'a>>,
+    cli_args: &'a CliArgs,
+    absolute_project_root: &Path,
 ) -> Result<ZorkModel<'a>> {
     let proj_name = config.project.name;
+    let output_dir = resolve_output_dir(&config, absolute_project_root);
 
     let project = assemble_project_model(config.project);
-    let compiler = assemble_compiler_model(config.compiler, cli_args);
-    let build = assemble_build_model(config.build, absolute_project_root);
 
     let code_root = PathBuf::from(absolute_project_root).join(
         project
@@ -112,15 +303,33 @@ pub fn build_model<'a>(
             .unwrap_or_default(),
     );
 
-    let modules = assemble_modules_model(config.modules, &code_root);
-    let targets = assemble_targets_model(config.targets, proj_name, &code_root);
+    let compiler = assemble_compiler_model(config.compiler, config.toolchain, cli_args, &code_root);
+
+    let mut modules = assemble_modules_model(config.modules, &code_root)?;
+    let mut targets =
+        assemble_targets_model(config.targets, None, proj_name, &code_root, &mut modules)?;
+
+    let mut generators =
+        assemble_generators_model(config.generators, None, &code_root, &mut targets)?;
+
+    for member in members {
+        let member_name = member.name.clone();
+        assemble_workspace_member(member, &mut modules, &mut targets, &mut generators)
+            .with_context(|| format!("Unable to assemble the workspace member: {member_name}"))?;
+    }
+
+    if targets.is_empty() {
+        return Err(eyre!("There's no targets declared for the project"));
+    }
+    let targets = sort_targets_by_dependencies(targets)?;
 
     Ok(ZorkModel {
         project,
         compiler,
-        build,
+        build: BuildModel { output_dir },
         modules,
         targets,
+        generators,
     })
 }
 
@@ -138,17 +347,98 @@ fn assemble_project_model(config: ProjectAttribute) -> ProjectModel {
             }),
         compilation_db: config.compilation_db.unwrap_or_default(),
         code_root: config.code_root.map(Cow::Borrowed),
+        hooks: BuildHooks::new(config.pre_build, config.post_build),
+    }
+}
+
+/// Resolves the directory where the products of the build process will be placed for the given
+/// configuration file. When the user targets a concrete platform through the `[toolchain]`
+/// triple, the products are nested under a subdirectory named after it, so the builds for the
+/// host and for other platforms can coexist
+pub fn resolve_output_dir(config: &ZorkConfigFile, project_root: &Path) -> PathBuf {
+    output_dir_path(
+        config.build.as_ref().and_then(|build| build.output_dir),
+        config.toolchain.as_ref().and_then(|tc| tc.target),
+        project_root,
+    )
+}
+
+fn output_dir_path(output_dir: Option<&str>, triple: Option<&str>, project_root: &Path) -> PathBuf {
+    let output_dir = output_dir
+        .map(|out_dir| out_dir.strip_prefix("./").unwrap_or(out_dir))
+        .unwrap_or(dir_names::DEFAULT_OUTPUT_DIR);
+    let output_dir = Path::new(project_root).join(output_dir);
+
+    match triple {
+        Some(triple) => output_dir.join(triple),
+        None => output_dir,
     }
 }
 
+/// The built-in variables available on the string values of the given configuration file.
+///
+/// The `${out_dir}` can't be used on the `output_dir` itself, and the variables only known when
+/// the commands of a target are executed are left as they are, to be expanded by then
+pub fn config_variables(
+    config: &ZorkConfigFile,
+    cli_args: &CliArgs,
+    project_root: &Path,
+) -> Result<Vec<(&'static str, Cow<'static, str>)>> {
+    let compiler: CppCompiler = config.compiler.cpp_compiler.into();
+    let mut variables = vec![
+        (
+            "project_root",
+            Cow::Owned(project_root.display().to_string()),
+        ),
+        ("project_name", Cow::Owned(config.project.name.to_string())),
+        ("compiler", Cow::Owned(compiler.as_ref().to_string())),
+        ("profile", Cow::Owned(cli_args.profile.clone())),
+        ("target_name", Cow::Borrowed("${target_name}")),
+        ("target_output", Cow::Borrowed("${target_output}")),
+    ];
+
+    let output_dir = config
+        .build
+        .as_ref()
+        .and_then(|build| build.output_dir)
+        .map(|out_dir| expansion::expand_variables(out_dir, &variables))
+        .transpose()
+        .with_context(|| "Invalid value of `build.output_dir`")?;
+    let output_dir = output_dir_path(
+        output_dir.as_deref(),
+        config.toolchain.as_ref().and_then(|tc| tc.target),
+        project_root,
+    );
+    variables.push(("out_dir", Cow::Owned(output_dir.display().to_string())));
+
+    Ok(variables)
+}
+
 fn assemble_compiler_model<'a>(
     config: CompilerAttribute<'a>,
+    toolchain: Option<ToolchainAttribute<'a>>,
     cli_args: &'a CliArgs,
+    code_root: &Path,
 ) -> CompilerModel<'a> {
-    let extra_args = config
-        .extra_args
+    let options = assemble_compile_options(
+        config.defines,
+        config.include_dirs,
+        config.system_include_dirs,
+        config.warnings,
+        config.optimization,
+        config.debug_info,
+        code_root,
+    );
+
+    let compile_args = config
+        .compile_args
+        .map(|args| args.into_iter().map(Argument::from).collect())
+        .unwrap_or_default();
+    let link_args = config
+        .link_args
         .map(|args| args.into_iter().map(Argument::from).collect())
         .unwrap_or_default();
+    let extra_args = assemble_extra_args(config.extra_args, "[compiler]");
 
     CompilerModel {
         cpp_compiler: config.cpp_compiler.into(),
@@ -162,26 +452,82 @@ fn assemble_compiler_model<'a>(
         std_lib_installed_dir: config
             .std_lib_installed_dir
             .map(|inst_dir| Cow::Borrowed(Path::new(inst_dir))),
+        compile_args,
+        link_args,
         extra_args,
+        // The one selected on the command line is applied once the model is loaded
+        launcher: config.launcher.map(Cow::Borrowed),
+        toolchain: assemble_toolchain_model(toolchain.unwrap_or_default()),
+        options,
     }
 }
 
-fn assemble_build_model(config: Option<BuildAttribute>, project_root: &Path) -> BuildModel {
-    let output_dir = config
-        .as_ref()
-        .and_then(|build| build.output_dir)
-        .map(|out_dir| out_dir.strip_prefix("./").unwrap_or(out_dir))
-        .unwrap_or(dir_names::DEFAULT_OUTPUT_DIR);
+/// The arguments declared under the deprecated `extra_args` attribute, which are passed to both
+/// the compile and the link command lines, as they were before the `compile_args` and the
+/// `link_args` attributes
+fn assemble_extra_args<'a>(
+    extra_args: Option<Vec<&'a str>>,
+    declared_on: &str,
+) -> Vec<Argument<'a>> {
+    if extra_args.is_some() {
+        log::warn!(
+            "The `extra_args` attribute of {declared_on} is deprecated. Use `compile_args` and `link_args` instead"
+        );
+    }
 
-    BuildModel {
-        output_dir: Path::new(project_root).join(output_dir),
+    extra_args
+        .unwrap_or_default()
+        .into_iter()
+        .map(Argument::from)
+        .collect()
+}
+
+fn assemble_compile_options<'a>(
+    defines: Option<Vec<&'a str>>,
+    include_dirs: Option<Vec<&'a str>>,
+    system_include_dirs: Option<Vec<&'a str>>,
+    warnings: Option<WarningsLevel>,
+    optimization: Option<OptimizationLevel>,
+    debug_info: Option<bool>,
+    code_root: &Path,
+) -> CompileOptions<'a> {
+    let to_paths = |dirs: Option<Vec<&str>>| -> Vec<PathBuf> {
+        dirs.unwrap_or_default()
+            .into_iter()
+            .map(|dir| get_file_path(code_root, None, dir))
+            .collect()
+    };
+
+    CompileOptions {
+        defines: defines
+            .unwrap_or_default()
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+        include_dirs: to_paths(include_dirs),
+        system_include_dirs: to_paths(system_include_dirs),
+        warnings: warnings.map(|level| level.into()),
+        optimization: optimization.map(|level| level.into()),
+        debug_info,
+    }
+}
+
+fn assemble_toolchain_model(config: ToolchainAttribute) -> ToolchainModel {
+    ToolchainModel {
+        target: config.target.map(Cow::Borrowed),
+        sysroot: config
+            .sysroot
+            .map(|sysroot| Cow::Borrowed(Path::new(sysroot))),
+        linker: config.linker.map(Cow::Borrowed),
+        archiver: config.archiver.map(Cow::Borrowed),
+        target_os: config.target_os.map(Cow::Borrowed),
     }
 }
 
 fn assemble_modules_model<'a>(
     config: Option<ModulesAttribute<'a>>,
     code_root: &Path,
-) -> ModulesModel<'a> {
+) -> Result<ModulesModel<'a>> {
     let modules = config.unwrap_or_default();
 
     let base_ifcs_dir = modules
@@ -190,16 +536,12 @@ fn assemble_modules_model<'a>(
         .map(Cow::from)
         .unwrap_or_default();
 
-    let interfaces = modules
-        .interfaces
-        .map(|ifcs| {
-            ifcs.into_iter()
-                .map(|m_ifc| -> ModuleInterfaceModel<'_> {
-                    assemble_module_interface_model(m_ifc, &base_ifcs_dir, code_root)
-                })
-                .collect()
-        })
-        .unwrap_or_default();
+    let interfaces = assemble_module_interfaces(
+        modules.interfaces.unwrap_or_default(),
+        &base_ifcs_dir,
+        code_root,
+    )
+    .with_context(|| error_messages::FAILURE_MODULE_INTERFACES)?;
 
     let base_impls_dir = modules
         .base_impls_dir
@@ -207,17 +549,12 @@ fn assemble_modules_model<'a>(
         .map(Cow::from)
         .unwrap_or_default();
 
-    let implementations = modules
-        .implementations
-        .map(|impls| {
-            impls
-                .into_iter()
-                .map(|m_impl| {
-                    assemble_module_implementation_model(m_impl, &base_impls_dir, code_root)
-                })
-                .collect()
-        })
-        .unwrap_or_default();
+    let implementations = assemble_module_implementations(
+        modules.implementations.unwrap_or_default(),
+        &base_impls_dir,
+        code_root,
+    )
+    .with_context(|| error_messages::FAILURE_MODULE_IMPLEMENTATIONS)?;
 
     let sys_modules = modules
         .sys_modules
@@ -232,20 +569,305 @@ fn assemble_modules_model<'a>(
                 .collect()
         });
 
-    ModulesModel {
+    let header_units = assemble_header_units(modules.header_units.unwrap_or_default(), code_root)
+        .with_context(|| error_messages::FAILURE_HEADER_UNITS)?;
+
+    Ok(ModulesModel {
         base_ifcs_dir,
         interfaces,
         base_impls_dir,
         implementations,
         sys_modules,
+        header_units,
+    })
+}
+
+/// Assembles the user headers that must be built as header units, expanding the ones declared
+/// through a glob pattern. The same header declared more than once is only built once
+fn assemble_header_units<'a>(
+    header_units: Vec<&'a str>,
+    code_root: &Path,
+) -> Result<Vec<HeaderUnitModel<'a>>> {
+    let mut models: Vec<HeaderUnitModel> = Vec::with_capacity(header_units.len());
+
+    for header_unit in header_units {
+        let file_path = get_file_path(code_root, None, header_unit);
+        let source = if header_unit.contains('*') {
+            Source::Glob(GlobPattern(file_path))
+        } else {
+            Source::File(file_path)
+        };
+
+        for path in source.paths()? {
+            if models.iter().any(|hu| hu.path().eq(&path)) {
+                continue;
+            }
+            let (dir, file_stem, extension) = utils::fs::get_file_details(&path)?;
+            models.push(HeaderUnitModel {
+                path: dir,
+                file_stem: Cow::Owned(file_stem),
+                extension: Cow::Owned(extension),
+            });
+        }
+    }
+
+    Ok(models)
+}
+
+/// Assembles the declared module interfaces, expanding the ones declared through a glob pattern.
+///
+/// The declaration order is kept, and the interfaces matched by a glob pattern take its place,
+/// ordered so every one of them is placed after the ones that it depends on, since the globbed
+/// files doesn't have a meaningful declaration order
+fn assemble_module_interfaces<'a>(
+    ifcs: Vec<ModuleInterface<'a>>,
+    base_ifcs_dir: &Path,
+    code_root: &Path,
+) -> Result<Vec<ModuleInterfaceModel<'a>>> {
+    let explicitly_declared = explicitly_declared_paths(
+        ifcs.iter().map(|m_ifc| m_ifc.file),
+        base_ifcs_dir,
+        code_root,
+    );
+    let mut interfaces = Vec::with_capacity(ifcs.len());
+
+    for m_ifc in ifcs
+        .into_iter()
+        .filter(|m_ifc| !m_ifc.exclude_from_build.unwrap_or_default())
+    {
+        if !m_ifc.file.contains('*') {
+            interfaces.push(assemble_module_interface_model(
+                m_ifc,
+                base_ifcs_dir,
+                code_root,
+            )?);
+            continue;
+        }
+
+        if m_ifc.module_name.is_some() || m_ifc.partition.is_some() {
+            log::warn!(
+                "The `module_name` and `partition` of {:?} are ignored, since they are inferred for every file matched by a glob pattern",
+                m_ifc.file
+            );
+        }
+
+        let pattern = GlobPattern(get_file_path(code_root, Some(base_ifcs_dir), m_ifc.file));
+        let mut discovered: Vec<(ModuleInterfaceModel<'a>, Vec<String>)> = Vec::new();
+        for path in Source::Glob(pattern).paths()? {
+            let already_declared = explicitly_declared.contains(&path)
+                || interfaces.iter().any(|ifc| ifc.path().eq(&path))
+                || discovered.iter().any(|(ifc, _)| ifc.path().eq(&path));
+            if !already_declared {
+                discovered.push(infer_module_interface_model(&path, &m_ifc)?);
+            }
+        }
+        interfaces.extend(sort_module_interfaces(discovered));
+    }
+
+    Ok(interfaces)
+}
+
+/// Assembles the declared module implementations, expanding the ones declared through a glob pattern
+fn assemble_module_implementations<'a>(
+    impls: Vec<ModuleImplementation<'a>>,
+    base_impls_dir: &Path,
+    code_root: &Path,
+) -> Result<Vec<ModuleImplementationModel<'a>>> {
+    let explicitly_declared = explicitly_declared_paths(
+        impls.iter().map(|m_impl| m_impl.file),
+        base_impls_dir,
+        code_root,
+    );
+    let mut implementations: Vec<ModuleImplementationModel<'a>> = Vec::with_capacity(impls.len());
+
+    for m_impl in impls
+        .into_iter()
+        .filter(|m_impl| !m_impl.exclude_from_build.unwrap_or_default())
+    {
+        if !m_impl.file.contains('*') {
+            implementations.push(assemble_module_implementation_model(
+                m_impl,
+                base_impls_dir,
+                code_root,
+            )?);
+            continue;
+        }
+
+        let pattern = GlobPattern(get_file_path(code_root, Some(base_impls_dir), m_impl.file));
+        for path in Source::Glob(pattern).paths()? {
+            let already_declared = explicitly_declared.contains(&path)
+                || implementations.iter().any(|imp| imp.path().eq(&path));
+            if !already_declared {
+                implementations.push(infer_module_implementation_model(&path, &m_impl)?);
+            }
+        }
+    }
+
+    Ok(implementations)
+}
+
+/// The paths of the module units that are declared one by one (not through a glob pattern),
+/// whose explicit declaration takes precedence over the inferred data of a glob match
+fn explicitly_declared_paths<'a>(
+    files: impl Iterator<Item = &'a str>,
+    base_dir: &Path,
+    code_root: &Path,
+) -> Vec<PathBuf> {
+    files
+        .filter(|file| !file.contains('*'))
+        .map(|file| get_file_path(code_root, Some(base_dir), file))
+        .collect()
+}
+
+/// Builds the model of a module interface matched by a glob pattern, inferring its module name,
+/// partition and dependencies from its contents, or from its file name when the file doesn't
+/// declare a named module (`module_name-partition_name.ext` or `module_name.ext`).
+///
+/// Returns it along with all the modules imported by the interface
+fn infer_module_interface_model<'a>(
+    path: &Path,
+    glob_declaration: &ModuleInterface<'a>,
+) -> Result<(ModuleInterfaceModel<'a>, Vec<String>)> {
+    let declaration = scanner::scan_module_unit(path)?;
+    let (dir, file_stem, extension) = utils::fs::get_file_details(path)?;
+
+    let declared_deps = glob_declaration.dependencies.iter().flatten();
+    let dependencies = declaration
+        .named_modules_dependencies()
+        .map(|dep| Cow::Owned(dep.clone()))
+        .chain(declared_deps.clone().map(|dep| Cow::Borrowed(*dep)))
+        .collect();
+    let imports = declaration
+        .imports
+        .iter()
+        .cloned()
+        .chain(declared_deps.map(|dep| dep.to_string()))
+        .collect();
+
+    // A partition declared without the `export` keyword is an implementation partition
+    let is_internal_partition = declaration.module_name.is_some() && !declaration.is_exported;
+    let (module_name, partition_name) = match declaration.module_name {
+        Some(module_name) => (module_name, declaration.partition_name),
+        None => match file_stem.split_once('-') {
+            Some((module_name, partition_name)) => {
+                (module_name.to_string(), Some(partition_name.to_string()))
+            }
+            None => (file_stem.clone(), None),
+        },
+    };
+    let partition = partition_name.map(|partition_name| ModulePartitionModel {
+        module: Cow::Owned(module_name.clone()),
+        partition_name: Cow::Owned(partition_name),
+        is_internal_partition,
+    });
+
+    let model = ModuleInterfaceModel {
+        path: dir,
+        file_stem: Cow::Owned(file_stem),
+        extension: Cow::Owned(extension),
+        module_name: Cow::Owned(module_name),
+        partition,
+        dependencies,
+        extra_args: assemble_per_file_args(glob_declaration.extra_args.clone()),
+        owner: None,
+    };
+
+    Ok((model, imports))
+}
+
+/// Builds the model of a module implementation matched by a glob pattern, which depends on the
+/// module that it implements and on the named modules that it imports
+fn infer_module_implementation_model<'a>(
+    path: &Path,
+    glob_declaration: &ModuleImplementation<'a>,
+) -> Result<ModuleImplementationModel<'a>> {
+    let declaration = scanner::scan_module_unit(path)?;
+    let (dir, file_stem, extension) = utils::fs::get_file_details(path)?;
+
+    let implemented_module = declaration.module_name.clone().unwrap_or_else(|| {
+        file_stem
+            .split_once('-')
+            .map_or_else(|| file_stem.clone(), |(module, _)| module.to_string())
+    });
+
+    let mut dependencies: Vec<Cow<'a, str>> = vec![Cow::Owned(implemented_module)];
+    let inferred_deps = declaration
+        .named_modules_dependencies()
+        .map(|dep| Cow::Owned(dep.clone()));
+    let declared_deps = glob_declaration
+        .dependencies
+        .iter()
+        .flatten()
+        .map(|dep| Cow::Borrowed(*dep));
+    for dep in inferred_deps.chain(declared_deps) {
+        if !dependencies.contains(&dep) {
+            dependencies.push(dep);
+        }
+    }
+
+    Ok(ModuleImplementationModel {
+        path: dir,
+        file_stem: Cow::Owned(file_stem),
+        extension: Cow::Owned(extension),
+        dependencies,
+        extra_args: assemble_per_file_args(glob_declaration.extra_args.clone()),
+        owner: None,
+    })
+}
+
+/// Orders the module interfaces matched by the same glob pattern, so every one of them is placed
+/// after the matched interfaces that it imports, keeping their path order otherwise. Import cycles
+/// are left as matched
+fn sort_module_interfaces<'a>(
+    interfaces: Vec<(ModuleInterfaceModel<'a>, Vec<String>)>,
+) -> Vec<ModuleInterfaceModel<'a>> {
+    fn visit(
+        idx: usize,
+        interfaces: &[(ModuleInterfaceModel, Vec<String>)],
+        provided: &[String],
+        visited: &mut [bool],
+        order: &mut Vec<usize>,
+    ) {
+        if visited[idx] {
+            return;
+        }
+        visited[idx] = true;
+
+        for import in &interfaces[idx].1 {
+            provided
+                .iter()
+                .enumerate()
+                .filter(|(dep_idx, module)| *dep_idx != idx && import.eq(*module))
+                .for_each(|(dep_idx, _)| visit(dep_idx, interfaces, provided, visited, order));
+        }
+
+        order.push(idx);
     }
+
+    let provided: Vec<String> = interfaces
+        .iter()
+        .map(|(ifc, _)| ifc.provided_module_name())
+        .collect();
+
+    let mut visited = vec![false; interfaces.len()];
+    let mut order = Vec::with_capacity(interfaces.len());
+    for idx in 0..interfaces.len() {
+        visit(idx, &interfaces, &provided, &mut visited, &mut order);
+    }
+
+    let mut interfaces: Vec<Option<ModuleInterfaceModel>> =
+        interfaces.into_iter().map(|(ifc, _)| Some(ifc)).collect();
+    order
+        .into_iter()
+        .filter_map(|idx| interfaces[idx].take())
+        .collect()
 }
 
 fn assemble_module_interface_model<'a>(
     config: ModuleInterface<'a>,
     base_ifcs_dir_path: &Path,
     code_root: &Path,
-) -> ModuleInterfaceModel<'a> {
+) -> Result<ModuleInterfaceModel<'a>> {
     let file = config.file;
 
     let file_path = get_file_path(code_root, Some(base_ifcs_dir_path), file);
@@ -255,7 +877,7 @@ fn assemble_module_interface_model<'a>(
     } else {
         Path::new(file)
             .file_stem()
-            .unwrap_or_else(|| panic!("Found ill-formed file_stem data for: {file}"))
+            .with_context(|| format!("Found ill-formed file_stem data for: {file}"))?
             .to_string_lossy()
     };
     let dependencies = config
@@ -264,25 +886,27 @@ fn assemble_module_interface_model<'a>(
         .unwrap_or_default();
     let partition = config.partition.map(ModulePartitionModel::from);
 
-    let file_details = utils::fs::get_file_details(&file_path).unwrap_or_else(|_| {
-        panic!("An unexpected error happened getting the file details for {file_path:?}")
-    });
+    let file_details = utils::fs::get_file_details(&file_path).with_context(|| {
+        format!("An unexpected error happened getting the file details for {file_path:?}")
+    })?;
 
-    ModuleInterfaceModel {
+    Ok(ModuleInterfaceModel {
         path: file_details.0,
         file_stem: Cow::from(file_details.1),
         extension: Cow::from(file_details.2),
         module_name,
         partition,
         dependencies,
-    }
+        extra_args: assemble_per_file_args(config.extra_args),
+        owner: None,
+    })
 }
 
 fn assemble_module_implementation_model<'a>(
     config: ModuleImplementation<'a>,
     base_impls_dir_path: &Path,
     code_root: &Path,
-) -> ModuleImplementationModel<'a> {
+) -> Result<ModuleImplementationModel<'a>> {
     let file = config.file;
 
     let mut dependencies = config
@@ -304,100 +928,492 @@ fn assemble_module_implementation_model<'a>(
         }
     }
 
-    let file_details = utils::fs::get_file_details(&file_path).unwrap_or_else(|_| {
-        panic!("An unexpected error happened getting the file details for {file_path:?}")
-    });
+    let file_details = utils::fs::get_file_details(&file_path).with_context(|| {
+        format!("An unexpected error happened getting the file details for {file_path:?}")
+    })?;
 
-    ModuleImplementationModel {
+    Ok(ModuleImplementationModel {
         path: file_details.0,
         file_stem: Cow::Owned(file_details.1),
         extension: Cow::Owned(file_details.2),
         dependencies,
+        extra_args: assemble_per_file_args(config.extra_args),
+        owner: None,
+    })
+}
+
+fn assemble_per_file_args(extra_args: Option<Vec<&str>>) -> Vec<Argument<'_>> {
+    extra_args
+        .unwrap_or_default()
+        .into_iter()
+        .map(Argument::from)
+        .collect()
+}
+
+/// Adds the modules, targets and generators of a workspace member to the ones of the workspace.
+/// The rest of the tables of the member's configuration file are only used when the member is
+/// built on its own
+fn assemble_workspace_member<'a>(
+    member: WorkspaceMember<'a>,
+    modules: &mut ModulesModel<'a>,
+    targets: &mut IndexMap<TargetIdentifier<'a>, TargetModel<'a>>,
+    generators: &mut Vec<GeneratorModel<'a>>,
+) -> Result<()> {
+    let config = member.config;
+    if config.workspace.is_some() {
+        log::warn!(
+            "The [workspace] of the member {:?} is ignored, since nested workspaces aren't supported",
+            member.name
+        );
+    }
+
+    let code_root = member
+        .root
+        .join(config.project.code_root.unwrap_or_default());
+
+    let member_modules = assemble_modules_model(config.modules, &code_root)?;
+    modules.interfaces.extend(member_modules.interfaces);
+    modules
+        .implementations
+        .extend(member_modules.implementations);
+    for sys_module in member_modules.sys_modules {
+        if !modules.sys_modules.contains(&sys_module) {
+            modules.sys_modules.push(sys_module);
+        }
     }
+    for header_unit in member_modules.header_units {
+        if !modules.header_units.contains(&header_unit) {
+            modules.header_units.push(header_unit);
+        }
+    }
+
+    let member_targets = assemble_targets_model(
+        config.targets,
+        Some(&member.name),
+        config.project.name,
+        &code_root,
+        modules,
+    )?;
+    for (identifier, target) in member_targets {
+        targets.insert(identifier, target);
+    }
+
+    generators.extend(assemble_generators_model(
+        config.generators,
+        Some(&member.name),
+        &code_root,
+        targets,
+    )?);
+
+    Ok(())
+}
+
+/// The identifier of a target. The ones declared by a workspace member are qualified with the
+/// name of the member (`member:target`), unless they are already qualified
+fn qualified_target_identifier<'a>(member: Option<&str>, target: &'a str) -> TargetIdentifier<'a> {
+    match member {
+        Some(member) if !target.contains(':') => {
+            TargetIdentifier(Cow::Owned(format!("{member}:{target}")))
+        }
+        _ => TargetIdentifier::from(target),
+    }
+}
+
+/// Sorts the targets, so every target is placed after the ones that it depends on, failing
+/// whenever a dependency doesn't exist, isn't a library, or there's a cycle between them
+fn sort_targets_by_dependencies<'a>(
+    targets: IndexMap<TargetIdentifier<'a>, TargetModel<'a>>,
+) -> Result<IndexMap<TargetIdentifier<'a>, TargetModel<'a>>> {
+    fn visit(
+        idx: usize,
+        targets: &IndexMap<TargetIdentifier, TargetModel>,
+        state: &mut [Option<bool>], // Some(false) while visiting, Some(true) once sorted
+        order: &mut Vec<usize>,
+    ) -> Result<()> {
+        match state[idx] {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                return Err(eyre!(
+                    "There's a dependency cycle involving the target: {:?}",
+                    targets.get_index(idx).map(|(id, _)| id.name())
+                ))
+            }
+            None => state[idx] = Some(false),
+        }
+
+        let (identifier, target) = targets.get_index(idx).unwrap();
+        for dependency in &target.dependencies {
+            let dep_idx = targets.get_index_of(dependency).with_context(|| {
+                format!(
+                    "The target {:?} depends on an unknown target: {:?}",
+                    identifier.name(),
+                    dependency.name()
+                )
+            })?;
+            if targets[dep_idx].kind.eq(&TargetKind::Executable) {
+                return Err(eyre!(
+                    "The target {:?} can't depend on the executable {:?}",
+                    identifier.name(),
+                    dependency.name()
+                ));
+            }
+            visit(dep_idx, targets, state, order)?;
+        }
+
+        state[idx] = Some(true);
+        order.push(idx);
+        Ok(())
+    }
+
+    let mut state = vec![None; targets.len()];
+    let mut order = Vec::with_capacity(targets.len());
+    for idx in 0..targets.len() {
+        visit(idx, &targets, &mut state, &mut order)?;
+    }
+
+    let mut targets: Vec<Option<(TargetIdentifier, TargetModel)>> =
+        targets.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .filter_map(|idx| targets[idx].take())
+        .collect())
 }
 
 fn assemble_targets_model<'a>(
     targets: IndexMap<&'a str, TargetAttribute<'a>>,
+    member: Option<&str>,
     project_name: &'a str,
     code_root: &Path,
-) -> IndexMap<TargetIdentifier<'a>, TargetModel<'a>> {
+    modules: &mut ModulesModel<'a>,
+) -> Result<IndexMap<TargetIdentifier<'a>, TargetModel<'a>>> {
     targets
         .into_iter()
-        .map(|(k, v)| {
-            (
-                TargetIdentifier(Cow::Borrowed(k)),
-                assemble_target_model(v, project_name, code_root),
-            )
+        .map(|(k, mut v)| {
+            let identifier = qualified_target_identifier(member, k);
+            assemble_target_owned_modules(&identifier, v.modules.take(), code_root, modules)
+                .with_context(|| format!("Unable to assemble the modules of the target: {k}"))?;
+            let target_model =
+                assemble_target_model(&identifier, member, v, project_name, code_root)
+                    .with_context(|| format!("Unable to assemble the target: {k}"))?;
+            Ok((identifier, target_model))
         })
         .collect()
 }
 
+/// Assembles the module units declared under the `modules` table of a target, appending them to
+/// the ones of the project, marked as owned by the target. They are only visible to the target
+/// that owns them, and they are placed after the project ones, since they may depend on them
+fn assemble_target_owned_modules<'a>(
+    target_identifier: &TargetIdentifier<'a>,
+    config: Option<ModulesAttribute<'a>>,
+    code_root: &Path,
+    modules: &mut ModulesModel<'a>,
+) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+
+    if config.sys_modules.is_some() {
+        log::warn!(
+            "The `sys_modules` of [targets.{}.modules] are ignored. Declare them on the [modules] table instead",
+            target_identifier.0
+        );
+    }
+
+    let owned = assemble_modules_model(Some(config), code_root)?;
+    let owner = Some(target_identifier.0.clone());
+
+    modules.interfaces.extend(
+        owned
+            .interfaces
+            .into_iter()
+            .map(|ifc| ModuleInterfaceModel {
+                owner: owner.clone(),
+                ..ifc
+            }),
+    );
+    modules
+        .implementations
+        .extend(
+            owned
+                .implementations
+                .into_iter()
+                .map(|imp| ModuleImplementationModel {
+                    owner: owner.clone(),
+                    ..imp
+                }),
+        );
+
+    Ok(())
+}
+
 fn assemble_target_model<'a>(
+    target_identifier: &TargetIdentifier,
+    member: Option<&str>,
     target_config: TargetAttribute<'a>,
     project_name: &'a str,
     code_root: &Path,
-) -> TargetModel<'a> {
-    let sources = target_config
-        .sources
+) -> Result<TargetModel<'a>> {
+    let target_name = &target_identifier.0;
+    let exclusions = ExclusionPatterns::new(code_root, &target_config.exclude.unwrap_or_default())?;
+    let sources = get_sources_for_target(target_config.sources, &exclusions, code_root)?;
+
+    let compile_args = target_config
+        .compile_args
+        .unwrap_or_default()
         .into_iter()
-        .map(Cow::Borrowed)
+        .map(Argument::from)
         .collect();
+    let extra_args = assemble_extra_args(
+        target_config.extra_args,
+        &format!("[targets.{target_name}]"),
+    );
 
-    let sources = get_sources_for_target(sources, code_root);
+    let options = assemble_compile_options(
+        target_config.defines,
+        target_config.include_dirs,
+        target_config.system_include_dirs,
+        target_config.warnings,
+        target_config.optimization,
+        target_config.debug_info,
+        code_root,
+    );
 
-    let extra_args = target_config
-        .extra_args
-        .map(|args| args.iter().map(|arg| Argument::from(*arg)).collect())
-        .unwrap_or_default();
+    let link_options = LinkOptions {
+        link_libraries: target_config
+            .link_libraries
+            .unwrap_or_default()
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+        link_dirs: target_config
+            .link_dirs
+            .unwrap_or_default()
+            .into_iter()
+            .map(|dir| get_file_path(code_root, None, dir))
+            .collect(),
+        link_args: target_config
+            .link_args
+            .unwrap_or_default()
+            .into_iter()
+            .map(Argument::from)
+            .collect(),
+        frameworks: target_config
+            .frameworks
+            .unwrap_or_default()
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+    };
+
+    let pch = target_config
+        .pch
+        .map(|header| assemble_pch_model(header, code_root))
+        .transpose()
+        .with_context(|| format!("Unable to resolve the `pch` of [targets.{target_name}]"))?;
 
-    TargetModel {
+    Ok(TargetModel {
         output_name: Cow::Borrowed(target_config.output_name.unwrap_or(project_name)),
         sources,
+        compile_args,
         extra_args,
         kind: target_config.kind.unwrap_or_default(),
+        options,
+        link_options,
+        pch,
+        hooks: BuildHooks::new(target_config.pre_build, target_config.post_build),
+        dependencies: target_config
+            .dependencies
+            .unwrap_or_default()
+            .into_iter()
+            .map(|dependency| qualified_target_identifier(member, dependency))
+            .collect(),
         enabled_for_current_program_iteration: true, // NOTE: For now, it can only be manually
                                                      // disabled by cli args
+    })
+}
+
+/// Maps the header declared as the precompiled header of a target, which must exist on the fs
+fn assemble_pch_model<'a>(header: &str, code_root: &Path) -> Result<SourceFile<'a>> {
+    let file_path = get_file_path(code_root, None, header);
+    if !file_path.exists() {
+        return Err(eyre!("The precompiled header {file_path:?} doesn't exist"));
     }
+
+    let (path, file_stem, extension) = utils::fs::get_file_details(&file_path)?;
+    Ok(SourceFile {
+        path,
+        file_stem: Cow::Owned(file_stem),
+        extension: Cow::Owned(extension),
+        extra_args: Vec::default(),
+    })
 }
 
-/// Utilery function to map all the source files declared on the [`ZorkConfigFile::targets`]
-/// attribute to the domain model entity, including resolving any [`GlobPattern`] declared as
-/// any file on the input collection
-fn get_sources_for_target<'a>(srcs: Vec<Cow<str>>, code_root: &Path) -> SourceSet<'a> {
-    let sources = srcs
-        .iter()
-        .map(|src| {
-            let target_src = get_file_path(code_root, None, src.as_ref());
-            if src.contains('*') {
-                Source::Glob(GlobPattern(target_src))
-            } else {
-                Source::File(target_src)
+/// Maps the rules declared under the `[[generators]]` tables. The generated C++ source files are
+/// appended to the sources of the target declared on the rule, since they don't exist yet when
+/// the targets are assembled
+fn assemble_generators_model<'a>(
+    generators: Option<Vec<GeneratorAttribute<'a>>>,
+    member: Option<&str>,
+    code_root: &Path,
+    targets: &mut IndexMap<TargetIdentifier<'a>, TargetModel<'a>>,
+) -> Result<Vec<GeneratorModel<'a>>> {
+    generators
+        .unwrap_or_default()
+        .into_iter()
+        .map(|generator| {
+            let err_msg = || format!("Unable to assemble the generator: {:?}", generator.command);
+
+            if generator.outputs.is_empty() {
+                return Err(eyre!("A generator must declare at least one output"))
+                    .with_context(err_msg);
             }
+
+            let inputs = generator
+                .inputs
+                .iter()
+                .flatten()
+                .map(|input| {
+                    let path = get_file_path(code_root, None, input);
+                    if input.contains('*') {
+                        Source::Glob(GlobPattern(path)).paths()
+                    } else {
+                        Source::File(path).paths()
+                    }
+                })
+                .collect::<Result<Vec<_>>>()
+                .with_context(err_msg)?
+                .into_iter()
+                .flatten()
+                .collect();
+
+            let outputs = generator
+                .outputs
+                .iter()
+                .map(|output| get_file_path(code_root, None, output))
+                .collect::<Vec<PathBuf>>();
+
+            let target = generator
+                .target
+                .map(|target| qualified_target_identifier(member, target));
+            if let Some(target_identifier) = &target {
+                let target_model = targets.get_mut(target_identifier).with_context(|| {
+                    format!(
+                        "The generator {:?} declares an unknown target: {:?}",
+                        generator.command,
+                        target_identifier.name()
+                    )
+                })?;
+                append_generated_sources(&outputs, target_model)?;
+            }
+
+            Ok(GeneratorModel {
+                command: generator.command,
+                inputs,
+                outputs,
+                target,
+            })
         })
-        .flat_map(|source| {
-            source
-                .paths()
-                .expect("Error getting the declared paths for the source files")
-        })
-        .map(|pb| {
-            let file_details = utils::fs::get_file_details(&pb).unwrap_or_else(|_| {
-                panic!("An unexpected error happened getting the file details for {pb:?}")
+        .collect()
+}
+
+/// Adds the generated files that are C++ source files to the sources of the given target,
+/// unless they were already declared by the user
+fn append_generated_sources(outputs: &[PathBuf], target_model: &mut TargetModel) -> Result<()> {
+    for output in outputs {
+        let is_source = output
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| GENERATED_SOURCES_EXTENSIONS.contains(&ext));
+        let already_declared = target_model
+            .sources
+            .as_slice()
+            .iter()
+            .any(|sf| sf.path().eq(output));
+
+        if is_source && !already_declared {
+            let (path, file_stem, extension) = utils::fs::get_file_details(output)?;
+            target_model.sources.push(SourceFile {
+                path,
+                file_stem: Cow::Owned(file_stem),
+                extension: Cow::Owned(extension),
+                extra_args: Vec::default(),
             });
-            SourceFile {
+        }
+    }
+
+    Ok(())
+}
+
+/// Utilery function to map all the source files declared on the [`ZorkConfigFile::targets`]
+/// attribute to the domain model entity, including resolving any [`GlobPattern`] declared as
+/// any file on the input collection.
+///
+/// When the same file is matched by more than one entry, the last one wins, so a per-file
+/// declaration can override a glob pattern. Files declared as `exclude_from_build`, or matched
+/// by any of the target's [`ExclusionPatterns`], are removed from the set, regardless of the
+/// entry that matched them
+fn get_sources_for_target<'a>(
+    srcs: Vec<TargetSource<'a>>,
+    exclusions: &ExclusionPatterns,
+    code_root: &Path,
+) -> Result<SourceSet<'a>> {
+    let mut sources: Vec<SourceFile<'a>> = Vec::with_capacity(srcs.len());
+    let mut excluded: Vec<PathBuf> = Vec::new();
+
+    for src in srcs {
+        let declared = src.file();
+        let target_src = get_file_path(code_root, None, declared);
+        let source = if declared.contains('*') {
+            Source::Glob(GlobPattern(target_src))
+        } else {
+            Source::File(target_src)
+        };
+
+        let is_excluded = src.is_excluded_from_build();
+        let extra_args = match src {
+            TargetSource::Detailed(details) => assemble_per_file_args(details.extra_args),
+            TargetSource::Path(_) => Vec::with_capacity(0),
+        };
+
+        for pb in source.paths()? {
+            if exclusions.excludes(&pb) {
+                continue;
+            }
+
+            let file_details = utils::fs::get_file_details(&pb)?;
+            let source_file = SourceFile {
                 path: file_details.0,
                 file_stem: Cow::Owned(file_details.1),
                 extension: Cow::Owned(file_details.2),
+                extra_args: extra_args.clone(),
+            };
+
+            if is_excluded {
+                excluded.push(source_file.path());
+            } else if let Some(declared_before) = sources
+                .iter_mut()
+                .find(|sf| sf.path().eq(&source_file.path()))
+            {
+                *declared_before = source_file;
+            } else {
+                sources.push(source_file);
             }
-        })
-        .collect();
+        }
+    }
 
-    SourceSet::new(sources)
+    sources.retain(|sf| !excluded.contains(&sf.path()));
+    Ok(SourceSet::new(sources))
 }
 
 /// Helper to build the file path of a [`TranslationUnit`]
 /// Parameter *reduction* is any intermediate path offered by configuration that lives after the
 /// code root and before the file itself
-fn get_file_path(code_root: &Path, reduction: Option<&Path>, declared_file_path: &str) -> PathBuf {
+pub(crate) fn get_file_path(
+    code_root: &Path,
+    reduction: Option<&Path>,
+    declared_file_path: &str,
+) -> PathBuf {
     let declared_file_path = Path::new(declared_file_path);
     if declared_file_path.is_absolute() {
         declared_file_path.to_owned()
@@ -440,9 +1456,16 @@ mod test {
                     path: abs_path_for_mock.clone(),
                     file_stem: Cow::Borrowed("main"),
                     extension: Cow::Borrowed("cpp"),
+                    extra_args: vec![],
                 }]),
+                compile_args: vec![],
                 extra_args: vec!["-Werr".into()],
                 kind: TargetKind::Executable,
+                options: CompileOptions::default(),
+                link_options: LinkOptions::default(),
+                pch: None,
+                hooks: BuildHooks::default(),
+                dependencies: vec![],
                 enabled_for_current_program_iteration: true,
             },
         );
@@ -454,9 +1477,16 @@ mod test {
                     path: abs_path_for_mock.clone(),
                     file_stem: Cow::Borrowed("tests_main"),
                     extension: Cow::Borrowed("cpp"),
+                    extra_args: vec![],
                 }]),
+                compile_args: vec![],
                 extra_args: vec![],
                 kind: TargetKind::Executable,
+                options: CompileOptions::default(),
+                link_options: LinkOptions::default(),
+                pch: None,
+                hooks: BuildHooks::default(),
+                dependencies: vec![],
                 enabled_for_current_program_iteration: true,
             },
         );
@@ -467,6 +1497,7 @@ mod test {
                 authors: vec!["zerodaycode.gz@gmail.com".into()],
                 compilation_db: true,
                 code_root: None,
+                hooks: BuildHooks::default(),
             },
             compiler: CompilerModel {
                 cpp_compiler: CppCompiler::CLANG,
@@ -474,7 +1505,12 @@ mod test {
                 cpp_standard: LanguageLevel::CPP2B,
                 std_lib: Some(StdLib::LIBCPP),
                 std_lib_installed_dir: None,
+                compile_args: vec![],
+                link_args: vec![],
                 extra_args: vec![Argument::from("-Wall")],
+                launcher: None,
+                toolchain: ToolchainModel::default(),
+                options: CompileOptions::default(),
             },
             build: BuildModel {
                 output_dir: abs_path_for_mock.clone(),
@@ -489,6 +1525,8 @@ mod test {
                         module_name: "maths".into(),
                         partition: None,
                         dependencies: vec![],
+                        extra_args: vec![],
+                        owner: None,
                     },
                     ModuleInterfaceModel {
                         path: abs_path_for_mock.join("ifcs"),
@@ -497,6 +1535,8 @@ mod test {
                         module_name: "maths".into(),
                         partition: None,
                         dependencies: vec![],
+                        extra_args: vec![],
+                        owner: None,
                     },
                 ],
                 base_impls_dir: Cow::Borrowed(Path::new("srcs")),
@@ -506,24 +1546,336 @@ mod test {
                         file_stem: Cow::from("maths"),
                         extension: Cow::from("cpp"),
                         dependencies: vec!["maths".into()],
+                        extra_args: vec![],
+                        owner: None,
                     },
                     ModuleImplementationModel {
                         path: abs_path_for_mock.join("srcs"),
                         file_stem: Cow::from("some_module_impl"),
                         extension: Cow::from("cpp"),
                         dependencies: vec!["iostream".into()],
+                        extra_args: vec![],
+                        owner: None,
                     },
                 ],
                 sys_modules: vec![SystemModule {
                     file_stem: Cow::Borrowed("iostream"),
                     ..Default::default()
                 }],
+                header_units: vec![],
             },
             targets,
+            generators: vec![],
         };
 
         assert_eq!(model.unwrap(), expected);
 
         Ok(())
     }
+
+    #[test]
+    fn test_output_dir_of_cross_targets() -> Result<()> {
+        let root = Path::new("/proj");
+        let config = |toml: &'static str| -> Result<ZorkConfigFile<'static>> {
+            Ok(config_file::zork_cfg_from_file(toml)?)
+        };
+
+        let host = config(
+            "[project]\nname = 'p'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+             [targets.app]\nsources = ['main.cpp']\n",
+        )?;
+        assert_eq!(resolve_output_dir(&host, root), root.join("out"));
+
+        let cross = config(
+            "[project]\nname = 'p'\n[compiler]\ncpp_compiler = 'clang'\ncpp_standard = '20'\n\
+             [build]\noutput_dir = './build'\n[toolchain]\ntarget = 'aarch64-linux-gnu'\n\
+             [targets.app]\nsources = ['main.cpp']\n",
+        )?;
+        assert_eq!(
+            resolve_output_dir(&cross, root),
+            root.join("build").join("aarch64-linux-gnu")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_modules_declared_by_glob() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let code_root = temp.path();
+        let ifcs_dir = code_root.join("ifc");
+        std::fs::create_dir_all(ifcs_dir.join("math"))?;
+        std::fs::write(
+            ifcs_dir.join("a_math.cppm"),
+            "export module math;\nexport import :numbers;\nimport geometry;\n",
+        )?;
+        std::fs::write(
+            ifcs_dir.join("math").join("numbers.cppm"),
+            "export module math:numbers;\n",
+        )?;
+        std::fs::write(ifcs_dir.join("geometry.cppm"), "export module geometry;\n")?;
+        std::fs::write(ifcs_dir.join("shapes-circle.cppm"), "")?;
+
+        let config: ModulesAttribute = toml::from_str(
+            r#"
+            base_ifcs_dir = 'ifc'
+            interfaces = [ '**/*.cppm', { file = 'geometry.cppm', extra_args = ['-w'] } ]
+            "#,
+        )?;
+        let modules = assemble_modules_model(Some(config), code_root)?;
+
+        let names: Vec<(&str, Option<&str>)> = modules
+            .interfaces
+            .iter()
+            .map(|ifc| {
+                (
+                    ifc.file_stem.as_ref(),
+                    ifc.partition.as_ref().map(|p| p.partition_name.as_ref()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("numbers", Some("numbers")),
+                ("a_math", None),
+                ("shapes-circle", Some("circle")),
+                ("geometry", None),
+            ]
+        );
+
+        let math = &modules.interfaces[1];
+        assert_eq!(math.module_name, "math");
+        assert_eq!(math.dependencies, vec![Cow::Borrowed("geometry")]);
+
+        let geometry = &modules.interfaces[3];
+        assert_eq!(geometry.extra_args, vec![Argument::from("-w")]);
+
+        let circle = &modules.interfaces[2];
+        assert_eq!(circle.module_name, "shapes");
+        assert!(!circle.partition.as_ref().unwrap().is_internal_partition);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_header_units_declared_by_glob() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let code_root = temp.path();
+        let include_dir = code_root.join("include");
+        std::fs::create_dir_all(&include_dir)?;
+        for header in ["utils.h", "utils.hpp", "config.h"] {
+            std::fs::write(include_dir.join(header), "#pragma once\n")?;
+        }
+
+        let config: ModulesAttribute =
+            toml::from_str("header_units = [ 'include/config.h', 'include/*.h*' ]")?;
+        let modules = assemble_modules_model(Some(config), code_root)?;
+
+        let filenames: Vec<String> = modules
+            .header_units
+            .iter()
+            .map(|header_unit| header_unit.filename())
+            .collect();
+        assert_eq!(filenames, vec!["config.h", "utils.h", "utils.hpp"]);
+        assert_eq!(modules.header_units[0].path(), include_dir.join("config.h"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generators_add_their_sources_to_the_target() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let code_root = temp.path();
+        std::fs::create_dir_all(code_root.join("proto"))?;
+        std::fs::write(code_root.join("proto").join("messages.proto"), "")?;
+
+        let config: ZorkConfigFile = toml::from_str(
+            r#"
+            [project]
+            name = 'generated'
+
+            [compiler]
+            cpp_compiler = 'gcc'
+            cpp_standard = '20'
+
+            [targets.app]
+            sources = [ 'main.cpp' ]
+
+            [[generators]]
+            command = 'protoc --cpp_out=${out_dir}/gen proto/messages.proto'
+            inputs = [ 'proto/*.proto' ]
+            outputs = [ '${out_dir}/gen/messages.pb.cc', '${out_dir}/gen/messages.pb.h' ]
+            target = 'app'
+            "#,
+        )?;
+        let cli_args = CliArgs::parse_from(["", "build"]);
+        let variables = config_variables(&config, &cli_args, code_root)?;
+        let mut expanded_values = expansion::ExpandedValues::default();
+        let config = expansion::expand_config_file(config, &variables, &mut expanded_values)?;
+        let model = build_model(config, &cli_args, code_root)?;
+
+        let gen_dir = code_root.join("out").join("gen");
+        let generator = &model.generators[0];
+        assert_eq!(
+            generator.command,
+            format!(
+                "protoc --cpp_out={} proto/messages.proto",
+                gen_dir.display()
+            )
+        );
+        assert_eq!(
+            generator.inputs,
+            vec![code_root.join("proto").join("messages.proto")]
+        );
+        assert_eq!(
+            generator.outputs,
+            vec![
+                gen_dir.join("messages.pb.cc"),
+                gen_dir.join("messages.pb.h")
+            ]
+        );
+
+        let sources: Vec<PathBuf> = model.targets[&TargetIdentifier::from("app")]
+            .sources
+            .as_slice()
+            .iter()
+            .map(|source| source.path())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![code_root.join("main.cpp"), gen_dir.join("messages.pb.cc")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_files_filtered_by_name() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        for cfg in ["zork_linux.toml", "zork_windows.toml"] {
+            std::fs::write(root.join(cfg), "")?;
+        }
+
+        let found = find_config_files(root, &Some(String::from("linux")))?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, root.join("zork_linux.toml"));
+
+        let err = find_config_files(root, &Some(String::from("macos"))).unwrap_err();
+        assert!(err.to_string().contains("zork_windows.toml"));
+
+        let explicit = load_config_files(
+            Path::new("."),
+            &[root.join("zork_windows.toml").display().to_string()],
+        )?;
+        assert_eq!(explicit[0].path, root.join("zork_windows.toml"));
+        assert!(load_config_files(
+            Path::new("."),
+            &[root.join("zork.toml").display().to_string()]
+        )
+        .is_err());
+
+        // The relative ones are resolved against the project root, not the working directory
+        let relative = load_config_files(root, &[String::from("zork_linux.toml")])?;
+        assert_eq!(relative[0].path, root.join("zork_linux.toml"));
+        assert!(load_config_files(Path::new("."), &[String::from("zork_linux.toml")]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspace_members_targets_and_dependencies() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        for member in ["libs/core", "libs/utils", "apps/app"] {
+            std::fs::create_dir_all(root.join(member))?;
+        }
+        std::fs::write(
+            root.join("libs/core/zork.toml"),
+            "[project]\nname = 'core'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+            [targets.corelib]\nsources = ['core.cpp']\nkind = 'static_lib'\ndependencies = ['utils:utils']",
+        )?;
+        std::fs::write(
+            root.join("libs/utils/zork.toml"),
+            "[project]\nname = 'utils'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+            [targets.utils]\nsources = ['utils.cpp']\nkind = 'static_lib'",
+        )?;
+        std::fs::write(
+            root.join("apps/app/zork.toml"),
+            "[project]\nname = 'app'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+            [targets.main]\nsources = ['main.cpp']\ndependencies = ['core:corelib']",
+        )?;
+
+        let raw_root =
+            "[project]\nname = 'ws'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+            [workspace]\nmembers = ['apps/app', 'libs/*']";
+        let config = config_file::zork_cfg_from_file(raw_root)?;
+        let members_files = find_workspace_members(root, config.workspace.as_ref().unwrap())?;
+        let names: Vec<&str> = members_files.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "core", "utils"]);
+
+        let members_raw: Vec<String> = members_files
+            .iter()
+            .map(|m| std::fs::read_to_string(&m.config_file.path))
+            .collect::<std::io::Result<_>>()?;
+        let members = members_files
+            .iter()
+            .zip(members_raw.iter())
+            .map(|(m, raw)| {
+                Ok(WorkspaceMember {
+                    name: m.name.clone(),
+                    root: m.root.clone(),
+                    config: config_file::zork_cfg_from_file(raw)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let cli_args = CliArgs::parse_from(["", "build"]);
+        let model = build_workspace_model(config, members, &cli_args, root)?;
+
+        // The dependencies are placed before the targets that depend on them
+        let targets: Vec<&str> = model.targets.keys().map(|id| id.name()).collect();
+        assert_eq!(targets, vec!["utils:utils", "core:corelib", "app:main"]);
+        assert_eq!(
+            model.targets[&TargetIdentifier::from("core:corelib")]
+                .sources
+                .as_slice()[0]
+                .path(),
+            root.join("libs/core/core.cpp")
+        );
+        assert_eq!(
+            TargetIdentifier::from("core:corelib").as_path(),
+            Path::new("core").join("corelib")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generator_with_unknown_target() -> Result<()> {
+        let config: ZorkConfigFile = toml::from_str(
+            r#"
+            [project]
+            name = 'generated'
+
+            [compiler]
+            cpp_compiler = 'gcc'
+            cpp_standard = '20'
+
+            [targets.app]
+            sources = [ 'main.cpp' ]
+
+            [[generators]]
+            command = 'touch gen.cpp'
+            outputs = [ 'gen.cpp' ]
+            target = 'lib'
+            "#,
+        )?;
+        let cli_args = CliArgs::parse_from(["", "build"]);
+
+        assert!(build_model(config, &cli_args, Path::new(".")).is_err());
+
+        Ok(())
+    }
 }
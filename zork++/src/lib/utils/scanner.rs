@@ -0,0 +1,195 @@
+//! Lightweight scanner over the contents of the C++ module units, for inferring the
+//! data that the user didn't declare explicitly on the configuration file (ex: the ones
+//! matched by a glob pattern)
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use color_eyre::{eyre::Context, Result};
+use regex::Regex;
+
+static MODULE_DECL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(export\s+)?module\s+([\w.]+)\s*(?::\s*([\w.]+))?\s*;").unwrap()
+});
+static IMPORT_DECL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(?:export\s+)?import\s+([\w.]+)?\s*(?::\s*([\w.]+))?\s*;").unwrap()
+});
+static HEADER_IMPORT_DECL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^\s*(?:export\s+)?import\s*(?:<([^>]+)>|"([^"]+)")\s*;"#).unwrap()
+});
+static INCLUDE_DECL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^\s*#\s*include\s*"([^"]+)""#).unwrap());
+
+/// The module related declarations found on a C++ translation unit
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ModuleUnitDeclaration {
+    /// The name of the module declared by the unit, if any
+    pub module_name: Option<String>,
+    /// The partition name, when the unit declares a module partition
+    pub partition_name: Option<String>,
+    /// Whenever the module declaration is exported (`export module ...`)
+    pub is_exported: bool,
+    /// The named modules imported by the unit. Partitions are written fully qualified
+    /// with the name of their module (ex: `math:numbers`)
+    pub imports: Vec<String>,
+    /// The headers imported as header units, written as they appear between the angle
+    /// brackets or the quotes (ex: `vector` for `import <vector>;`)
+    pub header_imports: Vec<String>,
+}
+
+impl ModuleUnitDeclaration {
+    /// Whenever the unit is an interface or implementation partition
+    pub fn is_partition(&self) -> bool {
+        self.partition_name.is_some()
+    }
+
+    /// The imported named modules which aren't partitions nor the modular standard library,
+    /// that is, the ones expected to be built by Zork++ as another module interface
+    pub fn named_modules_dependencies(&self) -> impl Iterator<Item = &String> {
+        self.imports.iter().filter(|import| {
+            !import.contains(':') && !matches!(import.as_str(), "std" | "std.compat")
+        })
+    }
+}
+
+/// Reads the file at the given path and scans its module declarations
+pub fn scan_module_unit(path: &Path) -> Result<ModuleUnitDeclaration> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Unable to read the module unit: {path:?}"))?;
+    Ok(scan_module_declarations(&contents))
+}
+
+/// Scans the module declaration, the named module imports and the header unit imports
+/// (`import <vector>;`) of the given contents of a C++ translation unit
+pub fn scan_module_declarations(contents: &str) -> ModuleUnitDeclaration {
+    let mut declaration = ModuleUnitDeclaration::default();
+
+    if let Some(captures) = MODULE_DECL.captures(contents) {
+        declaration.is_exported = captures.get(1).is_some();
+        declaration.module_name = captures.get(2).map(|m| m.as_str().to_string());
+        declaration.partition_name = captures.get(3).map(|p| p.as_str().to_string());
+    }
+
+    for captures in IMPORT_DECL.captures_iter(contents) {
+        let import = match (captures.get(1), captures.get(2)) {
+            (Some(module), None) => module.as_str().to_string(),
+            (None, Some(partition)) => match &declaration.module_name {
+                Some(module) => format!("{module}:{}", partition.as_str()),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        if !declaration.imports.contains(&import) {
+            declaration.imports.push(import);
+        }
+    }
+
+    for captures in HEADER_IMPORT_DECL.captures_iter(contents) {
+        if let Some(header) = captures.get(1).or(captures.get(2)) {
+            declaration.header_imports.push(header.as_str().to_string());
+        }
+    }
+
+    declaration
+}
+
+/// Collects the files included through quoted `#include` directives by the given file, and
+/// transitively by the included ones. They are looked up on the directory of the includer first,
+/// and then on the given include directories. The ones that can't be found are ignored, since
+/// they are expected to be resolved by the compiler through other means
+pub fn scan_included_files(path: &Path, include_dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut included: Vec<PathBuf> = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+
+    while let Some(file) = pending.pop() {
+        let contents = std::fs::read_to_string(&file)
+            .with_context(|| format!("Unable to read the included file: {file:?}"))?;
+        let includer_dir = file.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        for captures in INCLUDE_DECL.captures_iter(&contents) {
+            let header = &captures[1];
+            let resolved = std::iter::once(&includer_dir)
+                .chain(include_dirs.iter())
+                .map(|dir| dir.join(header))
+                .find(|candidate| candidate.is_file());
+
+            if let Some(resolved) = resolved.filter(|r| !included.contains(r) && r.ne(path)) {
+                included.push(resolved.clone());
+                pending.push(resolved);
+            }
+        }
+    }
+
+    Ok(included)
+}
+
+#[cfg(test)]
+mod scanner_tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_module_interface_partition() {
+        let contents = r#"
+            module;
+            #include <cstdint>
+            export module math:numbers;
+
+            import std;
+            import <vector>;
+            import "config.h";
+            export import :details;
+            import geometry;
+        "#;
+
+        let declaration = scan_module_declarations(contents);
+        assert_eq!(declaration.module_name.as_deref(), Some("math"));
+        assert_eq!(declaration.partition_name.as_deref(), Some("numbers"));
+        assert!(declaration.is_exported);
+        assert!(declaration.is_partition());
+        assert_eq!(declaration.imports, vec!["std", "math:details", "geometry"]);
+        assert_eq!(declaration.header_imports, vec!["vector", "config.h"]);
+        assert_eq!(
+            declaration.named_modules_dependencies().collect::<Vec<_>>(),
+            vec!["geometry"]
+        );
+    }
+
+    #[test]
+    fn test_scan_module_implementation_unit() {
+        let declaration = scan_module_declarations("module math.core;\nimport fmt;\n");
+        assert_eq!(declaration.module_name.as_deref(), Some("math.core"));
+        assert_eq!(declaration.partition_name, None);
+        assert!(!declaration.is_exported);
+        assert_eq!(declaration.imports, vec!["fmt"]);
+
+        assert_eq!(
+            scan_module_declarations("int main() { return 0; }"),
+            ModuleUnitDeclaration::default()
+        );
+    }
+
+    #[test]
+    fn test_scan_included_files() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        let include_dir = root.join("include");
+        std::fs::create_dir_all(&include_dir)?;
+
+        std::fs::write(
+            root.join("pch.h"),
+            "#include <vector>\n#include \"config.h\"\n# include \"utils.h\"\n#include \"missing.h\"\n",
+        )?;
+        std::fs::write(root.join("config.h"), "#include \"pch.h\"\n")?;
+        std::fs::write(include_dir.join("utils.h"), "#include \"config.h\"\n")?;
+
+        let included =
+            scan_included_files(&root.join("pch.h"), std::slice::from_ref(&include_dir))?;
+        assert_eq!(
+            included,
+            vec![root.join("config.h"), include_dir.join("utils.h")]
+        );
+
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ use std::process::Command;
 ///       - math2.<extension -> .cpp, .cc, ...>
 ///    - main.cpp
 ///    - test/
+///       - tests_main.cpp
 ///    - dependencies/
 ///
 /// Note that this template is just a pnemonic. Any `C++` project can adhere to
@@ -40,6 +41,10 @@ pub fn create_templated_project(
     let path_test = project_root.join("test");
     let path_dependencies = project_root.join("deps");
 
+    if compiler.eq(&CppCompiler::CLANG_CL) {
+        bail!("There's no project template available for clang-cl yet")
+    }
+
     check_project_root_available(&project_root)?;
 
     utils::fs::create_directory(&project_root)?;
@@ -88,6 +93,11 @@ pub fn create_templated_project(
 
     utils::fs::create_file(&path_src, "math.cpp", resources::SRC_MOD_FILE.as_bytes())?;
     utils::fs::create_file(&path_src, "math2.cpp", resources::SRC_MOD_FILE_2.as_bytes())?;
+    utils::fs::create_file(
+        &path_test,
+        "tests_main.cpp",
+        resources::TESTS_MAIN.as_bytes(),
+    )?;
 
     let template = match compiler {
         CppCompiler::MSVC => match template {
@@ -102,6 +112,7 @@ pub fn create_templated_project(
             TemplateValues::BASIC => resources::CONFIG_FILE_BASIC_GCC,
             TemplateValues::PARTITIONS => resources::CONFIG_FILE_GCC,
         },
+        CppCompiler::CLANG_CL => unreachable!(),
     }
     .replace("<project_name>", project_name);
 
@@ -8,6 +8,7 @@ pub const SRC_MOD_FILE: &str = include_str!("math.cpp");
 pub const SRC_MOD_FILE_2: &str = include_str!("math2.cpp");
 pub const MAIN: &str = include_str!("main.cpp");
 pub const MAIN_BASIC: &str = include_str!("main_basic.cpp");
+pub const TESTS_MAIN: &str = include_str!("tests_main.cpp");
 
 pub const CONFIG_FILE: &str = include_str!("zork_example.toml");
 pub const CONFIG_FILE_GCC: &str = include_str!("zork_example_gcc.toml");
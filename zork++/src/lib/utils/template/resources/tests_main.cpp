@@ -0,0 +1,10 @@
+import math;
+
+int main() {
+    if (math::sum(2, 8) != 10 || math::subtract(8, 2) != 6)
+        return 1;
+    if (math::multiply(2, 8) != 16 || math::divide(2, 2) != 1)
+        return 1;
+
+    return 0;
+}
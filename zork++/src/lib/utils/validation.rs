@@ -0,0 +1,1017 @@
+//! Validation of the configuration files before building the project model out of them.
+//!
+//! Every problem found on the configuration files is collected as a [`Diagnostic`], located on
+//! the configuration file that declares the offending key or value, and all of them are reported
+//! at once:
+//! - keys that aren't attributes of the table where they are declared
+//! - module units, headers and sources declared one by one which doesn't exist
+//! - module interfaces that provides the same module more than once
+//! - dependencies on modules that aren't declared on any configuration file
+//! - targets of the same kind producing the same output
+//!
+//! The dependencies of the module units are only checked when no module interface is declared
+//! through a glob pattern, since the modules provided by the matched files are only known by
+//! scanning them
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use toml::value::Table;
+use toml::{Spanned, Value};
+
+use crate::config_file::build::BuildAttribute;
+use crate::config_file::compiler::CompilerAttribute;
+use crate::config_file::generator::GeneratorAttribute;
+use crate::config_file::modules::{
+    ModuleImplementation, ModuleInterface, ModulePartition, ModulesAttribute,
+};
+use crate::config_file::project::ProjectAttribute;
+use crate::config_file::target::{SourceFileAttribute, TargetAttribute};
+use crate::config_file::toolchain::ToolchainAttribute;
+use crate::config_file::workspace::WorkspaceAttribute;
+use crate::config_file::ZorkConfigFile;
+
+use super::conditionals::{self, CONDITIONAL_SECTIONS};
+use super::inheritance::{ResolvedConfigFile, REPLACE_MARKER};
+use super::reader::get_file_path;
+
+/// The modules of the modular standard library, which are never declared on the configuration files
+const STD_MODULES: [&str; 2] = ["std", "std.compat"];
+
+/// A problem found on a configuration file
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub file: PathBuf,
+    /// The location of the problem on the file, when it could be found
+    pub span: Option<Span>,
+    pub message: String,
+}
+
+/// A location on a configuration file, starting at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.file.display(),
+                span.line,
+                span.column,
+                self.message
+            ),
+            None => write!(f, "{}: {}", self.file.display(), self.message),
+        }
+    }
+}
+
+/// The locations of the keys and values of a configuration file and of the ones that it
+/// inherits from, where the diagnostics are located
+#[derive(Debug)]
+pub struct SourceFiles {
+    files: Vec<(PathBuf, SpanIndex)>,
+}
+
+impl SourceFiles {
+    pub fn new(config_file: &Path, resolved_file: &ResolvedConfigFile) -> Self {
+        let files = std::iter::once(config_file)
+            .chain(resolved_file.inherited.iter().map(|cfg| cfg.path.as_path()))
+            .map(|path| {
+                let contents = std::fs::read_to_string(path).unwrap_or_default();
+                (path.to_path_buf(), SpanIndex::new(&contents))
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// Creates a diagnostic located on the first of the files where the given lookup succeeds,
+    /// or on the configuration file itself without any location
+    fn diagnostic(
+        &self,
+        lookup: impl Fn(&SpanIndex) -> Option<Span>,
+        message: String,
+    ) -> Diagnostic {
+        let located = self
+            .files
+            .iter()
+            .find_map(|(path, spans)| lookup(spans).map(|span| (path, span)));
+        match located {
+            Some((file, span)) => Diagnostic {
+                file: file.clone(),
+                span: Some(span),
+                message,
+            },
+            None => Diagnostic {
+                file: self.files[0].0.clone(),
+                span: None,
+                message,
+            },
+        }
+    }
+}
+
+/// A parsed configuration file to be validated, along with the root of its sources
+pub struct ConfigFileUnit<'a> {
+    pub config: &'a ZorkConfigFile<'a>,
+    pub code_root: PathBuf,
+    pub sources: &'a SourceFiles,
+}
+
+/// Reports all the given diagnostics as a single error, if there is any
+pub fn report(diagnostics: Vec<Diagnostic>) -> Result<()> {
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+
+    let details = diagnostics
+        .iter()
+        .map(|diagnostic| format!("  {diagnostic}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(eyre!(
+        "Found {} problem(s) on the configuration files:\n{details}",
+        diagnostics.len()
+    ))
+}
+
+/// Checks that every key of the given (resolved) configuration file is an attribute of the
+/// table where it's declared. Syntax errors are left to be reported by the deserialization
+pub fn check_unknown_keys(raw: &str, sources: &SourceFiles) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let Ok(table) = toml::from_str::<Table>(raw) else {
+        return diagnostics;
+    };
+
+    let mut checker = KeysChecker {
+        sources,
+        diagnostics: &mut diagnostics,
+    };
+    let root = TablePath::default();
+    checker.check(&table, &root, fields_of::<ZorkConfigFile>());
+
+    for (key, value) in &table {
+        let path = root.key(key);
+        match (key.as_str(), value) {
+            ("project", Value::Table(t)) => {
+                checker.check(t, &path, fields_of::<ProjectAttribute>())
+            }
+            ("compiler", Value::Table(t)) => {
+                checker.check(t, &path, fields_of::<CompilerAttribute>())
+            }
+            ("build", Value::Table(t)) => checker.check(t, &path, fields_of::<BuildAttribute>()),
+            ("toolchain", Value::Table(t)) => {
+                checker.check(t, &path, fields_of::<ToolchainAttribute>())
+            }
+            ("workspace", Value::Table(t)) => {
+                checker.check(t, &path, fields_of::<WorkspaceAttribute>())
+            }
+            ("modules", Value::Table(t)) => checker.check_modules(t, &path),
+            ("generators", Value::Array(generators)) => {
+                for (idx, generator) in tables_of(generators) {
+                    checker.check(
+                        generator,
+                        &root.entry(key, idx),
+                        fields_of::<GeneratorAttribute>(),
+                    );
+                }
+            }
+            ("targets", Value::Table(targets)) => {
+                for (name, target) in targets {
+                    if let Value::Table(target) = target {
+                        checker.check_target(target, &path.key(name));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    diagnostics
+}
+
+/// The entries of an array that are tables, along with their index
+fn tables_of(array: &[Value]) -> impl Iterator<Item = (usize, &Table)> {
+    array
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, value)| value.as_table().map(|table| (idx, table)))
+}
+
+/// The path of a table from the root of a configuration file, where every key may be an array
+/// holding the table on one of its entries
+#[derive(Debug, Clone, Default)]
+struct TablePath<'k>(Vec<(&'k str, Option<usize>)>);
+
+impl<'k> TablePath<'k> {
+    /// The path of the table held by the given key of this one
+    fn key(&self, key: &'k str) -> Self {
+        let mut path = self.clone();
+        path.0.push((key, None));
+        path
+    }
+
+    /// The path of the table held on the given entry of the array of the given key of this one
+    fn entry(&self, key: &'k str, idx: usize) -> Self {
+        let mut path = self.clone();
+        path.0.push((key, Some(idx)));
+        path
+    }
+
+    /// The path of the given key of this table, optionally matching any entry of the arrays
+    /// instead of the exact ones (see [`SpanIndex`])
+    fn to_key(&self, key: &'k str, any_entry: bool) -> Vec<String> {
+        let mut segments = Vec::new();
+        for (table, idx) in &self.0 {
+            segments.push(table.to_string());
+            match idx {
+                Some(_) if any_entry => segments.push(String::from(ANY_ENTRY)),
+                Some(idx) => segments.push(idx.to_string()),
+                None => {}
+            }
+        }
+        segments.push(key.to_string());
+        segments
+    }
+}
+
+impl fmt::Display for TablePath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self.0.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        write!(f, "{}", keys.join("."))
+    }
+}
+
+struct KeysChecker<'a> {
+    sources: &'a SourceFiles,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl<'a> KeysChecker<'a> {
+    fn check(&mut self, table: &Table, path: &TablePath, fields: &[&str]) {
+        for key in table.keys() {
+            if fields.contains(&key.as_str()) || key == REPLACE_MARKER {
+                continue;
+            }
+
+            let location = if path.0.is_empty() {
+                String::from("the root of the configuration file")
+            } else {
+                format!("[{path}]")
+            };
+            let mut message = format!("Unknown attribute `{key}` on {location}");
+            if let Some(suggestion) = closest_field(key, fields) {
+                message.push_str(&format!(", did you mean `{suggestion}`?"));
+            }
+
+            // The entries of the arrays of a configuration file may be placed on another index
+            // once the inherited ones are merged, so any entry holding the key is a good guess
+            let exact = path.to_key(key, false);
+            let any_entry = path.to_key(key, true);
+            let diagnostic = self.sources.diagnostic(
+                |spans| spans.key(&exact).or_else(|| spans.key(&any_entry)),
+                message,
+            );
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn check_modules(&mut self, modules: &Table, path: &TablePath) {
+        self.check(modules, path, fields_of::<ModulesAttribute>());
+
+        let entries = |key: &str| {
+            modules
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|array| tables_of(array))
+                .into_iter()
+                .flatten()
+        };
+        for (idx, interface) in entries("interfaces") {
+            let interface_path = path.entry("interfaces", idx);
+            self.check(interface, &interface_path, fields_of::<ModuleInterface>());
+            if let Some(Value::Table(partition)) = interface.get("partition") {
+                self.check(
+                    partition,
+                    &interface_path.key("partition"),
+                    fields_of::<ModulePartition>(),
+                );
+            }
+        }
+        for (idx, implementation) in entries("implementations") {
+            self.check(
+                implementation,
+                &path.entry("implementations", idx),
+                fields_of::<ModuleImplementation>(),
+            );
+        }
+    }
+
+    fn check_target(&mut self, target: &Table, path: &TablePath) {
+        self.check(target, path, fields_of::<TargetAttribute>());
+
+        let sources = target.get("sources").and_then(Value::as_array);
+        for (idx, source) in sources.map(|array| tables_of(array)).into_iter().flatten() {
+            self.check(
+                source,
+                &path.entry("sources", idx),
+                fields_of::<SourceFileAttribute>(),
+            );
+        }
+        if let Some(Value::Table(modules)) = target.get("modules") {
+            self.check_modules(modules, &path.key("modules"));
+        }
+    }
+}
+
+/// Checks the declarations of the given configuration files, which are built together, so
+/// the modules of any of them are available for all of them
+pub fn check_config_files(units: &[ConfigFileUnit]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut provided_modules: Vec<(String, &Path)> = Vec::new();
+
+    for unit in units {
+        check_missing_files(unit, &mut diagnostics);
+        check_conflicting_outputs(unit, &mut diagnostics);
+
+        for (modules_path, modules) in modules_attributes(unit.config) {
+            for interface in modules.interfaces.iter().flatten() {
+                let Some(name) = provided_module_name(interface) else {
+                    continue;
+                };
+                if let Some((_, first)) = provided_modules.iter().find(|(n, _)| n.eq(&name)) {
+                    let message = format!(
+                        "The module `{name}` is provided by more than one interface (first declared on {})",
+                        first.display()
+                    );
+                    let interfaces = [modules_path.as_slice(), &["interfaces"]].concat();
+                    diagnostics.push(unit.sources.diagnostic(
+                        |spans| spans.declared_file(&interfaces, interface.file),
+                        message,
+                    ));
+                } else {
+                    provided_modules.push((name, &unit.sources.files[0].0));
+                }
+            }
+        }
+    }
+
+    let any_globbed_interface = units
+        .iter()
+        .flat_map(|unit| modules_attributes(unit.config))
+        .flat_map(|(_, modules)| modules.interfaces.iter().flatten())
+        .any(|interface| interface.file.contains('*'));
+    if !any_globbed_interface {
+        let names = provided_modules
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        for unit in units {
+            check_undeclared_dependencies(unit, &names, &mut diagnostics);
+        }
+    }
+
+    diagnostics
+}
+
+/// The `[modules]` table of a configuration file, and the ones owned by its targets, along
+/// with their path from the root of the configuration file
+fn modules_attributes<'a>(
+    config: &'a ZorkConfigFile<'a>,
+) -> impl Iterator<Item = (Vec<&'a str>, &'a ModulesAttribute<'a>)> {
+    config
+        .modules
+        .iter()
+        .map(|modules| (vec!["modules"], modules))
+        .chain(config.targets.iter().filter_map(|(name, target)| {
+            let modules = target.modules.as_ref()?;
+            Some((vec!["targets", *name, "modules"], modules))
+        }))
+}
+
+/// The module provided by an interface declared one by one, following the same rules that
+/// the project model does
+fn provided_module_name(interface: &ModuleInterface) -> Option<String> {
+    if interface.file.contains('*') || interface.exclude_from_build.unwrap_or_default() {
+        return None;
+    }
+    let file_stem = Path::new(interface.file).file_stem()?.to_string_lossy();
+
+    Some(match &interface.partition {
+        Some(partition) => format!(
+            "{}:{}",
+            partition.module,
+            partition.partition_name.unwrap_or(&file_stem)
+        ),
+        None => interface.module_name.unwrap_or(&file_stem).to_string(),
+    })
+}
+
+fn check_missing_files(unit: &ConfigFileUnit, diagnostics: &mut Vec<Diagnostic>) {
+    let config = unit.config;
+    let code_root = &unit.code_root;
+    // The declarations are located by the path of the array holding them, or by the path of
+    // their key, for the ones declared as a single value
+    let mut check = |declared: &str, base_dir: Option<&str>, at: &[&str], what: &str| {
+        // The globs may match no file at all, and the runtime variables are expanded later
+        if declared.contains('*') || declared.contains("${") {
+            return;
+        }
+        let path = get_file_path(code_root, base_dir.map(Path::new), declared);
+        if !path.exists() {
+            let message = format!("The {what} {path:?} doesn't exist");
+            diagnostics.push(unit.sources.diagnostic(
+                |spans| {
+                    spans
+                        .declared_file(at, declared)
+                        .or_else(|| spans.value(at, declared))
+                },
+                message,
+            ));
+        }
+    };
+
+    for (modules_path, modules) in modules_attributes(config) {
+        let at = |key: &'static str| [modules_path.as_slice(), &[key]].concat();
+        for interface in modules.interfaces.iter().flatten() {
+            if !interface.exclude_from_build.unwrap_or_default() {
+                check(
+                    interface.file,
+                    modules.base_ifcs_dir,
+                    &at("interfaces"),
+                    "module interface",
+                );
+            }
+        }
+        for implementation in modules.implementations.iter().flatten() {
+            if !implementation.exclude_from_build.unwrap_or_default() {
+                check(
+                    implementation.file,
+                    modules.base_impls_dir,
+                    &at("implementations"),
+                    "module implementation",
+                );
+            }
+        }
+        for header_unit in modules.header_units.iter().flatten() {
+            check(header_unit, None, &at("header_units"), "header unit");
+        }
+    }
+
+    // The sources produced by the generators doesn't exist until they are run
+    let generated = config
+        .generators
+        .iter()
+        .flatten()
+        .flat_map(|generator| generator.outputs.iter())
+        .map(|output| get_file_path(code_root, None, output))
+        .collect::<Vec<_>>();
+    for (name, target) in &config.targets {
+        for source in &target.sources {
+            if source.is_excluded_from_build()
+                || generated.contains(&get_file_path(code_root, None, source.file()))
+            {
+                continue;
+            }
+            check(
+                source.file(),
+                None,
+                &["targets", name, "sources"],
+                "source file",
+            );
+        }
+        if let Some(pch) = target.pch {
+            check(pch, None, &["targets", name, "pch"], "precompiled header");
+        }
+    }
+}
+
+fn check_undeclared_dependencies(
+    unit: &ConfigFileUnit,
+    provided_modules: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let sys_modules = modules_attributes(unit.config)
+        .flat_map(|(_, modules)| modules.sys_modules.iter().flatten())
+        .copied()
+        .collect::<Vec<_>>();
+    let is_declared = |dependency: &str| {
+        STD_MODULES.contains(&dependency)
+            || sys_modules.contains(&dependency)
+            || provided_modules.iter().any(|provided| {
+                provided.eq(&dependency)
+                    // A partition imported from its own module, like `import :partition;`
+                    || (dependency.starts_with(':') && provided.ends_with(dependency))
+            })
+    };
+
+    for (modules_path, modules) in modules_attributes(unit.config) {
+        let dependencies = modules
+            .interfaces
+            .iter()
+            .flatten()
+            .filter(|ifc| !ifc.exclude_from_build.unwrap_or_default())
+            .flat_map(|ifc| {
+                ifc.dependencies
+                    .iter()
+                    .flatten()
+                    .map(|d| ("interfaces", *d))
+            })
+            .chain(
+                modules
+                    .implementations
+                    .iter()
+                    .flatten()
+                    .filter(|imp| !imp.exclude_from_build.unwrap_or_default())
+                    .flat_map(|imp| {
+                        imp.dependencies
+                            .iter()
+                            .flatten()
+                            .map(|d| ("implementations", *d))
+                    }),
+            );
+
+        for (declared_on, dependency) in dependencies.filter(|(_, d)| !is_declared(d)) {
+            let message = format!("The module `{dependency}` isn't declared on any interface");
+            let at = [
+                modules_path.as_slice(),
+                &[declared_on, ANY_ENTRY, "dependencies", ANY_ENTRY],
+            ]
+            .concat();
+            diagnostics.push(
+                unit.sources
+                    .diagnostic(|spans| spans.value(&at, dependency), message),
+            );
+        }
+    }
+}
+
+fn check_conflicting_outputs(unit: &ConfigFileUnit, diagnostics: &mut Vec<Diagnostic>) {
+    let mut outputs = Vec::new();
+
+    for (name, target) in &unit.config.targets {
+        let output_name = target.output_name.unwrap_or(unit.config.project.name);
+        let output = (output_name, target.kind.unwrap_or_default());
+
+        match outputs.iter().find(|(_, o)| output.eq(o)) {
+            Some((first, _)) => {
+                let message = format!(
+                    "The targets `{first}` and `{name}` produce the same output: `{output_name}`"
+                );
+                let lookup = |spans: &SpanIndex| match target.output_name {
+                    Some(_) => spans.key(&["targets", name, "output_name"]),
+                    None => spans.table(&["targets", name]),
+                };
+                diagnostics.push(unit.sources.diagnostic(lookup, message));
+            }
+            None => outputs.push((name, output)),
+        }
+    }
+}
+
+/// The segment of a path of a [`SpanIndex`] lookup that matches any entry of an array
+const ANY_ENTRY: &str = "*";
+
+/// The locations of the keys and values declared on a configuration file, by their path from the
+/// root of the file, where the entries of the arrays are identified by their index. The ones
+/// declared on the conditional sections are identified by the path where they are merged into
+/// (see [`conditionals`])
+#[derive(Debug, Default)]
+struct SpanIndex {
+    entries: Vec<SpannedEntry>,
+}
+
+#[derive(Debug)]
+struct SpannedEntry {
+    path: Vec<String>,
+    /// The location of the key, unless it's an entry of an array
+    key: Option<Span>,
+    /// The location of the header of the table, when it's declared through one
+    header: Option<Span>,
+    value: Span,
+    string: Option<String>,
+}
+
+impl SpanIndex {
+    fn new(contents: &str) -> Self {
+        let mut index = Self::default();
+        // Syntax errors are left to be reported by the deserialization
+        if let Ok(root) = toml::from_str::<SpannedValue>(contents) {
+            index.collect(contents, &root, &mut Vec::new());
+        }
+        index
+    }
+
+    fn collect(&mut self, contents: &str, value: &SpannedValue, path: &mut Vec<String>) {
+        match value {
+            SpannedValue::Table(entries) => {
+                for (key, value) in entries {
+                    path.push(key.get_ref().clone());
+                    self.push(contents, path, Some(key), value);
+                    path.pop();
+                }
+            }
+            SpannedValue::Array(values) => {
+                for (idx, value) in values.iter().enumerate() {
+                    path.push(idx.to_string());
+                    self.push(contents, path, None, value);
+                    path.pop();
+                }
+            }
+            SpannedValue::String(_) | SpannedValue::Other => {}
+        }
+    }
+
+    /// Adds the entry at the given path, and then the ones that it holds
+    fn push(
+        &mut self,
+        contents: &str,
+        path: &mut Vec<String>,
+        key: Option<&Spanned<String>>,
+        value: &Spanned<SpannedValue>,
+    ) {
+        let key_span = key.map(|key| span_at(contents, key.start()));
+        // The tables declared through a header don't have the location of their value
+        let header = key_span
+            .filter(|_| value.start() == value.end())
+            .and_then(|span| {
+                let line = contents.lines().nth(span.line - 1)?;
+                let indentation = line.len() - line.trim_start().len();
+                line.trim_start().starts_with('[').then_some(Span {
+                    line: span.line,
+                    column: indentation + 1,
+                })
+            });
+        self.entries.push(SpannedEntry {
+            path: merged_path(path),
+            key: key_span,
+            header,
+            value: span_at(contents, value.start()),
+            string: match value.get_ref() {
+                SpannedValue::String(string) => Some(string.clone()),
+                _ => None,
+            },
+        });
+        self.collect(contents, value.get_ref(), path);
+    }
+
+    /// Locates the given key
+    fn key(&self, path: &[impl AsRef<str>]) -> Option<Span> {
+        self.find(path, |_| true).and_then(|entry| entry.key)
+    }
+
+    /// Locates the header of the given table, or its key, when it isn't declared through one
+    fn table(&self, path: &[&str]) -> Option<Span> {
+        self.find(path, |_| true)
+            .and_then(|entry| entry.header.or(entry.key))
+    }
+
+    /// Locates the given string value
+    fn value(&self, path: &[&str], value: &str) -> Option<Span> {
+        self.find(path, |entry| entry.string.as_deref() == Some(value))
+            .map(|entry| entry.value)
+    }
+
+    /// Locates the given file on an array whose entries are declared as the path of the file, or
+    /// as a table holding it on its `file` key
+    fn declared_file(&self, array: &[&str], file: &str) -> Option<Span> {
+        let entry = [array, &[ANY_ENTRY]].concat();
+        self.value(&entry, file)
+            .or_else(|| self.value(&[entry.as_slice(), &["file"]].concat(), file))
+    }
+
+    fn find(
+        &self,
+        path: &[impl AsRef<str>],
+        filter: impl Fn(&SpannedEntry) -> bool,
+    ) -> Option<&SpannedEntry> {
+        self.entries.iter().find(|entry| {
+            entry.path.len() == path.len()
+                && entry.path.iter().zip(path).all(|(segment, expected)| {
+                    let expected = expected.as_ref();
+                    expected == segment
+                        || (expected == ANY_ENTRY && segment.parse::<usize>().is_ok())
+                })
+                && filter(entry)
+        })
+    }
+}
+
+/// The path where the given one is merged into, when it's declared on a conditional section
+fn merged_path(path: &[String]) -> Vec<String> {
+    let mut merged = match path {
+        [section, _, rest @ ..] if section == CONDITIONAL_SECTIONS && !rest.is_empty() => {
+            rest.to_vec()
+        }
+        _ => path.to_vec(),
+    };
+    if merged.len() > 2
+        && merged[0] == "compiler"
+        && conditionals::key_as_compiler(&merged[1]).is_some()
+    {
+        merged.remove(1);
+    }
+    merged
+}
+
+/// The line and column of the given byte offset of the contents
+fn span_at(contents: &str, offset: usize) -> Span {
+    let preceding = &contents[..offset];
+    let line_start = preceding.rfind('\n').map_or(0, |idx| idx + 1);
+    Span {
+        line: preceding.matches('\n').count() + 1,
+        column: offset - line_start + 1,
+    }
+}
+
+/// Any value of a configuration file, along with the locations of the keys and values that
+/// it holds
+#[derive(Debug)]
+enum SpannedValue {
+    Table(Vec<(Spanned<String>, Spanned<SpannedValue>)>),
+    Array(Vec<Spanned<SpannedValue>>),
+    String(String),
+    Other,
+}
+
+impl<'de> Deserialize<'de> for SpannedValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SpannedValueVisitor;
+
+        impl<'de> Visitor<'de> for SpannedValueVisitor {
+            type Value = SpannedValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("any TOML value")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(SpannedValue::String(value.to_string()))
+            }
+
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(SpannedValue::Other)
+            }
+
+            fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(SpannedValue::Other)
+            }
+
+            fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+                Ok(SpannedValue::Other)
+            }
+
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(SpannedValue::Other)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(SpannedValue::Array(values))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(key) = map.next_key()? {
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(SpannedValue::Table(entries))
+            }
+        }
+
+        deserializer.deserialize_any(SpannedValueVisitor)
+    }
+}
+
+/// Suggests the field with the closest name to an unknown key, if it seems a typo of it
+fn closest_field<'a>(key: &str, fields: &[&'a str]) -> Option<&'a str> {
+    fields
+        .iter()
+        .map(|field| (edit_distance(key, field), *field))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Returns the attributes of the given configuration file struct, as they are known by its
+/// [`Deserialize`] implementation
+pub(crate) fn fields_of<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(NamesCollector(&mut fields));
+    fields
+}
+
+/// Returns the names of the variants of the given configuration file enum, without their aliases
+#[cfg(test)]
+pub(crate) fn variants_of<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut variants: &'static [&'static str] = &[];
+    let _ = T::deserialize(NamesCollector(&mut variants));
+    variants
+}
+
+/// A [`Deserializer`] that only captures the names of the fields requested by the
+/// deserialization of a struct, or the ones of the variants of an enum
+struct NamesCollector<'f>(&'f mut &'static [&'static str]);
+
+impl<'de, 'f> Deserializer<'de> for NamesCollector<'f> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("only structs are inspected"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("only the fields are inspected"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = variants;
+        Err(de::Error::custom("only the variants are inspected"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_files(path: &Path, contents: &str) -> SourceFiles {
+        SourceFiles {
+            files: vec![(path.to_path_buf(), SpanIndex::new(contents))],
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let contents = "[project]\nname = 'app'\n\n[compiler]\ncpp_compiler = 'gcc'\n\
+            cpp_standar = '20'\n\n[targets.app]\nsources = [ { file = 'main.cpp', extra_arg = ['-g'] } ]\n\
+            outputs = 'app'";
+        let sources = source_files(Path::new("zork.toml"), contents);
+
+        let diagnostics = check_unknown_keys(contents, &sources);
+        let messages = diagnostics
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "zork.toml:6:1: Unknown attribute `cpp_standar` on [compiler], did you mean `cpp_standard`?",
+                "zork.toml:10:1: Unknown attribute `outputs` on [targets.app]",
+                "zork.toml:9:34: Unknown attribute `extra_arg` on [targets.app.sources], did you mean `extra_args`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_config_files() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::create_dir(root.join("ifc"))?;
+        std::fs::write(root.join("ifc/math.cppm"), "export module math;")?;
+        std::fs::write(root.join("ifc/math2.cppm"), "export module math;")?;
+        std::fs::write(root.join("main.cpp"), "int main() {}")?;
+
+        let contents = "[project]\nname = 'app'\n[compiler]\ncpp_compiler = 'gcc'\ncpp_standard = '20'\n\
+            [modules]\nbase_ifcs_dir = 'ifc'\ninterfaces = [\n    { file = 'math.cppm' },\n    \
+            { file = 'math2.cppm', module_name = 'math' },\n    { file = 'geometry.cppm', dependencies = ['algebra'] },\n]\n\
+            [targets.app]\nsources = ['main.cpp']\n[targets.app2]\noutput_name = 'app'\nsources = ['*.cpp']";
+        let config = crate::config_file::zork_cfg_from_file(contents)?;
+        let sources = source_files(&root.join("zork.toml"), contents);
+
+        let diagnostics = check_config_files(&[ConfigFileUnit {
+            config: &config,
+            code_root: root.to_path_buf(),
+            sources: &sources,
+        }]);
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.span.map(|s| (s.line, s.column)),
+                    &diagnostic.message,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].0, Some((11, 14)));
+        assert!(messages[0].1.contains("geometry.cppm\" doesn't exist"));
+        assert_eq!(messages[1].0, Some((16, 1)));
+        assert!(messages[1]
+            .1
+            .contains("`app` and `app2` produce the same output"));
+        assert_eq!(messages[2].0, Some((10, 14)));
+        assert!(messages[2]
+            .1
+            .starts_with("The module `math` is provided by more than one"));
+        assert_eq!(messages[3].0, Some((11, 47)));
+        assert!(messages[3].1.contains("`algebra` isn't declared"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagnostics_locate_the_declaration_of_duplicated_values() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::write(root.join("main.cpp"), "int main() {}")?;
+
+        // The offending values are repeated on other tables, where they aren't a problem
+        let contents = "[project]\nname = 'algebra'\n[compiler]\ncpp_compiler = 'gcc'\n\
+            cpp_standard = '20'\nextra_args = ['-include', 'missing.hpp']\n\
+            [modules]\ninterfaces = [\n    { file = 'missing.hpp', exclude_from_build = true },\n]\n\
+            header_units = ['missing.hpp']\nimplementations = [{ file = 'main.cpp', dependencies = ['algebra'] }]\n\
+            [targets.app]\nsources = ['main.cpp']\nextra_args = ['-DALGEBRA']\n\
+            [targets.tool]\noutput_name = 'tool'\nsources = [{ file = 'main.cpp' }, 'tool.cpp']";
+        let config = crate::config_file::zork_cfg_from_file(contents)?;
+        let sources = source_files(&root.join("zork.toml"), contents);
+
+        let diagnostics = check_config_files(&[ConfigFileUnit {
+            config: &config,
+            code_root: root.to_path_buf(),
+            sources: &sources,
+        }]);
+        let located = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.span.map(|s| (s.line, s.column)))
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 3, "{diagnostics:?}");
+        assert!(diagnostics[0]
+            .message
+            .contains("missing.hpp\" doesn't exist"));
+        assert!(diagnostics[1].message.contains("tool.cpp\" doesn't exist"));
+        assert!(diagnostics[2].message.contains("`algebra` isn't declared"));
+        assert_eq!(located, [Some((11, 17)), Some((18, 35)), Some((12, 57))]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_keys_on_array_entries_and_conditional_sections() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let cfg_path = temp.path().join("zork.toml");
+        let contents = "[project]\nname = 'app'\n[compiler]\ncpp_compiler = 'gcc'\n\
+            cpp_standard = '20'\n[compiler.gcc]\nextra_arg = ['-g']\n\
+            [targets.app]\nsources = [\n    { file = 'a.cpp', extra_arg = ['-O2'] },\n    \
+            { file = 'b.cpp', extra_arg = ['-O3'] },\n]\nextra_arg = ['-Wall']";
+        std::fs::write(&cfg_path, contents)?;
+        let resolved_file = crate::utils::inheritance::read_config_file(&cfg_path)?;
+        let sources = SourceFiles::new(&cfg_path, &resolved_file);
+
+        let mut located = check_unknown_keys(&resolved_file.raw, &sources)
+            .iter()
+            .map(|diagnostic| diagnostic.span.map(|s| (s.line, s.column)))
+            .collect::<Vec<_>>();
+        located.sort();
+        // Every declaration of the same unknown key is located on its own table, or on the
+        // entry of its array
+        assert_eq!(
+            located,
+            [Some((7, 1)), Some((10, 23)), Some((11, 23)), Some((13, 1))]
+        );
+
+        Ok(())
+    }
+}
@@ -205,6 +205,762 @@ fn test_gcc_full_process() -> Result<()> {
     Ok(tempdir.close()?)
 }
 
+#[test]
+fn test_check_doesnt_build_anything() -> Result<()> {
+    let project_name = "check_example";
+
+    let tempdir = tempdir()?;
+    let path = tempdir.path();
+    let binding = path.join(project_name);
+    let project_root = binding.to_string_lossy();
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        path.to_str().unwrap(),
+        "new",
+        project_name,
+        "--compiler",
+        "gcc",
+        "--template",
+        "basic",
+    ]))?;
+
+    let check_result =
+        zork::worker::run_zork(&CliArgs::parse_from(["", "--root", &project_root, "check"]));
+    assert!(check_result.is_ok(), "{}", check_result.unwrap_err());
+
+    let has_object_files = walkdir::WalkDir::new(binding.as_path())
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| entry.path().extension().is_some_and(|ext| ext == "o"));
+    assert!(!has_object_files);
+
+    // The cache isn't left in a state that breaks the next build
+    let build_result =
+        zork::worker::run_zork(&CliArgs::parse_from(["", "--root", &project_root, "build"]));
+    assert!(build_result.is_ok(), "{}", build_result.unwrap_err());
+
+    let cfg_path = binding.join("zork_gcc.toml");
+    let cfg = std::fs::read_to_string(&cfg_path)?;
+    std::fs::write(&cfg_path, cfg.replace("sources", "source"))?;
+
+    let check_result =
+        zork::worker::run_zork(&CliArgs::parse_from(["", "--root", &project_root, "check"]));
+    let err = format!("{:#}", check_result.unwrap_err());
+    assert!(err.contains("The check failed for 1 of 1 configuration files"));
+    assert!(err.contains("Unknown attribute `source`"));
+
+    Ok(tempdir.close()?)
+}
+
+#[test]
+fn test_dry_run_doesnt_build_anything() -> Result<()> {
+    let project_name = "dry_run_example";
+
+    let tempdir = tempdir()?;
+    let path = tempdir.path();
+    let binding = path.join(project_name);
+    let project_root = binding.to_string_lossy();
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        path.to_str().unwrap(),
+        "new",
+        project_name,
+        "--compiler",
+        "gcc",
+        "--template",
+        "basic",
+    ]))?;
+
+    let dry_run_result = zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        &project_root,
+        "--dry-run",
+        "build",
+    ]));
+    assert!(dry_run_result.is_ok(), "{}", dry_run_result.unwrap_err());
+
+    let has_build_products = walkdir::WalkDir::new(binding.as_path())
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "o" || ext == "json")
+        });
+    assert!(!has_build_products);
+
+    // Nothing was recorded as built, so the next build starts from scratch
+    let build_result =
+        zork::worker::run_zork(&CliArgs::parse_from(["", "--root", &project_root, "build"]));
+    assert!(build_result.is_ok(), "{}", build_result.unwrap_err());
+
+    Ok(tempdir.close()?)
+}
+
+/// Writes a fake compiler driver, that logs its invocations, fails for the files whose name
+/// contains `broken`, and creates the outputs of the rest of them
+#[cfg(unix)]
+fn write_fake_driver(root: &std::path::Path) -> Result<std::path::PathBuf> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let driver = root.join("fake_cxx.sh");
+    fs::write(
+        &driver,
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{}"
+case "$*" in *broken*) exit 1 ;; esac
+while [ "$#" -gt 0 ]; do
+    if [ "$1" = "-o" ]; then touch "$2"; fi
+    shift
+done
+"#,
+            root.join("invocations.log").display()
+        ),
+    )?;
+    fs::set_permissions(&driver, fs::Permissions::from_mode(0o755))?;
+
+    Ok(driver)
+}
+
+/// The invocations of the fake driver since the last call, removing them from the log
+#[cfg(unix)]
+fn take_invocations(root: &std::path::Path) -> Result<String> {
+    let log = root.join("invocations.log");
+    let invocations = std::fs::read_to_string(&log).unwrap_or_default();
+    std::fs::write(&log, "")?;
+    Ok(invocations)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cross_target_commands() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let driver = write_fake_driver(root)?;
+    std::fs::write(
+        root.join("zork.toml"),
+        format!(
+            r#"
+            [project]
+            name = "cross"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+
+            [toolchain]
+            target = "x86_64-w64-mingw32"
+            sysroot = "/usr/x86_64-w64-mingw32"
+            linker = "lld"
+
+            [targets.executable]
+            sources = ["main.cpp"]
+            "#,
+            driver.display()
+        ),
+    )?;
+    std::fs::write(root.join("main.cpp"), "int main() {}\n")?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "build",
+    ]))?;
+    let invocations = take_invocations(root)?;
+    let main_cpp = root.join("main.cpp").display().to_string();
+    let (source, link): (Vec<&str>, Vec<&str>) = invocations
+        .lines()
+        .partition(|line| line.split_whitespace().any(|arg| arg.eq(&main_cpp)));
+
+    assert_eq!(source.len(), 1, "{invocations}");
+    assert!(source[0].contains("--sysroot=/usr/x86_64-w64-mingw32"));
+    assert!(!source[0].contains("-fuse-ld=lld"));
+
+    // The products of the cross builds are placed under the triple, and the binaries get the
+    // extension of the targeted platform
+    assert_eq!(link.len(), 1, "{invocations}");
+    assert!(link[0].contains("--sysroot=/usr/x86_64-w64-mingw32"));
+    assert!(link[0].contains("-fuse-ld=lld"));
+    let binary = root.join("out/x86_64-w64-mingw32/gcc/executable.exe");
+    assert!(link[0].contains(&binary.display().to_string()));
+
+    Ok(tempdir.close()?)
+}
+
+/// Writes a fake compiler launcher, that logs its invocations prefixed with its name, and then
+/// invokes the command that it wraps
+#[cfg(unix)]
+fn write_fake_launcher(root: &std::path::Path, name: &str) -> Result<std::path::PathBuf> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let launcher = root.join(name);
+    fs::write(
+        &launcher,
+        format!(
+            "#!/bin/sh\necho \"{name} $@\" >> \"{}\"\nexec \"$@\"\n",
+            root.join("invocations.log").display()
+        ),
+    )?;
+    fs::set_permissions(&launcher, fs::Permissions::from_mode(0o755))?;
+
+    Ok(launcher)
+}
+
+/// Writes a GCC project, built with the fake compiler driver through the given launcher
+#[cfg(unix)]
+fn write_launched_project(root: &std::path::Path, launcher: &std::path::Path) -> Result<()> {
+    use std::fs;
+
+    let driver = write_fake_driver(root)?;
+    fs::write(
+        root.join("zork.toml"),
+        format!(
+            r#"
+            [project]
+            name = "launched"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+            launcher = "{}"
+
+            [targets.executable]
+            sources = ["main.cpp"]
+
+            [modules]
+            interfaces = [{{ file = "math.cppm" }}]
+            implementations = [{{ file = "math.cpp", dependencies = ["math"] }}]
+            "#,
+            driver.display(),
+            launcher.display()
+        ),
+    )?;
+
+    let files = [
+        (
+            "main.cpp",
+            "import math;\nint main() { return add(1, 2); }\n",
+        ),
+        (
+            "math.cppm",
+            "export module math;\nexport int add(int a, int b);\n",
+        ),
+        (
+            "math.cpp",
+            "module math;\nint add(int a, int b) { return a + b; }\n",
+        ),
+    ];
+    for (file, contents) in files {
+        fs::write(root.join(file), contents)?;
+    }
+
+    Ok(())
+}
+
+/// The files of the project that were built through the given launcher
+#[cfg(unix)]
+fn launched_files(root: &std::path::Path, invocations: &str, launcher: &str) -> Vec<String> {
+    invocations
+        .lines()
+        .filter_map(|line| line.strip_prefix(&format!("{launcher} ")))
+        // Skipping the compiler driver wrapped by the launcher
+        .flat_map(|line| line.split_whitespace().skip(1))
+        .filter_map(|arg| std::path::Path::new(arg).strip_prefix(root).ok())
+        .filter(|file| file.parent().is_some_and(|dir| dir.as_os_str().is_empty()))
+        .map(|file| file.display().to_string())
+        .collect()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_launcher_prefixes_the_compile_commands() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let launcher = write_fake_launcher(root, "ccache")?;
+    write_launched_project(root, &launcher)?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "build",
+    ]))?;
+
+    // Neither the module interfaces nor the linker are invoked through the launcher
+    let invocations = take_invocations(root)?;
+    assert_eq!(
+        launched_files(root, &invocations, "ccache"),
+        ["math.cpp", "main.cpp"],
+        "{invocations}"
+    );
+
+    Ok(tempdir.close()?)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_launcher_selected_on_the_cli_overrides_the_declared_one() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let declared_launcher = write_fake_launcher(root, "ccache")?;
+    let cli_launcher = write_fake_launcher(root, "sccache")?;
+    write_launched_project(root, &declared_launcher)?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "--launcher",
+        cli_launcher.to_str().unwrap(),
+        "build",
+    ]))?;
+
+    let invocations = take_invocations(root)?;
+    assert_eq!(
+        launched_files(root, &invocations, "sccache"),
+        ["math.cpp", "main.cpp"],
+        "{invocations}"
+    );
+    assert!(launched_files(root, &invocations, "ccache").is_empty());
+
+    Ok(tempdir.close()?)
+}
+
+/// The invocation of the fake compiler driver that received the given file as an argument
+#[cfg(unix)]
+fn invocation_with<'i>(invocations: &'i str, file: &std::path::Path) -> &'i str {
+    let file = file.display().to_string();
+    invocations
+        .lines()
+        .find(|line| line.split_whitespace().any(|arg| arg.eq(&file)))
+        .unwrap_or_else(|| panic!("No invocation with {file} in {invocations}"))
+}
+
+#[cfg(unix)]
+#[test]
+fn test_template_targets_dont_share_their_main() -> Result<()> {
+    let tempdir = tempdir()?;
+    let path = tempdir.path();
+    let project_root = path.join("template");
+    let driver = write_fake_driver(path)?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        path.to_str().unwrap(),
+        "new",
+        "template",
+        "--compiler",
+        "gcc",
+        "--template",
+        "basic",
+    ]))?;
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        project_root.to_str().unwrap(),
+        "--driver-path",
+        driver.to_str().unwrap(),
+        "build",
+    ]))?;
+
+    let invocations = take_invocations(path)?;
+    let main = project_root.join("main.cpp").display().to_string();
+    let tests_main = project_root
+        .join("test/tests_main.cpp")
+        .display()
+        .to_string();
+    let compiled = |file: &str| {
+        invocations
+            .lines()
+            .filter(|line| line.split_whitespace().any(|arg| arg.eq(file)))
+            .count()
+    };
+    assert_eq!(compiled(&main), 1, "{invocations}");
+    assert_eq!(compiled(&tests_main), 1, "{invocations}");
+
+    Ok(tempdir.close()?)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_compile_and_link_args_are_kept_apart() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let driver = write_fake_driver(root)?;
+    std::fs::write(
+        root.join("zork.toml"),
+        format!(
+            r#"
+            [project]
+            name = "args"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+            compile_args = ["-DCOMPILER_COMPILE"]
+            extra_args = ["-DCOMPILER_EXTRA"]
+            link_args = ["-Wl,--compiler-link"]
+
+            [targets.executable]
+            sources = ["main.cpp"]
+            compile_args = ["-DTARGET_COMPILE"]
+            extra_args = ["-DTARGET_EXTRA"]
+            link_args = ["-Wl,--target-link"]
+
+            [modules]
+            interfaces = [{{ file = "math.cppm" }}]
+            "#,
+            driver.display()
+        ),
+    )?;
+    std::fs::write(root.join("main.cpp"), "import math;\nint main() {}\n")?;
+    std::fs::write(root.join("math.cppm"), "export module math;\n")?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "build",
+    ]))?;
+    let invocations = take_invocations(root)?;
+    let binary = root.join("out/gcc/executable").display().to_string();
+    let (link, compile_lines): (Vec<&str>, Vec<&str>) = invocations
+        .lines()
+        .partition(|line| line.contains(&format!("-o {binary} ")));
+    let compile_args = ["-DCOMPILER_COMPILE", "-DTARGET_COMPILE"];
+    let link_args = ["-Wl,--compiler-link", "-Wl,--target-link"];
+
+    assert_eq!(compile_lines.len(), 2, "{invocations}");
+    for line in compile_lines {
+        assert!(line.contains("-DCOMPILER_COMPILE"), "{line}");
+        assert!(line.contains("-DCOMPILER_EXTRA"), "{line}");
+        assert!(link_args.iter().all(|arg| !line.contains(arg)), "{line}");
+    }
+    // The ones of the target are only sent to the compile commands of its sources
+    let main_cpp = invocation_with(&invocations, &root.join("main.cpp"));
+    assert!(main_cpp.contains("-DTARGET_COMPILE -DTARGET_EXTRA"));
+    let math_cppm = invocation_with(&invocations, &root.join("math.cppm"));
+    assert!(!math_cppm.contains("-DTARGET_COMPILE"));
+    assert!(!math_cppm.contains("-DTARGET_EXTRA"));
+
+    assert_eq!(link.len(), 1, "{invocations}");
+    // The deprecated `extra_args` are still sent to both kinds of command lines
+    assert!(link[0].contains("-DCOMPILER_EXTRA"), "{}", link[0]);
+    assert!(link[0].contains("-DTARGET_EXTRA"), "{}", link[0]);
+    assert!(link[0].ends_with("-Wl,--compiler-link -Wl,--target-link"));
+    assert!(
+        compile_args.iter().all(|arg| !link[0].contains(arg)),
+        "{}",
+        link[0]
+    );
+
+    Ok(tempdir.close()?)
+}
+
+/// Writes a GCC project that builds with the fake compiler driver. The module interface of `math`
+/// and the precompiled header of the `tool` target are broken, while `other.cpp` doesn't
+/// depend on any of them
+#[cfg(unix)]
+fn write_project_with_broken_units(root: &std::path::Path) -> Result<()> {
+    use std::fs;
+
+    let driver = write_fake_driver(root)?;
+    fs::write(
+        root.join("zork.toml"),
+        format!(
+            r#"
+            [project]
+            name = "skips"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+
+            [targets.app]
+            output_name = "app"
+            sources = ["main.cpp", "other.cpp"]
+
+            [targets.tool]
+            output_name = "tool"
+            sources = ["tool.cpp"]
+            pch = "broken.hpp"
+
+            [modules]
+            interfaces = [{{ file = "broken_math.cppm", module_name = "math" }}]
+            implementations = [{{ file = "math.cpp", dependencies = ["math"] }}]
+            "#,
+            driver.display()
+        ),
+    )?;
+
+    let files = [
+        ("main.cpp", "import math;\nint main() {}\n"),
+        ("other.cpp", "void other() {}\n"),
+        ("tool.cpp", "int main() {}\n"),
+        ("broken.hpp", "#pragma once\n"),
+        ("broken_math.cppm", "export module math;\n"),
+        ("math.cpp", "module math;\n"),
+    ];
+    for (file, contents) in files {
+        fs::write(root.join(file), contents)?;
+    }
+
+    Ok(())
+}
+
+/// Whether the fake compiler driver was invoked with the given file of the project
+#[cfg(unix)]
+fn was_invoked_with(root: &std::path::Path, file: &str) -> Result<bool> {
+    let invocations = std::fs::read_to_string(root.join("invocations.log"))?;
+    let file = root.join(file).display().to_string();
+    Ok(invocations
+        .split_whitespace()
+        .any(|arg| arg.eq(&file) || arg.ends_with(&format!("include={file}"))))
+}
+
+#[cfg(unix)]
+#[test]
+fn test_build_stops_on_the_first_failure() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    write_project_with_broken_units(root)?;
+
+    let build_result = zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "build",
+    ]));
+    let err = format!("{:#}", build_result.unwrap_err());
+    assert!(err.contains("Ending the program, because the build of: \"broken_math.cppm\" failed"));
+
+    assert!(was_invoked_with(root, "broken_math.cppm")?);
+    for file in ["math.cpp", "main.cpp", "other.cpp", "tool.cpp"] {
+        assert!(!was_invoked_with(root, file)?, "{file} was built");
+    }
+
+    Ok(tempdir.close()?)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keep_going_skips_the_dependents_of_the_failures() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    write_project_with_broken_units(root)?;
+
+    let build_result = zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "--keep-going",
+        "build",
+    ]));
+    let err = format!("{:#}", build_result.unwrap_err());
+
+    // The units that don't depend on the failed ones are still built
+    assert!(was_invoked_with(root, "other.cpp")?);
+    // But the ones that import the failed module, or use the failed precompiled header, aren't
+    for file in ["math.cpp", "main.cpp", "tool.cpp"] {
+        assert!(!was_invoked_with(root, file)?, "{file} was built");
+    }
+
+    // And all of them are reported together
+    let listed_under = |title: &str| -> Vec<String> {
+        err.split_once(title)
+            .map(|(_, rest)| {
+                rest.lines()
+                    .skip(1)
+                    .map_while(|line| line.strip_prefix("\t- ").map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let in_root = |files: &[&str]| -> Vec<String> {
+        files
+            .iter()
+            .map(|file| root.join(file).display().to_string())
+            .collect()
+    };
+    assert_eq!(
+        listed_under("Failed translation units:"),
+        in_root(&["broken_math.cppm", "broken.hpp"]),
+        "{err}"
+    );
+    assert_eq!(
+        listed_under("Skipped translation units, because their dependencies failed:"),
+        in_root(&["math.cpp", "main.cpp", "tool.cpp"]),
+        "{err}"
+    );
+    assert_eq!(listed_under("Failed targets:"), ["app", "tool"], "{err}");
+
+    Ok(tempdir.close()?)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_changes_on_the_profile_and_the_environment_rebuild_the_model() -> Result<()> {
+    const FLAVOUR: &str = "ZORK_TEST_PROFILES_FLAVOUR";
+
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let driver = write_fake_driver(root)?;
+    std::fs::write(
+        root.join("zork.toml"),
+        format!(
+            r#"
+            [project]
+            name = "profiles"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+
+            [targets.app]
+            sources = ["main.cpp"]
+            compile_args = ["-DPROFILE_${{profile}}", "-DFLAVOUR_${{env:{FLAVOUR}}}"]
+            "#,
+            driver.display()
+        ),
+    )?;
+    std::fs::write(root.join("main.cpp"), "int main() {}\n")?;
+
+    let build = |profile: &str| {
+        zork::worker::run_zork(&CliArgs::parse_from([
+            "",
+            "--root",
+            root.to_str().unwrap(),
+            "--profile",
+            profile,
+            "build",
+        ]))
+    };
+    let main_cpp = root.join("main.cpp").display().to_string();
+    let main_cpp_built_with = |invocations: &str| -> Vec<String> {
+        invocations
+            .lines()
+            .filter(|line| line.split_whitespace().any(|arg| arg.eq(&main_cpp)))
+            .flat_map(|line| line.split_whitespace())
+            .filter(|arg| arg.starts_with("-DPROFILE_") || arg.starts_with("-DFLAVOUR_"))
+            .map(String::from)
+            .collect()
+    };
+
+    std::env::set_var(FLAVOUR, "vanilla");
+    build("debug")?;
+    assert_eq!(
+        main_cpp_built_with(&take_invocations(root)?),
+        ["-DPROFILE_debug", "-DFLAVOUR_vanilla"]
+    );
+
+    // Nothing changed, so the cached model and commands are reused
+    build("debug")?;
+    assert!(main_cpp_built_with(&take_invocations(root)?).is_empty());
+
+    // A different profile, without any change on the configuration file, rebuilds the model
+    build("release")?;
+    assert_eq!(
+        main_cpp_built_with(&take_invocations(root)?),
+        ["-DPROFILE_release", "-DFLAVOUR_vanilla"]
+    );
+    build("release")?;
+    assert!(main_cpp_built_with(&take_invocations(root)?).is_empty());
+
+    // As well as a different value of the environment variables read by the configuration file
+    std::env::set_var(FLAVOUR, "chocolate");
+    build("release")?;
+    assert_eq!(
+        main_cpp_built_with(&take_invocations(root)?),
+        ["-DPROFILE_release", "-DFLAVOUR_chocolate"]
+    );
+    build("release")?;
+    assert!(main_cpp_built_with(&take_invocations(root)?).is_empty());
+
+    Ok(tempdir.close()?)
+}
+
+#[cfg(unix)]
+#[test]
+fn test_workspace_members_expand_their_own_variables() -> Result<()> {
+    let tempdir = tempdir()?;
+    let root = tempdir.path();
+    let driver = write_fake_driver(root)?;
+    std::fs::create_dir_all(root.join("member"))?;
+    let project = |name: &str, tables: &str| {
+        format!(
+            r#"
+            [project]
+            name = "{name}"
+
+            [compiler]
+            cpp_compiler = "gcc"
+            cpp_standard = "20"
+            driver_path = "{}"
+
+            [build]
+            output_dir = "build_of_${{project_name}}"
+
+            {tables}
+            "#,
+            driver.display()
+        )
+    };
+    std::fs::write(
+        root.join("zork.toml"),
+        project(
+            "root",
+            "[targets.app]\nsources = [\"main.cpp\"]\ncompile_args = [\"-DNAME_${project_name}\"]\n\
+            [workspace]\nmembers = [\"member\"]",
+        ),
+    )?;
+    std::fs::write(
+        root.join("member").join("zork.toml"),
+        project(
+            "lib",
+            "[targets.lib]\nsources = [\"lib.cpp\"]\n\
+            compile_args = [\"-DNAME_${project_name}\", \"-DOUT_${out_dir}\"]",
+        ),
+    )?;
+    std::fs::write(root.join("main.cpp"), "int main() {}\n")?;
+    std::fs::write(root.join("member").join("lib.cpp"), "void lib() {}\n")?;
+
+    zork::worker::run_zork(&CliArgs::parse_from([
+        "",
+        "--root",
+        root.to_str().unwrap(),
+        "build",
+    ]))?;
+    let invocations = take_invocations(root)?;
+
+    let main_cpp = invocation_with(&invocations, &root.join("main.cpp"));
+    assert!(main_cpp.contains(" -DNAME_root "), "{main_cpp}");
+    let lib_cpp = invocation_with(&invocations, &root.join("member").join("lib.cpp"));
+    assert!(lib_cpp.contains(" -DNAME_lib "), "{lib_cpp}");
+    let member_out_dir = root.join("member").join("build_of_lib");
+    assert!(
+        lib_cpp.contains(&format!(" -DOUT_{} ", member_out_dir.display())),
+        "{lib_cpp}"
+    );
+
+    Ok(tempdir.close()?)
+}
+
 mod local_env_tests {
     use super::*;
     use std::env;